@@ -0,0 +1,117 @@
+//! Encoders and decoders for fixed-size arrays.
+use crate::{ByteCount, Decode, Eos, ErrorKind, Result};
+
+/// Decoder for fixed-size arrays.
+///
+/// This decodes exactly `N` items by using the inner decoder and returns them as `[D::Item; N]`.
+/// Unlike `DecodeExt::collectn`, no heap allocation is required for collecting the items.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::array::ArrayDecoder;
+/// use bytecodec::fixnum::U8Decoder;
+///
+/// let mut decoder = ArrayDecoder::<_, 3>::new(U8Decoder::new());
+/// let item = decoder.decode_from_bytes(b"foo").unwrap();
+/// assert_eq!(item, *b"foo");
+/// ```
+#[derive(Debug)]
+pub struct ArrayDecoder<D: Decode, const N: usize> {
+    inner: D,
+    items: [Option<D::Item>; N],
+    decoded_items: usize,
+}
+impl<D: Decode, const N: usize> ArrayDecoder<D, N> {
+    /// Makes a new `ArrayDecoder` instance.
+    pub fn new(inner: D) -> Self {
+        ArrayDecoder {
+            inner,
+            items: std::array::from_fn(|_| None),
+            decoded_items: 0,
+        }
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Decode + Default, const N: usize> Default for ArrayDecoder<D, N> {
+    fn default() -> Self {
+        Self::new(D::default())
+    }
+}
+impl<D: Decode, const N: usize> Decode for ArrayDecoder<D, N> {
+    type Item = [D::Item; N];
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        while self.decoded_items < N {
+            if !self.inner.is_idle() {
+                offset += track!(self.inner.decode(&buf[offset..], eos))?;
+                if !self.inner.is_idle() {
+                    break;
+                }
+            }
+
+            let item = track!(self.inner.finish_decoding())?;
+            self.items[self.decoded_items] = Some(item);
+            self.decoded_items += 1;
+        }
+        if self.decoded_items != N {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.decoded_items, N);
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert_eq!(self.decoded_items, N, ErrorKind::IncompleteDecoding);
+        self.decoded_items = 0;
+        Ok(std::array::from_fn(|i| {
+            self.items[i].take().expect("Never fails")
+        }))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.decoded_items == N {
+            ByteCount::Finite(0)
+        } else {
+            self.inner.requiring_bytes()
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.decoded_items == N
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixnum::U16beDecoder;
+    use crate::io::IoDecodeExt;
+
+    #[test]
+    fn array_decoder_works() {
+        let mut decoder = ArrayDecoder::<_, 3>::new(U16beDecoder::new());
+        let mut input = &[0, 1, 0x12, 0x34, 0xff, 0xff, 0, 2][..];
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, [1, 0x1234, 0xffff]);
+        assert_eq!(input, [0, 2]);
+
+        let error = decoder.decode(input, Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+}
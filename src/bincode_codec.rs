@@ -1,9 +1,11 @@
 //! `#[cfg(feature = "bincode_codec")]` Bincode encoder and decoder that use [bincode] internally.
 //!
 //! [bincode]: https://crates.io/crates/bincode
+use crate::fixnum::U64leDecoder;
 use crate::monolithic::{MonolithicDecode, MonolithicDecoder, MonolithicEncode, MonolithicEncoder};
 use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result};
 use serde::{Deserialize, Serialize};
+use std::cmp;
 use std::io::{Read, Write};
 use std::marker::PhantomData;
 use trackable::error::ErrorKindExt;
@@ -12,6 +14,11 @@ use trackable::error::ErrorKindExt;
 ///
 /// Note that this decodes items monolithically
 /// so very large items may impair real-time property of the system.
+///
+/// All the bytes of an item are buffered until the input reaches EOS,
+/// so the item needs to be delimited by EOS (e.g., by using `DecodeExt::length`),
+/// and `requiring_bytes` is unknown until then.
+/// For top-level `Vec<u8>` items, `BincodeBytesDecoder` can be used instead.
 #[derive(Debug)]
 pub struct BincodeDecoder<T>(MonolithicDecoder<MonolithicBincodeDecoder<T>>)
 where
@@ -24,6 +31,16 @@ where
     pub fn new() -> Self {
         BincodeDecoder(MonolithicDecoder::new(MonolithicBincodeDecoder::new()))
     }
+
+    /// Makes a new `BincodeDecoder` instance that buffers at most `max_buffer_bytes` bytes.
+    ///
+    /// See `MonolithicDecoder::with_max_buffer_bytes` for the details.
+    pub fn with_max_buffer_bytes(max_buffer_bytes: usize) -> Self {
+        BincodeDecoder(MonolithicDecoder::with_max_buffer_bytes(
+            MonolithicBincodeDecoder::new(),
+            max_buffer_bytes,
+        ))
+    }
 }
 impl<T> Decode for BincodeDecoder<T>
 where
@@ -81,6 +98,87 @@ where
     }
 }
 
+/// Decoder for `Vec<u8>` items serialized by bincode (with the default configuration).
+///
+/// Unlike `BincodeDecoder`, this decodes items incrementally.
+/// It reads the length prefix (little-endian `u64`) of an item first,
+/// so the number of remaining bytes is known before EOS and no extra buffering is needed.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{ByteCount, Decode, EncodeExt, Eos};
+/// use bytecodec::bincode_codec::{BincodeBytesDecoder, BincodeEncoder};
+///
+/// let mut encoder = BincodeEncoder::new();
+/// let bytes = encoder.encode_into_bytes(vec![1u8, 2, 3]).unwrap();
+///
+/// let mut decoder = BincodeBytesDecoder::new();
+/// assert_eq!(decoder.decode(&bytes[..9], Eos::new(false)).unwrap(), 9);
+/// assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(2));
+/// assert_eq!(decoder.decode(&bytes[9..], Eos::new(false)).unwrap(), 2);
+/// assert_eq!(decoder.finish_decoding().unwrap(), [1, 2, 3]);
+/// ```
+#[derive(Debug, Default)]
+pub struct BincodeBytesDecoder {
+    length: U64leDecoder,
+    bytes: Option<Vec<u8>>,
+    remaining_bytes: u64,
+}
+impl BincodeBytesDecoder {
+    /// Makes a new `BincodeBytesDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for BincodeBytesDecoder {
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.bytes.is_none() {
+            bytecodec_try_decode!(self.length, offset, buf, eos);
+            self.remaining_bytes = track!(self.length.finish_decoding())?;
+
+            // The length prefix is untrusted, so limits the initial allocation.
+            let capacity = cmp::min(self.remaining_bytes, MAX_INITIAL_CAPACITY);
+            self.bytes = Some(Vec::with_capacity(capacity as usize));
+        }
+
+        let size = cmp::min(self.remaining_bytes, (buf.len() - offset) as u64) as usize;
+        let bytes = self.bytes.as_mut().expect("Never fails");
+        bytes.extend_from_slice(&buf[offset..][..size]);
+        self.remaining_bytes -= size as u64;
+        offset += size;
+        if self.remaining_bytes != 0 {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.remaining_bytes);
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert_eq!(self.remaining_bytes, 0, ErrorKind::IncompleteDecoding);
+        let bytes = track_assert_some!(self.bytes.take(), ErrorKind::IncompleteDecoding);
+        Ok(bytes)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.bytes.is_none() {
+            self.length
+                .requiring_bytes()
+                .add_for_decoding(ByteCount::Unknown)
+        } else {
+            ByteCount::Finite(self.remaining_bytes)
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.bytes.is_some() && self.remaining_bytes == 0
+    }
+}
+
+const MAX_INITIAL_CAPACITY: u64 = 64 * 1024;
+
 /// Bincode encoder.
 ///
 /// Note that this encodes items monolithically
@@ -164,4 +262,25 @@ mod test {
         let decoded = decoder.decode_exact(&buf[..]).unwrap();
         assert_eq!(decoded, item);
     }
+
+    #[test]
+    fn bincode_bytes_decoder_works() {
+        let item = (0..100_000).map(|i| i as u8).collect::<Vec<_>>();
+        let mut encoder = BincodeEncoder::new();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(item.clone()));
+
+        let mut decoder = BincodeBytesDecoder::new();
+        let mut chunks = bytes.chunks(1000);
+        let size = track_try_unwrap!(decoder.decode(chunks.next().unwrap(), Eos::new(false)));
+        assert_eq!(size, 1000);
+        assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(99_008));
+        assert!(decoder.bytes.as_ref().unwrap().capacity() <= 64 * 1024);
+
+        for chunk in chunks {
+            let size = track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+            assert_eq!(size, chunk.len());
+        }
+        assert!(decoder.is_idle());
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), item);
+    }
 }
@@ -0,0 +1,436 @@
+//! Encoders and decoders for bit-level values.
+use crate::bytes::BytesEncoder;
+use crate::fixnum::{U32beDecoder, U8Decoder, U8Encoder};
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use std::cmp;
+
+/// Decoder which decodes up to eight boolean flags packed into a single byte.
+///
+/// The `i`-th flag corresponds to the `i`-th least significant bit of the byte.
+///
+/// # Errors
+///
+/// If the decoder is strict (the default) and any of the unused high bits is set,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::bits::BitFlagsDecoder;
+///
+/// let mut decoder = BitFlagsDecoder::<3>::new();
+/// let item = decoder.decode_from_bytes(&[0b101][..]).unwrap();
+/// assert_eq!(item, [true, false, true]);
+///
+/// assert!(decoder.decode_from_bytes(&[0b1000][..]).is_err());
+/// ```
+#[derive(Debug)]
+pub struct BitFlagsDecoder<const N: usize> {
+    inner: U8Decoder,
+    strict: bool,
+}
+impl<const N: usize> BitFlagsDecoder<N> {
+    /// Makes a new strict `BitFlagsDecoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is greater than `8`, this function will panic.
+    pub fn new() -> Self {
+        assert!(N <= 8, "Too many flags: {}", N);
+        BitFlagsDecoder {
+            inner: U8Decoder::new(),
+            strict: true,
+        }
+    }
+
+    /// Returns `true` if the decoder rejects bytes that have unused bits set, otherwise `false`.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets whether the decoder rejects bytes that have unused bits set.
+    ///
+    /// If `false` is specified, the unused bits are simply ignored.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+}
+impl<const N: usize> Default for BitFlagsDecoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> Decode for BitFlagsDecoder<N> {
+    type Item = [bool; N];
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let b = track!(self.inner.finish_decoding())?;
+        if self.strict {
+            track_assert_eq!(
+                u16::from(b) >> N,
+                0,
+                ErrorKind::InvalidInput,
+                "Unused bits are set"; b, N
+            );
+        }
+
+        let mut flags = [false; N];
+        for (i, flag) in flags.iter_mut().enumerate() {
+            *flag = b & (1 << i) != 0;
+        }
+        Ok(flags)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+
+/// Encoder which encodes up to eight boolean flags into a single byte.
+///
+/// The `i`-th flag is stored in the `i`-th least significant bit of the byte,
+/// and the unused high bits are always zero.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bits::BitFlagsEncoder;
+///
+/// let mut encoder = BitFlagsEncoder::<3>::new();
+/// let bytes = encoder.encode_into_bytes([true, false, true]).unwrap();
+/// assert_eq!(bytes, [0b101]);
+/// ```
+#[derive(Debug)]
+pub struct BitFlagsEncoder<const N: usize> {
+    inner: U8Encoder,
+}
+impl<const N: usize> BitFlagsEncoder<N> {
+    /// Makes a new `BitFlagsEncoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is greater than `8`, this function will panic.
+    pub fn new() -> Self {
+        assert!(N <= 8, "Too many flags: {}", N);
+        BitFlagsEncoder {
+            inner: U8Encoder::new(),
+        }
+    }
+}
+impl<const N: usize> Default for BitFlagsEncoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<const N: usize> Encode for BitFlagsEncoder<N> {
+    type Item = [bool; N];
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let b = item
+            .iter()
+            .enumerate()
+            .filter(|(_, &flag)| flag)
+            .fold(0, |b, (i, _)| b | (1 << i));
+        track!(self.inner.start_encoding(b))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+impl<const N: usize> SizedEncode for BitFlagsEncoder<N> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Decoder which decodes boolean vectors packed into bit vectors.
+///
+/// A bit vector consists of a 32-bit big-endian length (i.e., the number of bits)
+/// followed by `ceil(length / 8)` bytes.
+/// The `i`-th element corresponds to the `i % 8`-th least significant bit of the `i / 8`-th byte.
+/// The padding bits in the final byte are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::bits::BitVecDecoder;
+///
+/// let mut decoder = BitVecDecoder::new();
+/// let item = decoder.decode_from_bytes(&[0, 0, 0, 3, 0b101][..]).unwrap();
+/// assert_eq!(item, [true, false, true]);
+/// ```
+#[derive(Debug, Default)]
+pub struct BitVecDecoder {
+    length: U32beDecoder,
+    bits: Option<usize>,
+    bytes: Vec<u8>,
+}
+impl BitVecDecoder {
+    /// Makes a new `BitVecDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn remaining_bytes(&self) -> Option<usize> {
+        self.bits.map(|bits| bits.div_ceil(8) - self.bytes.len())
+    }
+}
+impl Decode for BitVecDecoder {
+    type Item = Vec<bool>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.bits.is_none() {
+            bytecodec_try_decode!(self.length, offset, buf, eos);
+            self.bits = Some(track!(self.length.finish_decoding())? as usize);
+        }
+
+        let remaining = self.remaining_bytes().expect("Never fails");
+        let size = cmp::min(buf.len() - offset, remaining);
+        self.bytes.extend_from_slice(&buf[offset..][..size]);
+        offset += size;
+        if size < remaining {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; remaining - size);
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.is_idle(), ErrorKind::IncompleteDecoding);
+        let bits = self.bits.take().expect("Never fails");
+        let item = (0..bits)
+            .map(|i| self.bytes[i / 8] & (1 << (i % 8)) != 0)
+            .collect();
+        self.bytes.clear();
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if let Some(remaining) = self.remaining_bytes() {
+            ByteCount::Finite(remaining as u64)
+        } else {
+            self.length.requiring_bytes()
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.remaining_bytes() == Some(0)
+    }
+}
+
+/// Encoder which encodes boolean vectors as packed bit vectors.
+///
+/// See the documentation of `BitVecDecoder` for the format.
+///
+/// # Errors
+///
+/// If the length of an item exceeds `u32::MAX`, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bits::BitVecEncoder;
+///
+/// let mut encoder = BitVecEncoder::new();
+/// let bytes = encoder.encode_into_bytes(vec![true, false, true]).unwrap();
+/// assert_eq!(bytes, [0, 0, 0, 3, 0b101]);
+/// ```
+#[derive(Debug, Default)]
+pub struct BitVecEncoder(BytesEncoder<Vec<u8>>);
+impl BitVecEncoder {
+    /// Makes a new `BitVecEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for BitVecEncoder {
+    type Item = Vec<bool>;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(item.len() <= u32::MAX as usize, ErrorKind::InvalidInput; item.len());
+        let mut bytes = (item.len() as u32).to_be_bytes().to_vec();
+        bytes.resize(4 + item.len().div_ceil(8), 0);
+        for (i, _) in item.iter().enumerate().filter(|(_, &bit)| bit) {
+            bytes[4 + i / 8] |= 1 << (i % 8);
+        }
+        track!(self.0.start_encoding(bytes))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for BitVecEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+/// Reader for extracting bit fields from a byte sequence.
+///
+/// Bits are read in MSB-first order (i.e., the most significant bit of the first byte comes first).
+///
+/// This is useful for implementing `Decode::finish_decoding` of bit-oriented formats
+/// (e.g., by combining with `MonolithicDecoder` or `CopyableBytesDecoder`).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::bits::BitReader;
+///
+/// let mut reader = BitReader::new(&[0b1011_0111]);
+/// assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+/// assert_eq!(reader.read_signed_bits(5).unwrap(), -9);
+/// assert_eq!(reader.remaining_bits(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    buf: &'a [u8],
+    bit_offset: usize,
+}
+impl<'a> BitReader<'a> {
+    /// Makes a new `BitReader` instance.
+    pub fn new(buf: &'a [u8]) -> Self {
+        BitReader { buf, bit_offset: 0 }
+    }
+
+    /// Returns the number of bits read so far.
+    pub fn bit_offset(&self) -> usize {
+        self.bit_offset
+    }
+
+    /// Returns the number of bits that have not been read yet.
+    pub fn remaining_bits(&self) -> usize {
+        self.buf.len() * 8 - self.bit_offset
+    }
+
+    /// Reads an `n`-bit unsigned integer.
+    ///
+    /// If there are less than `n` remaining bits, an `ErrorKind::UnexpectedEos` error will be returned.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is not in the range of `1..=64`, this method will panic.
+    pub fn read_bits(&mut self, n: usize) -> Result<u64> {
+        assert!((1..=64).contains(&n), "n={}", n);
+        track_assert!(n <= self.remaining_bits(), ErrorKind::UnexpectedEos; n, self.remaining_bits());
+
+        let mut value = 0;
+        for _ in 0..n {
+            let b = self.buf[self.bit_offset / 8] >> (7 - self.bit_offset % 8);
+            value = (value << 1) | u64::from(b & 1);
+            self.bit_offset += 1;
+        }
+        Ok(value)
+    }
+
+    /// Reads an `n`-bit signed integer represented in two's complement.
+    ///
+    /// The value is sign-extended into `i64`.
+    ///
+    /// If there are less than `n` remaining bits, an `ErrorKind::UnexpectedEos` error will be returned.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is not in the range of `1..=64`, this method will panic.
+    pub fn read_signed_bits(&mut self, n: usize) -> Result<i64> {
+        let value = track!(self.read_bits(n))?;
+        let shift = 64 - n;
+        Ok(((value << shift) as i64) >> shift)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn bit_flags_works() {
+        let mut encoder = BitFlagsEncoder::<3>::new();
+        let mut decoder = BitFlagsDecoder::<3>::new();
+        for &flags in &[[false; 3], [true, true, false], [false, true, true]] {
+            let bytes = track_try_unwrap!(encoder.encode_into_bytes(flags));
+            assert_eq!(bytes.len(), 1);
+            assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), flags);
+        }
+        assert_eq!(
+            track_try_unwrap!(encoder.encode_into_bytes([false, true, true])),
+            [0b110]
+        );
+
+        let error = decoder.decode_from_bytes(&[0b1001]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        decoder.set_strict(false);
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&[0b1001]));
+        assert_eq!(item, [true, false, false]);
+
+        let mut decoder = BitFlagsDecoder::<8>::new();
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&[0xff]));
+        assert_eq!(item, [true; 8]);
+    }
+
+    #[test]
+    fn read_signed_bits_works() {
+        let mut reader = BitReader::new(&[0b1111_0111]);
+        assert_eq!(track_try_unwrap!(reader.read_signed_bits(4)), -1);
+        assert_eq!(track_try_unwrap!(reader.read_signed_bits(4)), 7);
+
+        let error = reader.read_signed_bits(1).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+
+        let mut reader = BitReader::new(&[0x80, 0, 0, 0, 0, 0, 0, 0, 0x80]);
+        assert_eq!(track_try_unwrap!(reader.read_signed_bits(64)), i64::MIN);
+        assert_eq!(track_try_unwrap!(reader.read_signed_bits(1)), -1);
+        assert_eq!(track_try_unwrap!(reader.read_signed_bits(7)), 0);
+    }
+
+    #[test]
+    fn bit_vec_works() {
+        let item = vec![
+            true, false, false, true, true, false, true, false, false, true,
+        ];
+        let mut encoder = BitVecEncoder::new();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(item.clone()));
+        assert_eq!(bytes.len(), 4 + 2);
+        assert_eq!(bytes[4..], [0b0101_1001, 0b10]);
+
+        let mut decoder = BitVecDecoder::new();
+        let mut padded = bytes.clone();
+        padded[5] |= 0b1111_1100;
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&padded)), item);
+
+        let error = decoder.decode_from_bytes(&bytes[..5]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+}
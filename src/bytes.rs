@@ -1,11 +1,17 @@
 //! Encoders and decoders for reading/writing byte sequences.
-use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use crate::{ByteCount, Decode, DecodeStatus, Encode, Eos, ErrorKind, Result, SizedEncode};
 use std::cmp;
+use std::fmt;
 use std::mem;
+use std::sync::Arc;
 use trackable::error::ErrorKindExt;
 
 /// `BytesEncoder` writes the given bytes into an output byte sequence.
 ///
+/// `B` can be a borrowed type such as `&[u8]`.
+/// In that case, the bytes are encoded directly from the referenced data without copying them
+/// (see also `EncodeExt::with_item_ref`).
+///
 /// # Examples
 ///
 /// ```
@@ -45,7 +51,7 @@ impl<B: AsRef<[u8]>> Encode for BytesEncoder<B> {
         let mut size = 0;
         let drop_item = if let Some(ref b) = self.bytes {
             size = cmp::min(buf.len(), b.as_ref().len() - self.offset);
-            (&mut buf[..size]).copy_from_slice(&b.as_ref()[self.offset..][..size]);
+            buf[..size].copy_from_slice(&b.as_ref()[self.offset..][..size]);
             self.offset += size;
             if self.offset == b.as_ref().len() {
                 true
@@ -146,7 +152,7 @@ impl<B: AsRef<[u8]> + AsMut<[u8]> + Copy> Decode for CopyableBytesDecoder<B> {
 
     fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
         let size = cmp::min(buf.len(), self.bytes.as_ref().len() - self.offset);
-        (&mut self.bytes.as_mut()[self.offset..][..size]).copy_from_slice(&buf[..size]);
+        self.bytes.as_mut()[self.offset..][..size].copy_from_slice(&buf[..size]);
         self.offset += size;
         if self.offset != self.bytes.as_mut().len() {
             track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos;
@@ -237,7 +243,7 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Decode for BytesDecoder<B> {
         let size = {
             let bytes = track_assert_some!(self.bytes.as_mut(), ErrorKind::DecoderTerminated);
             let size = cmp::min(buf.len(), bytes.as_ref().len() - self.offset);
-            (&mut bytes.as_mut()[self.offset..][..size]).copy_from_slice(&buf[..size]);
+            bytes.as_mut()[self.offset..][..size].copy_from_slice(&buf[..size]);
             self.offset += size;
             size
         };
@@ -264,6 +270,16 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Decode for BytesDecoder<B> {
     fn is_idle(&self) -> bool {
         self.exact_requiring_bytes() == 0
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        if self.bytes.is_none() {
+            DecodeStatus::Terminated
+        } else if self.is_idle() {
+            DecodeStatus::Idle
+        } else {
+            DecodeStatus::NeedMoreBytes
+        }
+    }
 }
 
 /// `RemainingBytesDecoder` reads all the bytes from a input sequence until it reaches EOS.
@@ -286,16 +302,68 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Decode for BytesDecoder<B> {
 /// assert_eq!(decoder.is_idle(), true);
 /// assert_eq!(decoder.finish_decoding().unwrap(), b"foobar");
 /// ```
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct RemainingBytesDecoder {
     buf: Vec<u8>,
     eos: bool,
+    remaining_hint: Option<u64>,
 }
 impl RemainingBytesDecoder {
     /// Makes a new `RemainingBytesDecoder` instance.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Makes a new `RemainingBytesDecoder` instance that
+    /// expects each item to have `bytes` bytes.
+    ///
+    /// See the documentation of `set_remaining_hint` method for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, Eos};
+    /// use bytecodec::bytes::RemainingBytesDecoder;
+    ///
+    /// let mut decoder = RemainingBytesDecoder::with_remaining_hint(6);
+    /// decoder.decode(b"foo", Eos::new(false)).unwrap();
+    /// decoder.decode(b"bar", Eos::new(true)).unwrap();
+    ///
+    /// let item = decoder.finish_decoding().unwrap();
+    /// assert_eq!(item, b"foobar");
+    /// assert_eq!(item.capacity(), 6);
+    /// ```
+    pub fn with_remaining_hint(bytes: u64) -> Self {
+        RemainingBytesDecoder {
+            remaining_hint: Some(bytes),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the expected number of bytes of each item.
+    pub fn remaining_hint(&self) -> Option<u64> {
+        self.remaining_hint
+    }
+
+    /// Sets the expected number of bytes of each item (e.g., the value of a `Content-Length` header).
+    ///
+    /// If a hint is set, the decoder reserves the buffer capacity at once when it starts decoding an item,
+    /// instead of growing the buffer incrementally.
+    ///
+    /// Note that the hint is used only for memory allocation.
+    /// The decoder always finishes an item when the input reaches EOS, even if the hint was wrong.
+    pub fn set_remaining_hint(&mut self, bytes: Option<u64>) {
+        self.remaining_hint = bytes;
+    }
+}
+impl fmt::Debug for RemainingBytesDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RemainingBytesDecoder")
+            .field("buffered_bytes", &self.buf.len())
+            .field("eos", &self.eos)
+            .field("remaining_hint", &self.remaining_hint)
+            .finish()
+    }
 }
 impl Decode for RemainingBytesDecoder {
     type Item = Vec<u8>;
@@ -307,6 +375,8 @@ impl Decode for RemainingBytesDecoder {
 
         if let Some(remaining) = eos.remaining_bytes().to_u64() {
             self.buf.reserve_exact(buf.len() + remaining as usize);
+        } else if let (true, Some(hint)) = (self.buf.is_empty(), self.remaining_hint) {
+            self.buf.reserve_exact(hint as usize);
         }
         self.buf.extend_from_slice(buf);
         self.eos = eos.is_reached();
@@ -333,6 +403,277 @@ impl Decode for RemainingBytesDecoder {
     }
 }
 
+/// `ChunkedDecoder` splits an input byte sequence into fixed-size chunks.
+///
+/// Each item is a chunk of `chunk_size` bytes, except that the final chunk may be shorter
+/// if the input reaches EOS. After EOS, no more items are decoded.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::bytes::ChunkedDecoder;
+///
+/// let mut decoder = ChunkedDecoder::new(2);
+/// let items: Vec<_> = decoder.decode_all_from_bytes(b"foo").unwrap();
+/// assert_eq!(items, [b"fo".to_vec(), b"o".to_vec()]);
+/// ```
+#[derive(Debug)]
+pub struct ChunkedDecoder {
+    chunk_size: usize,
+    chunk: Vec<u8>,
+    ready: bool,
+    eos: bool,
+}
+impl ChunkedDecoder {
+    /// Makes a new `ChunkedDecoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is `0`, this function will panic.
+    pub fn new(chunk_size: usize) -> Self {
+        assert_ne!(chunk_size, 0);
+        ChunkedDecoder {
+            chunk_size,
+            chunk: Vec::new(),
+            ready: false,
+            eos: false,
+        }
+    }
+
+    /// Returns the maximum number of bytes in a chunk.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+}
+impl Decode for ChunkedDecoder {
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.ready || self.eos {
+            return Ok(0);
+        }
+
+        let size = cmp::min(self.chunk_size - self.chunk.len(), buf.len());
+        self.chunk.extend_from_slice(&buf[..size]);
+        if self.chunk.len() == self.chunk_size {
+            self.ready = true;
+        } else if eos.is_reached() {
+            self.eos = true;
+            self.ready = !self.chunk.is_empty();
+        }
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.ready, ErrorKind::IncompleteDecoding);
+        self.ready = false;
+        Ok(mem::take(&mut self.chunk))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.ready || self.eos {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Finite((self.chunk_size - self.chunk.len()) as u64)
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.ready
+    }
+}
+
+/// `SharedBytesEncoder` writes the bytes of the given shared buffer into an output byte sequence.
+///
+/// The buffer is encoded by reference, so multiple encoders can share one backing buffer without copying it.
+///
+/// Note that `BytesEncoder<Arc<[u8]>>` (or `BytesEncoder<Rc<[u8]>>`) also works,
+/// and shared buffers can be decoded by, for example, `RemainingBytesDecoder::new().map(Arc::from)`.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, EncodeExt};
+/// use bytecodec::bytes::{RemainingBytesDecoder, SharedBytesEncoder};
+/// use std::sync::Arc;
+///
+/// let bytes: Arc<[u8]> = Arc::from(&b"foo"[..]);
+/// let mut encoder = SharedBytesEncoder::new();
+/// assert_eq!(encoder.encode_into_bytes(bytes.clone()).unwrap(), b"foo");
+///
+/// let mut decoder = RemainingBytesDecoder::new().map(Arc::<[u8]>::from);
+/// assert_eq!(decoder.decode_from_bytes(b"foo").unwrap(), bytes);
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedBytesEncoder(BytesEncoder<Arc<[u8]>>);
+impl SharedBytesEncoder {
+    /// Makes a new `SharedBytesEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for SharedBytesEncoder {
+    type Item = Arc<[u8]>;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.0.start_encoding(item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for SharedBytesEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+/// `FillEncoder` writes the given number of copies of a byte into an output byte sequence.
+///
+/// The input item is a tuple of the byte to be written and the number of its copies.
+/// No intermediate buffer is allocated.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bytes::FillEncoder;
+///
+/// let mut encoder = FillEncoder::new();
+/// assert_eq!(encoder.encode_into_bytes((b'a', 3)).unwrap(), b"aaa");
+/// ```
+#[derive(Debug, Default)]
+pub struct FillEncoder {
+    byte: u8,
+    remaining: u64,
+}
+impl FillEncoder {
+    /// Makes a new `FillEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for FillEncoder {
+    type Item = (u8, u64);
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let size = cmp::min(buf.len() as u64, self.remaining) as usize;
+        for b in &mut buf[..size] {
+            *b = self.byte;
+        }
+        self.remaining -= size as u64;
+        if self.remaining != 0 {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.remaining);
+        }
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, (byte, count): Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        self.byte = byte;
+        self.remaining = count;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.exact_requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.remaining == 0
+    }
+}
+impl SizedEncode for FillEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.remaining
+    }
+}
+
+/// `IterBytesEncoder` writes the bytes yielded by the given iterator into an output byte sequence.
+///
+/// The bytes are pulled from the iterator lazily, so no intermediate buffer is allocated.
+///
+/// If the iterator reports an exact `size_hint`, it is used as the result of `requiring_bytes`.
+/// Otherwise, `ByteCount::Unknown` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bytes::IterBytesEncoder;
+///
+/// let mut encoder = IterBytesEncoder::new();
+/// assert_eq!(encoder.encode_into_bytes(b"foo".iter().cloned()).unwrap(), b"foo");
+/// ```
+#[derive(Debug)]
+pub struct IterBytesEncoder<I: Iterator<Item = u8>> {
+    iter: Option<std::iter::Peekable<I>>,
+}
+impl<I: Iterator<Item = u8>> IterBytesEncoder<I> {
+    /// Makes a new `IterBytesEncoder` instance.
+    pub fn new() -> Self {
+        IterBytesEncoder { iter: None }
+    }
+}
+impl<I: Iterator<Item = u8>> Encode for IterBytesEncoder<I> {
+    type Item = I;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut size = 0;
+        if let Some(iter) = self.iter.as_mut() {
+            for (b, x) in buf.iter_mut().zip(&mut *iter) {
+                *b = x;
+                size += 1;
+            }
+            if iter.peek().is_none() {
+                self.iter = None;
+            }
+        }
+        if !self.is_idle() {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        }
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        let mut iter = item.peekable();
+        if iter.peek().is_some() {
+            self.iter = Some(iter);
+        }
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        match self.iter {
+            None => ByteCount::Finite(0),
+            Some(ref iter) => match iter.size_hint() {
+                (lower, Some(upper)) if lower == upper => ByteCount::Finite(lower as u64),
+                _ => ByteCount::Unknown,
+            },
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.iter.is_none()
+    }
+}
+impl<I: Iterator<Item = u8>> Default for IterBytesEncoder<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug)]
 struct Utf8Bytes<T>(T);
 impl<T: AsRef<str>> AsRef<[u8]> for Utf8Bytes<T> {
@@ -343,6 +684,10 @@ impl<T: AsRef<str>> AsRef<[u8]> for Utf8Bytes<T> {
 
 /// `Utf8Encoder` writes the given Rust string into an output byte sequence.
 ///
+/// Any `S: AsRef<str>` can be used as the item type (e.g., `String`, `&str` and `Cow<str>`).
+/// In particular, `Utf8Encoder<Cow<str>>` can encode both borrowed and owned strings
+/// without extra copying.
+///
 /// # Examples
 ///
 /// ```
@@ -462,13 +807,276 @@ where
     fn is_idle(&self) -> bool {
         self.0.is_idle()
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.0.decode_status()
+    }
+}
+
+/// `IncrementalUtf8Decoder` decodes Rust strings from a input byte sequence until it reaches EOS.
+///
+/// Unlike `Utf8Decoder`, this validates the input bytes as they arrive,
+/// so an invalid byte sequence is detected immediately rather than at EOS.
+/// A trailing incomplete multibyte sequence is held back until the subsequent bytes arrive.
+///
+/// The validated part of the string can be taken by calling `take_chunk` method
+/// before the entire item is decoded.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, Eos};
+/// use bytecodec::bytes::IncrementalUtf8Decoder;
+///
+/// let mut decoder = IncrementalUtf8Decoder::new();
+///
+/// decoder.decode(b"foo\xE3\x81", Eos::new(false)).unwrap();
+/// assert_eq!(decoder.take_chunk(), "foo");
+///
+/// decoder.decode(b"\x82bar", Eos::new(true)).unwrap();
+/// assert_eq!(decoder.finish_decoding().unwrap(), "\u{3042}bar");
+/// ```
+#[derive(Debug, Default)]
+pub struct IncrementalUtf8Decoder {
+    text: String,
+    pending: [u8; 4],
+    pending_len: usize,
+    eos: bool,
+}
+impl IncrementalUtf8Decoder {
+    /// Makes a new `IncrementalUtf8Decoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes the validated part of the string that has been decoded so far.
+    pub fn take_chunk(&mut self) -> String {
+        mem::take(&mut self.text)
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => self.text.push_str(s),
+            Err(e) => {
+                track_assert!(e.error_len().is_none(), ErrorKind::InvalidInput;
+                              e, self.text.len() + e.valid_up_to());
+                let valid = e.valid_up_to();
+                let s = track!(std::str::from_utf8(&bytes[..valid])
+                    .map_err(|e| ErrorKind::InconsistentState.cause(e)))?;
+                self.text.push_str(s);
+
+                let rest = &bytes[valid..];
+                self.pending[..rest.len()].copy_from_slice(rest);
+                self.pending_len = rest.len();
+            }
+        }
+        Ok(())
+    }
+}
+impl Decode for IncrementalUtf8Decoder {
+    type Item = String;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.eos {
+            return Ok(0);
+        }
+
+        let mut offset = 0;
+        while self.pending_len != 0 && offset < buf.len() {
+            self.pending[self.pending_len] = buf[offset];
+            self.pending_len += 1;
+            offset += 1;
+
+            let pending = self.pending;
+            let pending_len = mem::replace(&mut self.pending_len, 0);
+            track!(self.push_bytes(&pending[..pending_len]))?;
+        }
+        track!(self.push_bytes(&buf[offset..]))?;
+
+        if eos.is_reached() {
+            track_assert_eq!(
+                self.pending_len,
+                0,
+                ErrorKind::InvalidInput,
+                "Incomplete UTF-8 sequence at EOS"
+            );
+            self.eos = true;
+        }
+        Ok(buf.len())
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.eos, ErrorKind::IncompleteDecoding);
+        self.eos = false;
+        Ok(self.take_chunk())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.eos {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Infinite
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.eos
+    }
+}
+
+/// `SequenceDelimitedDecoder` decodes the bytes preceding a delimiter byte sequence.
+///
+/// The delimiter can consist of multiple bytes (e.g., `b"\r\n"`) and
+/// may straddle the boundaries of the input chunks.
+/// The delimiter itself is consumed but not included in the decoded items.
+///
+/// # Errors
+///
+/// If the input reaches EOS before the delimiter is found,
+/// an `ErrorKind::UnexpectedEos` error will be returned.
+///
+/// If the length of an item exceeds `max_length`,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, Eos};
+/// use bytecodec::bytes::SequenceDelimitedDecoder;
+///
+/// let mut decoder = SequenceDelimitedDecoder::new(b"\r\n".to_vec());
+/// assert_eq!(decoder.decode(b"foo\r", Eos::new(false)).unwrap(), 4);
+/// assert!(!decoder.is_idle());
+///
+/// assert_eq!(decoder.decode(b"\nbar", Eos::new(false)).unwrap(), 1);
+/// assert_eq!(decoder.finish_decoding().unwrap(), b"foo");
+/// ```
+#[derive(Debug)]
+pub struct SequenceDelimitedDecoder {
+    delimiter: Vec<u8>,
+    buf: Vec<u8>,
+    ready: bool,
+    max_length: Option<usize>,
+}
+impl SequenceDelimitedDecoder {
+    /// Makes a new `SequenceDelimitedDecoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `delimiter` is empty, this function will panic.
+    pub fn new(delimiter: Vec<u8>) -> Self {
+        assert!(!delimiter.is_empty());
+        SequenceDelimitedDecoder {
+            delimiter,
+            buf: Vec::new(),
+            ready: false,
+            max_length: None,
+        }
+    }
+
+    /// Makes a new `SequenceDelimitedDecoder` instance that rejects items longer than `max_length`.
+    ///
+    /// # Panics
+    ///
+    /// If `delimiter` is empty, this function will panic.
+    pub fn with_max_length(delimiter: Vec<u8>, max_length: usize) -> Self {
+        let mut this = Self::new(delimiter);
+        this.max_length = Some(max_length);
+        this
+    }
+
+    /// Returns the delimiter byte sequence.
+    pub fn delimiter(&self) -> &[u8] {
+        &self.delimiter
+    }
+
+    /// Returns the maximum length of an item (excluding the delimiter).
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Sets the maximum length of an item (excluding the delimiter).
+    ///
+    /// `None` means unlimited.
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+    }
+
+    fn check_length(&mut self, length: usize) -> Result<()> {
+        if let Some(max) = self.max_length {
+            if length > max {
+                self.buf.clear();
+                track_panic!(ErrorKind::InvalidInput; length, max);
+            }
+        }
+        Ok(())
+    }
+}
+impl Decode for SequenceDelimitedDecoder {
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.ready {
+            return Ok(0);
+        }
+
+        // The last `delimiter.len() - 1` buffered bytes may be a prefix of the delimiter.
+        let old_len = self.buf.len();
+        let start = old_len.saturating_sub(self.delimiter.len() - 1);
+        let straddling = (start..old_len).find(|&i| {
+            let (head, tail) = self.delimiter.split_at(old_len - i);
+            self.buf[i..] == *head && buf.starts_with(tail)
+        });
+        if let Some(end) = straddling {
+            track!(self.check_length(end))?;
+            self.buf.truncate(end);
+            self.ready = true;
+            return Ok(self.delimiter.len() - (old_len - end));
+        }
+
+        let position = buf
+            .windows(self.delimiter.len())
+            .position(|window| window == &self.delimiter[..]);
+        if let Some(position) = position {
+            track!(self.check_length(old_len + position))?;
+            self.buf.extend_from_slice(&buf[..position]);
+            self.ready = true;
+            Ok(position + self.delimiter.len())
+        } else {
+            // The trailing bytes may be a prefix of the delimiter, so they are not counted yet.
+            let length = (old_len + buf.len()).saturating_sub(self.delimiter.len() - 1);
+            track!(self.check_length(length))?;
+            self.buf.extend_from_slice(buf);
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.buf.len());
+            Ok(buf.len())
+        }
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.ready, ErrorKind::IncompleteDecoding);
+        self.ready = false;
+        Ok(mem::take(&mut self.buf))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.ready {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.ready
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::io::{IoDecodeExt, IoEncodeExt};
-    use crate::{Encode, EncodeExt, ErrorKind};
+    use crate::{DecodeExt, DecodeStatus, Encode, EncodeExt, ErrorKind};
+    use std::rc::Rc;
 
     #[test]
     fn bytes_decoder_works() {
@@ -486,6 +1094,171 @@ mod test {
         );
     }
 
+    #[test]
+    fn remaining_bytes_decoder_with_hint_works() {
+        let mut decoder = RemainingBytesDecoder::with_remaining_hint(9);
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        assert_eq!(decoder.buf.capacity(), 9);
+        track_try_unwrap!(decoder.decode(b"bar", Eos::new(false)));
+        track_try_unwrap!(decoder.decode(b"baz", Eos::new(false)));
+        assert_eq!(decoder.buf.capacity(), 9);
+        track_try_unwrap!(decoder.decode(b"", Eos::new(true)));
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), b"foobarbaz");
+
+        // A wrong hint only affects the allocation
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        track_try_unwrap!(decoder.decode(b"barbazqux", Eos::new(true)));
+        assert_eq!(
+            track_try_unwrap!(decoder.finish_decoding()),
+            b"foobarbazqux"
+        );
+    }
+
+    #[test]
+    fn bytes_encoder_with_borrowed_item_works() {
+        let data = vec![0xAB; 100];
+        let mut encoder = track_try_unwrap!(BytesEncoder::<&[u8]>::with_item_ref(&data[..]));
+        assert_eq!(encoder.exact_requiring_bytes(), 100);
+
+        let mut output = Vec::new();
+        track_try_unwrap!(encoder.encode_all(&mut output));
+        assert_eq!(output, data);
+
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(&data[90..]));
+        assert_eq!(bytes, [0xAB; 10]);
+    }
+
+    #[test]
+    fn remaining_bytes_decoder_debug_shows_progress() {
+        let mut decoder = RemainingBytesDecoder::new();
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        assert_eq!(
+            format!("{:?}", decoder),
+            "RemainingBytesDecoder { buffered_bytes: 3, eos: false, remaining_hint: None }"
+        );
+    }
+
+    #[test]
+    fn chunked_decoder_works() {
+        let mut decoder = ChunkedDecoder::new(4);
+        let input = (0..10).collect::<Vec<u8>>();
+        let items: Vec<_> = track_try_unwrap!(decoder.decode_all_from_bytes(&input));
+        assert_eq!(items, [vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]);
+
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(b"foo", Eos::new(false))),
+            0
+        );
+        assert_eq!(decoder.decode_status(), DecodeStatus::Terminated);
+        assert!(decoder.finish_decoding().is_err());
+    }
+
+    #[test]
+    fn iter_bytes_encoder_works() {
+        let mut encoder = IterBytesEncoder::new();
+        track_try_unwrap!(encoder.start_encoding(0u8..5));
+        assert_eq!(encoder.requiring_bytes(), ByteCount::Finite(5));
+
+        let mut buf = [0; 3];
+        assert_eq!(
+            track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false))),
+            3
+        );
+        assert_eq!(buf, [0, 1, 2]);
+        assert_eq!(encoder.requiring_bytes(), ByteCount::Finite(2));
+        assert_eq!(
+            track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false))),
+            2
+        );
+        assert_eq!(&buf[..2], [3, 4]);
+        assert!(encoder.is_idle());
+
+        let mut encoder = IterBytesEncoder::new();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes((0u8..10).filter(|x| x % 2 == 0)));
+        assert_eq!(bytes, [0, 2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn fill_encoder_works() {
+        let mut encoder = FillEncoder::new();
+        track_try_unwrap!(encoder.start_encoding((0xFF, 1000)));
+        assert_eq!(encoder.exact_requiring_bytes(), 1000);
+
+        let mut output = Vec::new();
+        let mut buf = [0; 64];
+        while !encoder.is_idle() {
+            let size = track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false)));
+            output.extend_from_slice(&buf[..size]);
+            assert_eq!(encoder.exact_requiring_bytes(), 1000 - output.len() as u64);
+        }
+        assert_eq!(output, vec![0xFF; 1000]);
+
+        track_try_unwrap!(encoder.start_encoding((0, 10)));
+        let error = encoder.encode(&mut buf[..5], Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+
+    #[test]
+    fn shared_bytes_encoder_works() {
+        let bytes: Arc<[u8]> = Arc::from(&b"foobar"[..]);
+        let mut encoder0 = SharedBytesEncoder::new();
+        let mut encoder1 = SharedBytesEncoder::new();
+        track_try_unwrap!(encoder0.start_encoding(bytes.clone()));
+        track_try_unwrap!(encoder1.start_encoding(bytes.clone()));
+        assert_eq!(Arc::strong_count(&bytes), 3);
+
+        let mut output0 = [0; 6];
+        let mut output1 = [0; 6];
+        track_try_unwrap!(encoder0.encode(&mut output0[..3], Eos::new(false)));
+        track_try_unwrap!(encoder1.encode(&mut output1, Eos::new(false)));
+        track_try_unwrap!(encoder0.encode(&mut output0[3..], Eos::new(false)));
+        assert_eq!(&output0, b"foobar");
+        assert_eq!(&output1, b"foobar");
+        assert_eq!(Arc::strong_count(&bytes), 1);
+    }
+
+    #[test]
+    fn shared_bytes_works_with_bytes_encoder() {
+        let bytes: Rc<[u8]> = Rc::from(&b"foo"[..]);
+        let mut encoder = BytesEncoder::new();
+        assert_eq!(
+            track_try_unwrap!(encoder.encode_into_bytes(bytes.clone())),
+            b"foo"
+        );
+
+        let bytes: Arc<[u8]> = Arc::from(&b"bar"[..]);
+        let mut encoder = BytesEncoder::new();
+        assert_eq!(track_try_unwrap!(encoder.encode_into_bytes(bytes)), b"bar");
+    }
+
+    #[test]
+    fn incremental_utf8_decoder_works() {
+        let mut decoder = IncrementalUtf8Decoder::new();
+        let bytes = "\u{3042}".as_bytes();
+        assert_eq!(bytes.len(), 3);
+
+        track_try_unwrap!(decoder.decode(&bytes[..1], Eos::new(false)));
+        assert_eq!(decoder.take_chunk(), "");
+        track_try_unwrap!(decoder.decode(&bytes[1..], Eos::new(false)));
+        assert_eq!(decoder.take_chunk(), "\u{3042}");
+
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(true)));
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "foo");
+
+        track_try_unwrap!(decoder.decode(&bytes[..2], Eos::new(false)));
+        let error = decoder.decode(b"", Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn incremental_utf8_decoder_rejects_invalid_byte_early() {
+        let mut decoder = IncrementalUtf8Decoder::new();
+        track_try_unwrap!(decoder.decode(b"foo\xE3", Eos::new(false)));
+
+        let error = decoder.decode(b"A", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
     #[test]
     fn utf8_encoder_works() {
         let mut buf = Vec::new();
@@ -494,4 +1267,105 @@ mod test {
         assert!(encoder.is_idle());
         assert_eq!(buf, b"foo");
     }
+
+    #[test]
+    fn utf8_encoder_works_with_cow() {
+        use std::borrow::Cow;
+
+        let mut encoder = Utf8Encoder::<Cow<str>>::new();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(Cow::Borrowed("foo")));
+        assert_eq!(bytes, b"foo");
+
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(Cow::Owned("bar".to_owned())));
+        assert_eq!(bytes, b"bar");
+    }
+
+    #[test]
+    fn sequence_delimited_decoder_works() {
+        let mut decoder = SequenceDelimitedDecoder::new(b"\r\n\r\n".to_vec());
+        let input = b"Host: example.com\r\n\r\nbody";
+        let chunks = [&input[..18], &input[18..20], &input[20..]];
+
+        let mut consumed = 0;
+        for chunk in &chunks[..2] {
+            let size = track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+            assert_eq!(size, chunk.len());
+            assert!(!decoder.is_idle());
+            consumed += size;
+        }
+        consumed += track_try_unwrap!(decoder.decode(chunks[2], Eos::new(false)));
+        assert_eq!(&input[consumed..], b"body");
+        assert_eq!(
+            track_try_unwrap!(decoder.finish_decoding()),
+            b"Host: example.com"
+        );
+
+        let error = decoder.decode(b"body", Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+
+    #[test]
+    fn sequence_delimited_decoder_leaves_bytes_after_delimiter() {
+        let mut decoder = SequenceDelimitedDecoder::new(b"\r\n".to_vec());
+        let mut input = b"foo\r\n".to_vec();
+        input.extend_from_slice(&[b'a'; 1000]);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(&input, Eos::new(false))),
+            5
+        );
+        assert!(decoder.buf.capacity() < 1000);
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), b"foo");
+
+        // A delimiter that straddles the chunks
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(b"bar\r", Eos::new(false))),
+            4
+        );
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(b"\nbaz", Eos::new(false))),
+            1
+        );
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), b"bar");
+    }
+
+    #[test]
+    fn sequence_delimited_decoder_max_length_works() {
+        let mut decoder = SequenceDelimitedDecoder::with_max_length(b"\r\n".to_vec(), 3);
+        assert_eq!(decoder.max_length(), Some(3));
+        let item = track_try_unwrap!(decoder.decode_exact(&b"foo\r\n"[..]));
+        assert_eq!(item, b"foo");
+
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(b"bar\r", Eos::new(false))),
+            4
+        );
+        let error = decoder
+            .decode(b"foobar\r\n", Eos::new(false))
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        let error = decoder.decode(b"foobar", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        let item = track_try_unwrap!(decoder.decode_exact(&b"baz\r\n"[..]));
+        assert_eq!(item, b"baz");
+
+        decoder.set_max_length(None);
+        let item = track_try_unwrap!(decoder.decode_exact(&b"foobar\r\n"[..]));
+        assert_eq!(item, b"foobar");
+
+        // A delimiter that straddles the chunks
+        let mut decoder = SequenceDelimitedDecoder::with_max_length(b"XYZW".to_vec(), 2);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(b"abcX", Eos::new(false))),
+            4
+        );
+        let error = decoder.decode(b"YZW", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert!(!decoder.is_idle());
+
+        let item = track_try_unwrap!(decoder.decode_exact(&b"abXYZW"[..]));
+        assert_eq!(item, b"ab");
+    }
 }
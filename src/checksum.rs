@@ -0,0 +1,205 @@
+//! Checksum algorithms used by `Checksummed` and `ChecksumVerified` combinators.
+//!
+//! See `EncodeExt::checksummed` and `DecodeExt::checksum_verified` methods.
+
+/// This trait allows for computing a checksum incrementally.
+pub trait Checksum {
+    /// Updates the checksum by the given bytes.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the number of bytes of the finalized checksum.
+    fn size(&self) -> usize;
+
+    /// Finalizes the computation and returns the resulting checksum bytes.
+    ///
+    /// The length of the returned bytes must be equal to `self.size()`.
+    fn finalize(self) -> Vec<u8>;
+}
+
+const CRC32_TABLE: [u32; 256] = make_crc32_table();
+
+const fn make_crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-32 (IEEE 802.3) checksum.
+///
+/// The finalized checksum is a 4-byte big-endian integer.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::checksum::{Checksum, Crc32};
+///
+/// let mut crc = Crc32::new();
+/// crc.update(b"123456789");
+/// assert_eq!(crc.value(), 0xCBF4_3926);
+/// assert_eq!(crc.finalize(), [0xCB, 0xF4, 0x39, 0x26]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32(u32);
+impl Crc32 {
+    /// Makes a new `Crc32` instance.
+    pub fn new() -> Self {
+        Crc32(0xFFFF_FFFF)
+    }
+
+    /// Returns the checksum of the bytes given so far.
+    pub fn value(&self) -> u32 {
+        !self.0
+    }
+}
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Checksum for Crc32 {
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = CRC32_TABLE[((self.0 ^ u32::from(b)) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    fn size(&self) -> usize {
+        4
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.value().to_be_bytes().to_vec()
+    }
+}
+
+/// Adler-32 checksum.
+///
+/// The finalized checksum is a 4-byte big-endian integer.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::checksum::{Adler32, Checksum};
+///
+/// let mut adler = Adler32::new();
+/// adler.update(b"Wikipedia");
+/// assert_eq!(adler.value(), 0x11E6_0398);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+impl Adler32 {
+    const MODULO: u32 = 65521;
+
+    /// Makes a new `Adler32` instance.
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+
+    /// Returns the checksum of the bytes given so far.
+    pub fn value(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Checksum for Adler32 {
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.a = (self.a + u32::from(b)) % Self::MODULO;
+            self.b = (self.b + self.a) % Self::MODULO;
+        }
+    }
+
+    fn size(&self) -> usize {
+        4
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.value().to_be_bytes().to_vec()
+    }
+}
+
+/// Checksum which XORs all bytes together (1 byte).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::checksum::{Checksum, Xor8};
+///
+/// let mut xor = Xor8::new();
+/// xor.update(&[0b0011, 0b0101]);
+/// assert_eq!(xor.value(), 0b0110);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Xor8(u8);
+impl Xor8 {
+    /// Makes a new `Xor8` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the checksum of the bytes given so far.
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+}
+impl Checksum for Xor8 {
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b;
+        }
+    }
+
+    fn size(&self) -> usize {
+        1
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        vec![self.0]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crc32_works() {
+        let mut crc = Crc32::new();
+        assert_eq!(crc.value(), 0);
+
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.value(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn adler32_works() {
+        let mut adler = Adler32::new();
+        assert_eq!(adler.value(), 1);
+
+        adler.update(&[0xff; 6000]);
+        assert_eq!(adler.size(), 4);
+        assert_eq!(adler.finalize(), [0xA4, 0x97, 0x59, 0xEA]);
+    }
+}
@@ -1,14 +1,21 @@
 //! Encoders and decoders for combination.
 //!
 //! These are mainly created via the methods provided by `EncodeExt` or `DecodeExt` traits.
-use crate::bytes::BytesEncoder;
+use crate::bytes::{BytesEncoder, CopyableBytesDecoder, RemainingBytesDecoder};
+use crate::checksum::{Checksum, Crc32};
+use crate::io::{IoDecodeExt, ReadBuf};
 use crate::marker::Never;
-use crate::{ByteCount, Decode, Encode, EncodeExt, Eos, Error, ErrorKind, Result, SizedEncode};
+use crate::{
+    ByteCount, Decode, DecodeStatus, Encode, EncodeExt, Eos, Error, ErrorKind, Result, SizedEncode,
+    TaggedDecode, TryTaggedDecode,
+};
 use std::cmp;
+use std::collections::VecDeque;
 use std::fmt;
 use std::iter;
 use std::marker::PhantomData;
 use std::mem;
+use trackable::error::{BoxError, ErrorKindExt};
 
 /// Combinator for converting decoded items to other values.
 ///
@@ -69,6 +76,89 @@ where
     fn is_idle(&self) -> bool {
         self.inner.is_idle()
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator for converting decoded items to other values with a mutable state.
+///
+/// The state is shared by the conversions of all the items.
+///
+/// This is created by calling `DecodeExt::map_with_state` method.
+#[derive(Debug)]
+pub struct MapWithState<D, S, T, F> {
+    inner: D,
+    state: S,
+    map: F,
+    _item: PhantomData<T>,
+}
+impl<D: Decode, S, T, F> MapWithState<D, S, T, F> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    /// Returns a reference to the state.
+    pub fn state_ref(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns a mutable reference to the state.
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    pub(crate) fn new(inner: D, state: S, map: F) -> Self
+    where
+        F: FnMut(&mut S, D::Item) -> T,
+    {
+        MapWithState {
+            inner,
+            state,
+            map,
+            _item: PhantomData,
+        }
+    }
+}
+impl<D, S, T, F> Decode for MapWithState<D, S, T, F>
+where
+    D: Decode,
+    F: FnMut(&mut S, D::Item) -> T,
+{
+    type Item = T;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track!(self.inner.finish_decoding())?;
+        Ok((self.map)(&mut self.state, item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
 }
 
 /// Combinator for modifying encoding/decoding errors.
@@ -135,6 +225,10 @@ where
     fn is_idle(&self) -> bool {
         self.inner.is_idle()
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
 }
 impl<C, E, F> Encode for MapErr<C, E, F>
 where
@@ -175,6 +269,131 @@ where
     }
 }
 
+/// Combinator for attaching a static context label to decoding errors.
+///
+/// This is created by calling `DecodeExt::context` method.
+#[derive(Debug)]
+pub struct Context<D> {
+    inner: D,
+    label: &'static str,
+}
+impl<D> Context<D> {
+    /// Returns the context label.
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D, label: &'static str) -> Self {
+        Context { inner, label }
+    }
+}
+impl<D: Decode> Decode for Context<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let label = self.label;
+        self.inner.decode(buf, eos).map_err(|e| track!(e, label))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let label = self.label;
+        self.inner.finish_decoding().map_err(|e| track!(e, label))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator for recovering from errors returned by `finish_decoding` of the inner decoder.
+///
+/// Only the errors returned by `finish_decoding` (e.g., `InvalidInput` errors of `TryMap`) are recovered.
+/// The errors returned by `decode` (e.g., `UnexpectedEos`) are structural and are propagated as they are,
+/// because the inner decoder cannot continue decoding after them.
+/// `ErrorKind::IncompleteDecoding` errors are also propagated,
+/// because they mean that `finish_decoding` was called too early rather than that the input was invalid.
+///
+/// This is created by calling `DecodeExt::recover` method.
+#[derive(Debug)]
+pub struct Recover<D, F> {
+    inner: D,
+    recover: F,
+}
+impl<D, F> Recover<D, F> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D, recover: F) -> Self {
+        Recover { inner, recover }
+    }
+}
+impl<D, F> Decode for Recover<D, F>
+where
+    D: Decode,
+    F: Fn(Error) -> D::Item,
+{
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        match self.inner.finish_decoding() {
+            Ok(item) => Ok(item),
+            Err(e) if *e.kind() == ErrorKind::IncompleteDecoding => Err(track!(e)),
+            Err(e) => Ok((self.recover)(e)),
+        }
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
 /// Combinator for conditional decoding.
 ///
 /// If the first item is successfully decoded,
@@ -234,91 +453,232 @@ where
     }
 
     fn is_idle(&self) -> bool {
-        self.inner1.as_ref().map_or(false, Decode::is_idle)
+        self.inner1.as_ref().is_some_and(Decode::is_idle)
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        if let Some(ref d) = self.inner1 {
+            d.decode_status()
+        } else {
+            match self.inner0.decode_status() {
+                DecodeStatus::Idle => DecodeStatus::NeedMoreBytes,
+                status => status,
+            }
+        }
     }
 }
 
-/// Combinator for converting items into ones that
-/// suited to the inner encoder by calling the given function.
+/// Combinator for conditional decoding with a fallible continuation.
 ///
-/// This is created by calling `EncodeExt::map_from` method.
+/// If the first item is successfully decoded,
+/// it will start decoding the second item by using the decoder returned by `f` function.
+/// Unlike `AndThen`, `f` can reject the first item by returning an error.
+///
+/// This is created by calling `DecodeExt::flat_map` method.
 #[derive(Debug)]
-pub struct MapFrom<E, T, F> {
-    inner: E,
-    _item: PhantomData<T>,
-    from: F,
+pub struct FlatMap<D0, D1, F> {
+    inner0: D0,
+    inner1: Option<D1>,
+    flat_map: F,
 }
-impl<E, T, F> MapFrom<E, T, F> {
-    /// Returns a reference to the inner encoder.
-    pub fn inner_ref(&self) -> &E {
-        &self.inner
-    }
-
-    /// Returns a mutable reference to the inner encoder.
-    pub fn inner_mut(&mut self) -> &mut E {
-        &mut self.inner
-    }
-
-    /// Takes ownership of this instance and returns the inner encoder.
-    pub fn into_inner(self) -> E {
-        self.inner
-    }
-
-    pub(crate) fn new(inner: E, from: F) -> Self {
-        MapFrom {
-            inner,
-            _item: PhantomData,
-            from,
+impl<D0: Decode, D1, F> FlatMap<D0, D1, F> {
+    pub(crate) fn new(inner0: D0, flat_map: F) -> Self
+    where
+        F: Fn(D0::Item) -> Result<D1>,
+    {
+        FlatMap {
+            inner0,
+            inner1: None,
+            flat_map,
         }
     }
 }
-impl<E, T, F> Encode for MapFrom<E, T, F>
+impl<D0, D1, F> Decode for FlatMap<D0, D1, F>
 where
-    E: Encode,
-    F: Fn(T) -> E::Item,
+    D0: Decode,
+    D1: Decode,
+    F: Fn(D0::Item) -> Result<D1>,
 {
-    type Item = T;
+    type Item = D1::Item;
 
-    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
-        self.inner.encode(buf, eos)
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.inner1.is_none() {
+            bytecodec_try_decode!(self.inner0, offset, buf, eos);
+            let item = track!(self.inner0.finish_decoding())?;
+            self.inner1 = Some(track!((self.flat_map)(item))?);
+        }
+
+        let inner1 = self.inner1.as_mut().expect("Never fails");
+        bytecodec_try_decode!(inner1, offset, buf, eos);
+        Ok(offset)
     }
 
-    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
-        self.inner.start_encoding((self.from)(item))
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let mut d = track_assert_some!(self.inner1.take(), ErrorKind::IncompleteDecoding);
+        track!(d.finish_decoding())
     }
 
     fn requiring_bytes(&self) -> ByteCount {
-        self.inner.requiring_bytes()
+        if let Some(ref d) = self.inner1 {
+            d.requiring_bytes()
+        } else {
+            self.inner0.requiring_bytes()
+        }
     }
 
     fn is_idle(&self) -> bool {
-        self.inner.is_idle()
-    }
-}
-impl<E, T, F> SizedEncode for MapFrom<E, T, F>
-where
-    E: SizedEncode,
-    F: Fn(T) -> E::Item,
-{
-    fn exact_requiring_bytes(&self) -> u64 {
-        self.inner.exact_requiring_bytes()
+        self.inner1.as_ref().is_some_and(Decode::is_idle)
     }
 }
 
-/// Combinator that tries to convert items into ones that
-/// suited to the inner encoder by calling the given function.
+/// Combinator for conditional decoding with an explicit size of the second item.
 ///
-/// This is created by calling `EncodeExt::try_map_from` method.
+/// If the first item is successfully decoded,
+/// it will start decoding the second item by using the decoder returned by `f` function.
+/// Unlike `AndThen`, `f` also returns the exact number of bytes of the second item,
+/// so `requiring_bytes` reports `ByteCount::Finite` once the first item is decoded.
+///
+/// This is created by calling `DecodeExt::and_then_sized` method.
 #[derive(Debug)]
-pub struct TryMapFrom<C, T, E, F> {
-    inner: C,
-    try_from: F,
-    _phantom: PhantomData<(T, E)>,
+pub struct AndThenSized<D0, D1, F> {
+    inner0: D0,
+    inner1: Option<Length<D1>>,
+    and_then: F,
 }
-impl<C, T, E, F> TryMapFrom<C, T, E, F> {
-    /// Returns a reference to the inner encoder.
-    pub fn inner_ref(&self) -> &C {
-        &self.inner
+impl<D0: Decode, D1, F> AndThenSized<D0, D1, F> {
+    pub(crate) fn new(inner0: D0, and_then: F) -> Self
+    where
+        F: Fn(D0::Item) -> (D1, u64),
+    {
+        AndThenSized {
+            inner0,
+            inner1: None,
+            and_then,
+        }
+    }
+}
+impl<D0, D1, F> Decode for AndThenSized<D0, D1, F>
+where
+    D0: Decode,
+    D1: Decode,
+    F: Fn(D0::Item) -> (D1, u64),
+{
+    type Item = D1::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.inner1.is_none() {
+            bytecodec_try_decode!(self.inner0, offset, buf, eos);
+            let item = track!(self.inner0.finish_decoding())?;
+            let (inner1, size) = (self.and_then)(item);
+            self.inner1 = Some(Length::new(inner1, size));
+        }
+
+        let inner1 = self.inner1.as_mut().expect("Never fails");
+        bytecodec_try_decode!(inner1, offset, buf, eos);
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let mut d = track_assert_some!(self.inner1.take(), ErrorKind::IncompleteDecoding);
+        track!(d.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if let Some(ref d) = self.inner1 {
+            d.requiring_bytes()
+        } else {
+            self.inner0.requiring_bytes()
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner1.as_ref().is_some_and(Decode::is_idle)
+    }
+}
+
+/// Combinator for converting items into ones that
+/// suited to the inner encoder by calling the given function.
+///
+/// This is created by calling `EncodeExt::map_from` method.
+#[derive(Debug)]
+pub struct MapFrom<E, T, F> {
+    inner: E,
+    _item: PhantomData<T>,
+    from: F,
+}
+impl<E, T, F> MapFrom<E, T, F> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: E, from: F) -> Self {
+        MapFrom {
+            inner,
+            _item: PhantomData,
+            from,
+        }
+    }
+}
+impl<E, T, F> Encode for MapFrom<E, T, F>
+where
+    E: Encode,
+    F: Fn(T) -> E::Item,
+{
+    type Item = T;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        self.inner.encode(buf, eos)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        self.inner.start_encoding((self.from)(item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+impl<E, T, F> SizedEncode for MapFrom<E, T, F>
+where
+    E: SizedEncode,
+    F: Fn(T) -> E::Item,
+{
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Combinator that tries to convert items into ones that
+/// suited to the inner encoder by calling the given function.
+///
+/// This is created by calling `EncodeExt::try_map_from` method.
+#[derive(Debug)]
+pub struct TryMapFrom<C, T, E, F> {
+    inner: C,
+    try_from: F,
+    _phantom: PhantomData<(T, E)>,
+}
+impl<C, T, E, F> TryMapFrom<C, T, E, F> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &C {
+        &self.inner
     }
 
     /// Returns a mutable reference to the inner encoder.
@@ -454,6 +814,214 @@ impl<E: Default, I> Default for Repeat<E, I> {
     }
 }
 
+/// Combinator for repeating encoding of fixed-size `E::Item`s.
+///
+/// Unlike `Repeat`, this knows the exact number of bytes required to encode the remaining items,
+/// assuming that every item is encoded to the same number of bytes as the first one.
+/// If an item of a different size is found, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// This is created by calling `EncodeExt::repeat_exact` method.
+#[derive(Debug)]
+pub struct RepeatExact<E, I> {
+    inner: E,
+    items: Option<I>,
+    item_size: u64,
+}
+impl<E, I> RepeatExact<E, I> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: E) -> Self {
+        RepeatExact {
+            inner,
+            items: None,
+            item_size: 0,
+        }
+    }
+
+    fn release_exhausted_items(&mut self)
+    where
+        E: Encode,
+        I: ExactSizeIterator,
+    {
+        if self.inner.is_idle() && self.items.as_ref().is_some_and(|items| items.len() == 0) {
+            self.items = None;
+        }
+    }
+}
+impl<E, I> Encode for RepeatExact<E, I>
+where
+    E: SizedEncode,
+    I: ExactSizeIterator<Item = E::Item>,
+{
+    type Item = I;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        loop {
+            while self.inner.is_idle() {
+                if let Some(item) = self.items.as_mut().and_then(Iterator::next) {
+                    track!(self.inner.start_encoding(item))?;
+                    track_assert_eq!(
+                        self.inner.exact_requiring_bytes(),
+                        self.item_size,
+                        ErrorKind::InvalidInput,
+                        "Non-uniform item size"
+                    );
+                } else {
+                    self.items = None;
+                    return Ok(offset);
+                }
+            }
+
+            let size = track!(self.inner.encode(&mut buf[offset..], eos))?;
+            offset += size;
+            self.release_exhausted_items();
+            if size == 0 || self.items.is_none() {
+                return Ok(offset);
+            }
+        }
+    }
+
+    fn start_encoding(&mut self, mut item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        if let Some(first) = item.next() {
+            track!(self.inner.start_encoding(first))?;
+            self.item_size = self.inner.exact_requiring_bytes();
+            self.items = Some(item);
+            self.release_exhausted_items();
+        }
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.exact_requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.items.is_none()
+    }
+}
+impl<E, I> SizedEncode for RepeatExact<E, I>
+where
+    E: SizedEncode,
+    I: ExactSizeIterator<Item = E::Item>,
+{
+    fn exact_requiring_bytes(&self) -> u64 {
+        let remaining_items = self.items.as_ref().map_or(0, ExactSizeIterator::len) as u64;
+        self.inner.exact_requiring_bytes() + self.item_size * remaining_items
+    }
+}
+impl<E: Default, I> Default for RepeatExact<E, I> {
+    fn default() -> Self {
+        Self::new(E::default())
+    }
+}
+
+/// Encoder for encoding a sequence of items that are queued one at a time.
+///
+/// Each call of `start_encoding` appends an item to the queue (it never fails with `ErrorKind::EncoderFull`),
+/// and the queued items are encoded in order. This becomes idle when the queue is empty.
+///
+/// Unlike `Repeat`, the items do not have to be known up front.
+/// Since an encoder is started for each queued item, the number of bytes required to
+/// encode all the items is known if `E` is `SizedEncode`.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Encode, EncodeExt};
+/// use bytecodec::combinator::SequenceEncoder;
+/// use bytecodec::fixnum::U8Encoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut encoder = SequenceEncoder::<U8Encoder>::new();
+/// encoder.start_encoding(1).unwrap();
+/// encoder.start_encoding(2).unwrap();
+///
+/// let mut buf = Vec::new();
+/// encoder.encode_all(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 2]);
+/// ```
+#[derive(Debug)]
+pub struct SequenceEncoder<E> {
+    encoders: VecDeque<E>,
+}
+impl<E> SequenceEncoder<E> {
+    /// Makes a new `SequenceEncoder` instance.
+    pub fn new() -> Self {
+        SequenceEncoder {
+            encoders: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of the items being encoded (including the partially encoded one).
+    pub fn len(&self) -> usize {
+        self.encoders.len()
+    }
+
+    /// Returns `true` if there are no items being encoded, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.encoders.is_empty()
+    }
+}
+impl<E: Encode + Default> Encode for SequenceEncoder<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        while let Some(encoder) = self.encoders.front_mut() {
+            offset += track!(encoder.encode(&mut buf[offset..], eos))?;
+            if !encoder.is_idle() {
+                break;
+            }
+            self.encoders.pop_front();
+        }
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let mut encoder = E::default();
+        track!(encoder.start_encoding(item))?;
+        if !encoder.is_idle() {
+            self.encoders.push_back(encoder);
+        }
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.encoders.iter().fold(ByteCount::Finite(0), |n, e| {
+            n.add_for_encoding(e.requiring_bytes())
+        })
+    }
+
+    fn is_idle(&self) -> bool {
+        self.encoders.is_empty()
+    }
+}
+impl<E: SizedEncode + Default> SizedEncode for SequenceEncoder<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.encoders.iter().map(E::exact_requiring_bytes).sum()
+    }
+}
+impl<E> Default for SequenceEncoder<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Combinator for representing optional decoders.
 ///
 /// This is created by calling `DecodeExt::omit` method.
@@ -525,23 +1093,190 @@ impl<D: Decode> Decode for Omittable<D> {
     }
 }
 
-/// Combinator for representing an optional encoder.
-#[derive(Debug, Default)]
-pub struct Optional<E>(E);
-impl<E> Optional<E> {
-    /// Returns a reference to the inner encoder.
-    pub fn inner_ref(&self) -> &E {
-        &self.0
+/// Combinator for decoding optional items preceded by presence flags.
+///
+/// The presence decoder `P` is run first.
+/// If it decodes `true`, the inner decoder is run and its item is returned as `Some(_)`,
+/// otherwise `None` is returned without consuming any more bytes.
+///
+/// This is created by calling `DecodeExt::optional_if` method.
+#[derive(Debug)]
+pub struct PresenceGated<P, D> {
+    presence: P,
+    inner: D,
+    is_present: Option<bool>,
+}
+impl<P, D> PresenceGated<P, D> {
+    /// Returns a reference to the presence decoder.
+    pub fn presence_ref(&self) -> &P {
+        &self.presence
     }
 
-    /// Returns a mutable reference to the inner encoder.
-    pub fn inner_mut(&mut self) -> &mut E {
-        &mut self.0
+    /// Returns a mutable reference to the presence decoder.
+    pub fn presence_mut(&mut self) -> &mut P {
+        &mut self.presence
     }
 
-    /// Takes ownership of this instance and returns the inner encoder.
-    pub fn into_inner(self) -> E {
-        self.0
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the presence decoder and the inner decoder.
+    pub fn into_inner(self) -> (P, D) {
+        (self.presence, self.inner)
+    }
+
+    pub(crate) fn new(inner: D, presence: P) -> Self {
+        PresenceGated {
+            presence,
+            inner,
+            is_present: None,
+        }
+    }
+}
+impl<P, D> Decode for PresenceGated<P, D>
+where
+    P: Decode<Item = bool>,
+    D: Decode,
+{
+    type Item = Option<D::Item>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.is_present.is_none() {
+            bytecodec_try_decode!(self.presence, offset, buf, eos);
+            self.is_present = Some(track!(self.presence.finish_decoding())?);
+        }
+        if self.is_present == Some(true) {
+            bytecodec_try_decode!(self.inner, offset, buf, eos);
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let is_present = track_assert_some!(self.is_present.take(), ErrorKind::IncompleteDecoding);
+        if is_present {
+            track!(self.inner.finish_decoding()).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        match self.is_present {
+            None => self.presence.requiring_bytes(),
+            Some(true) => self.inner.requiring_bytes(),
+            Some(false) => ByteCount::Finite(0),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        match self.is_present {
+            None => false,
+            Some(true) => self.inner.is_idle(),
+            Some(false) => true,
+        }
+    }
+}
+
+/// Combinator for decoders which can be enabled or disabled at runtime.
+///
+/// While disabled, the decoder consumes no bytes and decodes no items.
+///
+/// This is created by calling `DecodeExt::conditional` method.
+#[derive(Debug, Default)]
+pub struct Conditional<D> {
+    inner: D,
+    enabled: bool,
+}
+impl<D> Conditional<D> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    /// Enables or disables the decoder.
+    ///
+    /// This should be called between items.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the decoder is enabled, otherwise `false`.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn new(inner: D, enabled: bool) -> Self {
+        Conditional { inner, enabled }
+    }
+}
+impl<D: Decode> Decode for Conditional<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.enabled {
+            track!(self.inner.decode(buf, eos))
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(
+            self.enabled,
+            ErrorKind::InconsistentState,
+            "The decoder is disabled"
+        );
+        track!(self.inner.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.enabled {
+            self.inner.requiring_bytes()
+        } else {
+            ByteCount::Finite(0)
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.enabled || self.inner.is_idle()
+    }
+}
+
+/// Combinator for representing an optional encoder.
+#[derive(Debug, Default)]
+pub struct Optional<E>(E);
+impl<E> Optional<E> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.0
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.0
     }
 
     pub(crate) fn new(inner: E) -> Self {
@@ -581,6 +1316,9 @@ impl<E: SizedEncode> SizedEncode for Optional<E> {
 /// `Collect` decodes all items until it reaches EOS
 /// and returns the collected items as the single decoded item.
 ///
+/// Any collection that implements `Extend` and `Default` can be used as the target
+/// (e.g., `SmallVec` for avoiding heap allocations of small collections).
+///
 /// This is created by calling `DecodeExt::collect` method.
 #[derive(Debug, Default)]
 pub struct Collect<D, T> {
@@ -655,169 +1393,127 @@ where
     fn is_idle(&self) -> bool {
         self.eos
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        if self.eos {
+            DecodeStatus::Idle
+        } else {
+            match self.inner.decode_status() {
+                DecodeStatus::Idle => DecodeStatus::NeedMoreBytes,
+                status => status,
+            }
+        }
+    }
 }
 
-/// Combinator for consuming the specified number of bytes exactly.
+/// Combinator for collecting decoded items except consecutive duplicates.
 ///
-/// This is created by calling `{DecodeExt, EncodeExt}::length` method.
+/// `DedupCollect` decodes all items until it reaches EOS
+/// and returns the collected items as the single decoded item.
+/// An item equal to the previously decoded one is skipped.
+///
+/// This is created by calling `DecodeExt::dedup` method.
 #[derive(Debug, Default)]
-pub struct Length<C> {
-    inner: C,
-    expected_bytes: u64,
-    remaining_bytes: u64,
+pub struct DedupCollect<D: Decode, T> {
+    inner: D,
+    items: T,
+    last: Option<D::Item>,
+    eos: bool,
 }
-impl<C> Length<C> {
-    pub(crate) fn new(inner: C, expected_bytes: u64) -> Self {
-        Length {
-            inner,
-            expected_bytes,
-            remaining_bytes: expected_bytes,
-        }
-    }
-
-    /// Returns the number of bytes expected to be consumed for decoding an item.
-    pub fn expected_bytes(&self) -> u64 {
-        self.expected_bytes
-    }
-
-    /// Sets the number of bytes expected to be consumed for decoding an item.
-    ///
-    /// # Errors
-    ///
-    /// If it is in the middle of decoding an item, it willl return an `ErrorKind::IncompleteDecoding` error.
-    pub fn set_expected_bytes(&mut self, bytes: u64) -> Result<()> {
-        track_assert_eq!(
-            self.remaining_bytes,
-            self.expected_bytes,
-            ErrorKind::IncompleteDecoding,
-            "An item is being decoded"
-        );
-        self.expected_bytes = bytes;
-        self.remaining_bytes = bytes;
-        Ok(())
-    }
-
-    /// Returns the number of remaining bytes required to decode the next item.
-    pub fn remaining_bytes(&self) -> u64 {
-        self.remaining_bytes
-    }
-
-    /// Returns a reference to the inner encoder or decoder.
-    pub fn inner_ref(&self) -> &C {
+impl<D: Decode, T: Default> DedupCollect<D, T> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
         &self.inner
     }
 
-    /// Returns a mutable reference to the inner encoder or decoder.
-    pub fn inner_mut(&mut self) -> &mut C {
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
         &mut self.inner
     }
 
-    /// Takes ownership of this instance and returns the inner encoder or decoder.
-    pub fn into_inner(self) -> C {
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
         self.inner
     }
+
+    pub(crate) fn new(inner: D) -> Self {
+        DedupCollect {
+            inner,
+            items: T::default(),
+            last: None,
+            eos: false,
+        }
+    }
 }
-impl<D: Decode> Decode for Length<D> {
-    type Item = D::Item;
+impl<D, T: Default> Decode for DedupCollect<D, T>
+where
+    D: Decode,
+    D::Item: PartialEq + Clone,
+    T: Extend<D::Item>,
+{
+    type Item = T;
 
     fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
-        let limit = cmp::min(buf.len() as u64, self.remaining_bytes) as usize;
-        let required = self.remaining_bytes - limit as u64;
-        let expected_eos = Eos::with_remaining_bytes(ByteCount::Finite(required));
-        if let Some(mut remaining) = eos.remaining_bytes().to_u64() {
-            remaining += buf.len() as u64;
-            track_assert!(remaining >= required, ErrorKind::UnexpectedEos; remaining, required);
+        if self.eos {
+            return Ok(0);
         }
 
-        let size = track!(self.inner.decode(&buf[..limit], expected_eos))?;
-        self.remaining_bytes -= size as u64;
-        Ok(size)
+        let mut offset = 0;
+        while offset < buf.len() {
+            bytecodec_try_decode!(self.inner, offset, buf, eos);
+
+            let item = track!(self.inner.finish_decoding())?;
+            if self.last.as_ref() != Some(&item) {
+                self.last = Some(item.clone());
+                self.items.extend(iter::once(item));
+            }
+        }
+        if eos.is_reached() {
+            self.eos = true;
+        }
+        Ok(offset)
     }
 
     fn finish_decoding(&mut self) -> Result<Self::Item> {
-        track_assert_eq!(self.remaining_bytes, 0, ErrorKind::IncompleteDecoding);
-        self.remaining_bytes = self.expected_bytes;
-
-        track!(self.inner.finish_decoding())
+        track_assert!(self.eos, ErrorKind::IncompleteDecoding);
+        self.eos = false;
+        self.last = None;
+        let items = mem::take(&mut self.items);
+        Ok(items)
     }
 
     fn requiring_bytes(&self) -> ByteCount {
-        ByteCount::Finite(self.remaining_bytes)
+        if self.eos {
+            ByteCount::Finite(0)
+        } else {
+            self.inner.requiring_bytes()
+        }
     }
 
     fn is_idle(&self) -> bool {
-        self.inner.is_idle()
+        self.eos
     }
 }
-impl<E: Encode> Encode for Length<E> {
-    type Item = E::Item;
 
-    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
-        if (buf.len() as u64) < self.remaining_bytes {
-            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
-        }
-
-        let (limit, eos) = if (buf.len() as u64) < self.remaining_bytes {
-            (buf.len(), eos)
-        } else {
-            (self.remaining_bytes as usize, Eos::new(true))
-        };
-        let size = track!(self.inner.encode(&mut buf[..limit], eos))?;
-        self.remaining_bytes -= size as u64;
-        if self.inner.is_idle() {
-            track_assert_eq!(
-                self.remaining_bytes,
-                0,
-                ErrorKind::InvalidInput,
-                "Too small item"
-            );
-        }
-        Ok(size)
-    }
-
-    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
-        track_assert_eq!(
-            self.remaining_bytes,
-            self.expected_bytes,
-            ErrorKind::EncoderFull
-        );
-        self.remaining_bytes = self.expected_bytes;
-        track!(self.inner.start_encoding(item))
-    }
-
-    fn requiring_bytes(&self) -> ByteCount {
-        ByteCount::Finite(self.remaining_bytes)
-    }
-
-    fn is_idle(&self) -> bool {
-        self.remaining_bytes == 0
-    }
-}
-impl<E: Encode> SizedEncode for Length<E> {
-    fn exact_requiring_bytes(&self) -> u64 {
-        self.remaining_bytes
-    }
-}
-
-/// Combinator for decoding the specified number of items and collecting the result.
-///
-/// This is created by calling `DecodeExt::collectn` method.
-#[derive(Debug, Default)]
-pub struct CollectN<D, T> {
-    inner: D,
-    remaining_items: usize,
-    items: T,
-}
-impl<D, T: Default> CollectN<D, T> {
-    /// Returns the number of remaining items expected to be decoded.
-    pub fn remaining_items(&self) -> usize {
-        self.remaining_items
-    }
-
-    /// Sets the number of remaining items expected to be decoded.
-    pub fn set_remaining_items(&mut self, n: usize) {
-        self.remaining_items = n;
-    }
+/// Combinator for pulling decoded items one at a time from a read buffer until it reaches EOS.
+///
+/// Unlike `Collect`, the items are not materialized at once.
+/// Each call of `next_item` method decodes (at most) one item from the given read buffer.
+///
+/// This is created by calling `DecodeExt::repeat_until_eos` method.
+#[derive(Debug, Default)]
+pub struct RepeatUntilEos<D> {
+    inner: D,
+    in_progress: bool,
+    eos: bool,
+}
+impl<D> RepeatUntilEos<D> {
+    /// Returns `true` if the read buffer reached EOS between items, otherwise `false`.
+    ///
+    /// Once this returns `true`, `next_item` method always returns `Ok(None)`.
+    pub fn is_eos(&self) -> bool {
+        self.eos
+    }
 
     /// Returns a reference to the inner decoder.
     pub fn inner_ref(&self) -> &D {
@@ -834,65 +1530,64 @@ impl<D, T: Default> CollectN<D, T> {
         self.inner
     }
 
-    pub(crate) fn new(inner: D, count: usize) -> Self {
-        CollectN {
+    pub(crate) fn new(inner: D) -> Self {
+        RepeatUntilEos {
             inner,
-            remaining_items: count,
-            items: T::default(),
+            in_progress: false,
+            eos: false,
         }
     }
 }
-impl<D, T> Decode for CollectN<D, T>
-where
-    D: Decode,
-    T: Default + Extend<D::Item>,
-{
-    type Item = T;
-
-    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
-        let mut offset = 0;
-        while self.remaining_items != 0 && offset < buf.len() {
-            bytecodec_try_decode!(self.inner, offset, buf, eos);
-
-            let item = track!(self.inner.finish_decoding())?;
-            self.items.extend(iter::once(item));
-            self.remaining_items -= 1;
+impl<D: Decode> RepeatUntilEos<D> {
+    /// Decodes the next item from the given read buffer.
+    ///
+    /// This returns `Ok(None)` if the read buffer reached EOS between items
+    /// (see `is_eos` method) or if more bytes are needed to decode the next item
+    /// (in that case, the caller should fill the buffer and call this method again).
+    ///
+    /// If the read buffer reached EOS in the middle of an item, an error will be returned.
+    pub fn next_item<B>(&mut self, buf: &mut ReadBuf<B>) -> Result<Option<D::Item>>
+    where
+        B: AsRef<[u8]> + AsMut<[u8]>,
+    {
+        if self.eos {
+            return Ok(None);
         }
-        if self.remaining_items != 0 {
-            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        if buf.is_empty() && buf.stream_state().is_eos() && !self.in_progress {
+            self.eos = true;
+            return Ok(None);
         }
-        Ok(offset)
-    }
-
-    fn finish_decoding(&mut self) -> Result<Self::Item> {
-        track_assert_eq!(self.remaining_items, 0, ErrorKind::IncompleteDecoding);
-        let items = mem::take(&mut self.items);
-        Ok(items)
-    }
 
-    fn requiring_bytes(&self) -> ByteCount {
-        if self.remaining_items == 0 {
-            ByteCount::Finite(0)
+        let before = buf.len();
+        track!(self.inner.decode_from_read_buf(buf))?;
+        if buf.len() != before {
+            self.in_progress = true;
+        }
+        if self.inner.is_idle() {
+            self.in_progress = false;
+            let item = track!(self.inner.finish_decoding())?;
+            Ok(Some(item))
         } else {
-            self.inner.requiring_bytes()
+            Ok(None)
         }
     }
-
-    fn is_idle(&self) -> bool {
-        self.remaining_items == 0
-    }
 }
 
-/// Combinator which tries to convert decoded values by calling the specified function.
+/// Combinator for collecting decoded items until an item satisfies the specified predicate.
 ///
-/// This is created by calling `DecodeExt::try_map` method.
+/// The item that satisfies the predicate (i.e., the terminator) is not included in the result.
+/// If the input reaches EOS before the terminator is decoded,
+/// an `ErrorKind::UnexpectedEos` error will be returned.
+///
+/// This is created by calling `DecodeExt::collect_until` method.
 #[derive(Debug)]
-pub struct TryMap<D, T, E, F> {
+pub struct CollectUntil<D, T, F> {
     inner: D,
-    try_map: F,
-    _phantom: PhantomData<(T, E)>,
+    items: T,
+    is_terminator: F,
+    terminated: bool,
 }
-impl<D, T, E, F> TryMap<D, T, E, F> {
+impl<D, T: Default, F> CollectUntil<D, T, F> {
     /// Returns a reference to the inner decoder.
     pub fn inner_ref(&self) -> &D {
         &self.inner
@@ -908,86 +1603,110 @@ impl<D, T, E, F> TryMap<D, T, E, F> {
         self.inner
     }
 
-    pub(crate) fn new(inner: D, try_map: F) -> Self {
-        TryMap {
+    pub(crate) fn new(inner: D, is_terminator: F) -> Self {
+        CollectUntil {
             inner,
-            try_map,
-            _phantom: PhantomData,
+            items: T::default(),
+            is_terminator,
+            terminated: false,
         }
     }
 }
-impl<D, T, E, F> Decode for TryMap<D, T, E, F>
+impl<D, T: Default, F> Decode for CollectUntil<D, T, F>
 where
     D: Decode,
-    F: Fn(D::Item) -> std::result::Result<T, E>,
-    Error: From<E>,
+    T: Extend<D::Item>,
+    F: Fn(&D::Item) -> bool,
 {
     type Item = T;
 
     fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
-        track!(self.inner.decode(buf, eos))
+        if self.terminated {
+            return Ok(0);
+        }
+
+        let mut offset = 0;
+        while offset < buf.len() {
+            bytecodec_try_decode!(self.inner, offset, buf, eos);
+
+            let item = track!(self.inner.finish_decoding())?;
+            if (self.is_terminator)(&item) {
+                self.terminated = true;
+                return Ok(offset);
+            }
+            self.items.extend(iter::once(item));
+        }
+        track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        Ok(offset)
     }
 
     fn finish_decoding(&mut self) -> Result<Self::Item> {
-        let item = track!(self.inner.finish_decoding())?;
-        track!((self.try_map)(item).map_err(Error::from))
+        track_assert!(self.terminated, ErrorKind::IncompleteDecoding);
+        self.terminated = false;
+        let items = mem::take(&mut self.items);
+        Ok(items)
     }
 
     fn requiring_bytes(&self) -> ByteCount {
-        self.inner.requiring_bytes()
+        if self.terminated {
+            ByteCount::Finite(0)
+        } else {
+            self.inner.requiring_bytes()
+        }
     }
 
     fn is_idle(&self) -> bool {
-        self.inner.is_idle()
+        self.terminated
     }
 }
 
-/// Combinator that will fail if the number of consumed bytes exceeds the specified size.
-///
-/// This is created by calling `{DecodeExt, EncodeExt}::max_bytes` method.
+/// Combinator for consuming the specified number of bytes exactly.
 ///
-/// Note that `MaxBytes` assumes the inner decoder will consume all the bytes in the target stream.
+/// This is created by calling `{DecodeExt, EncodeExt}::length` method.
 #[derive(Debug, Default)]
-pub struct MaxBytes<C> {
+pub struct Length<C> {
     inner: C,
-    consumed_bytes: u64,
-    max_bytes: u64,
+    expected_bytes: u64,
+    remaining_bytes: u64,
 }
-impl<C> MaxBytes<C> {
-    pub(crate) fn new(inner: C, max_bytes: u64) -> Self {
-        MaxBytes {
+impl<C> Length<C> {
+    pub(crate) fn new(inner: C, expected_bytes: u64) -> Self {
+        Length {
             inner,
-            consumed_bytes: 0,
-            max_bytes,
+            expected_bytes,
+            remaining_bytes: expected_bytes,
         }
     }
 
-    /// Returns the number of bytes consumed for encoding/decoding the current item.
-    pub fn consumed_bytes(&self) -> u64 {
-        self.consumed_bytes
-    }
-
-    /// Returns the maximum number of bytes that can be consumed for encoding/decoding an item.
-    pub fn max_bytes(&self) -> u64 {
-        self.max_bytes
+    /// Returns the number of bytes expected to be consumed for decoding an item.
+    pub fn expected_bytes(&self) -> u64 {
+        self.expected_bytes
     }
 
-    /// Sets the maximum number of bytes that can be consumed for encoding/decoding an item.
+    /// Sets the number of bytes expected to be consumed for decoding an item.
     ///
-    /// # Error
+    /// This must be called between items (i.e., before starting to decode or encode an item).
     ///
-    /// If `n` is smaller than `self.consumed_bytes()`, an `ErrorKind::InvalidInput` error will be returned.
-    pub fn set_max_bytes(&mut self, n: u64) -> Result<()> {
-        track_assert!(
-            self.consumed_bytes <= n,
-            ErrorKind::InvalidInput;
-            self.consumed_bytes,
-            n
+    /// # Errors
+    ///
+    /// If it is in the middle of decoding an item, it willl return an `ErrorKind::IncompleteDecoding` error.
+    pub fn set_expected_bytes(&mut self, bytes: u64) -> Result<()> {
+        track_assert_eq!(
+            self.remaining_bytes,
+            self.expected_bytes,
+            ErrorKind::IncompleteDecoding,
+            "An item is being decoded"
         );
-        self.max_bytes = n;
+        self.expected_bytes = bytes;
+        self.remaining_bytes = bytes;
         Ok(())
     }
 
+    /// Returns the number of remaining bytes required to decode the next item.
+    pub fn remaining_bytes(&self) -> u64 {
+        self.remaining_bytes
+    }
+
     /// Returns a reference to the inner encoder or decoder.
     pub fn inner_ref(&self) -> &C {
         &self.inner
@@ -1003,322 +1722,344 @@ impl<C> MaxBytes<C> {
         self.inner
     }
 }
-impl<D: Decode> Decode for MaxBytes<D> {
+impl<D: Decode> Decode for Length<D> {
     type Item = D::Item;
 
     fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
-        match eos.remaining_bytes() {
-            ByteCount::Infinite => {
-                track_panic!(ErrorKind::InvalidInput, "Max bytes limit exceeded";
-                             self.consumed_bytes, self.max_bytes)
-            }
-            ByteCount::Unknown => {
-                let consumable_bytes = self.max_bytes - self.consumed_bytes;
-                track_assert!((buf.len() as u64) <= consumable_bytes,
-                              ErrorKind::InvalidInput, "Max bytes limit exceeded";
-                              buf.len(), self.consumed_bytes, self.max_bytes);
-            }
-            ByteCount::Finite(remaining_bytes) => {
-                let consumable_bytes = self.max_bytes - self.consumed_bytes;
-                track_assert!((buf.len() as u64) + remaining_bytes <= consumable_bytes,
-                              ErrorKind::InvalidInput, "Max bytes limit exceeded";
-                              buf.len(), remaining_bytes, self.consumed_bytes, self.max_bytes)
-            }
+        let limit = cmp::min(buf.len() as u64, self.remaining_bytes) as usize;
+        let required = self.remaining_bytes - limit as u64;
+        let expected_eos = Eos::with_remaining_bytes(ByteCount::Finite(required));
+        if let Some(mut remaining) = eos.remaining_bytes().to_u64() {
+            remaining += buf.len() as u64;
+            track_assert!(remaining >= required, ErrorKind::UnexpectedEos; remaining, required);
         }
 
-        let size = track!(self.inner.decode(buf, eos))?;
-        self.consumed_bytes += size as u64;
+        let size = track!(self.inner.decode(&buf[..limit], expected_eos))?;
+        track_assert!(size <= limit, ErrorKind::InconsistentState; size, limit);
+        self.remaining_bytes -= size as u64;
         Ok(size)
     }
 
     fn finish_decoding(&mut self) -> Result<Self::Item> {
-        self.consumed_bytes = 0;
+        track_assert_eq!(self.remaining_bytes, 0, ErrorKind::IncompleteDecoding);
+        self.remaining_bytes = self.expected_bytes;
+
         track!(self.inner.finish_decoding())
     }
 
     fn requiring_bytes(&self) -> ByteCount {
-        self.inner.requiring_bytes()
+        ByteCount::Finite(self.remaining_bytes)
     }
 
     fn is_idle(&self) -> bool {
         self.inner.is_idle()
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
 }
-impl<E: Encode> Encode for MaxBytes<E> {
+impl<E: Encode> Encode for Length<E> {
     type Item = E::Item;
 
     fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
-        let size = track!(self.inner.encode(buf, eos))?;
-        self.consumed_bytes += size as u64;
-        track_assert!(self.consumed_bytes <= self.max_bytes,
-                      ErrorKind::InvalidInput, "Max bytes limit exceeded";
-                      self.consumed_bytes, self.max_bytes);
-        if self.is_idle() {
-            self.consumed_bytes = 0;
+        if (buf.len() as u64) < self.remaining_bytes {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
         }
-        Ok(size)
-    }
 
-    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
-        track!(self.inner.start_encoding(item))
-    }
-
-    fn requiring_bytes(&self) -> ByteCount {
-        self.inner.requiring_bytes()
-    }
-
-    fn is_idle(&self) -> bool {
-        self.inner.is_idle()
-    }
-}
-impl<E: SizedEncode> SizedEncode for MaxBytes<E> {
-    fn exact_requiring_bytes(&self) -> u64 {
-        self.inner.exact_requiring_bytes()
-    }
-}
-
-/// Combinator for pre-encoding items when `start_encoding` method is called.
-///
-/// This is created by calling `EncodeExt::pre_encode` method.
-#[derive(Debug, Default)]
-pub struct PreEncode<E> {
-    inner: E,
-    pre_encoded: BytesEncoder<Vec<u8>>,
-}
-impl<E> PreEncode<E> {
-    /// Returns a reference to the inner encoder.
-    pub fn inner_ref(&self) -> &E {
-        &self.inner
-    }
-
-    /// Returns a mutable reference to the inner encoder.
-    pub fn inner_mut(&mut self) -> &mut E {
-        &mut self.inner
-    }
-
-    /// Takes ownership of this instance and returns the inner encoder.
-    pub fn into_inner(self) -> E {
-        self.inner
-    }
-
-    pub(crate) fn new(inner: E) -> Self {
-        PreEncode {
-            inner,
-            pre_encoded: BytesEncoder::new(),
+        let (limit, eos) = if (buf.len() as u64) < self.remaining_bytes {
+            (buf.len(), eos)
+        } else {
+            (self.remaining_bytes as usize, Eos::new(true))
+        };
+        let size = track!(self.inner.encode(&mut buf[..limit], eos))?;
+        track_assert!(size <= limit, ErrorKind::InconsistentState; size, limit);
+        self.remaining_bytes -= size as u64;
+        if self.inner.is_idle() {
+            track_assert_eq!(
+                self.remaining_bytes,
+                0,
+                ErrorKind::InvalidInput,
+                "Too small item"
+            );
         }
-    }
-}
-impl<E: Encode> Encode for PreEncode<E> {
-    type Item = E::Item;
-
-    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
-        track!(self.pre_encoded.encode(buf, eos))
+        Ok(size)
     }
 
     fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
-        let buf = track!(self.inner.encode_into_bytes(item))?;
-        track!(self.pre_encoded.start_encoding(buf))?;
-        Ok(())
+        track_assert_eq!(
+            self.remaining_bytes,
+            self.expected_bytes,
+            ErrorKind::EncoderFull
+        );
+        self.remaining_bytes = self.expected_bytes;
+        track!(self.inner.start_encoding(item))
     }
 
     fn requiring_bytes(&self) -> ByteCount {
-        self.pre_encoded.requiring_bytes()
+        ByteCount::Finite(self.remaining_bytes)
     }
 
     fn is_idle(&self) -> bool {
-        self.pre_encoded.is_idle()
+        self.remaining_bytes == 0
     }
 }
-impl<E: Encode> SizedEncode for PreEncode<E> {
+impl<E: Encode> SizedEncode for Length<E> {
     fn exact_requiring_bytes(&self) -> u64 {
-        self.pre_encoded.exact_requiring_bytes()
+        self.remaining_bytes
     }
 }
 
-/// Combinator for slicing an input/output byte sequence by the specified number of bytes.
+/// Combinator for decoding an item from at most the specified number of bytes.
 ///
-/// This is created by calling `{DecodeExt, EncodeExt}::slice`.
+/// The inner decoder is given a synthetic EOS at the boundary,
+/// so a decoder that consumes bytes until EOS (e.g., `RemainingBytesDecoder`) stops there.
+/// Unlike `Length`, the item is not required to consume all the bytes.
+///
+/// This is created by calling `DecodeExt::take` method.
 #[derive(Debug, Default)]
-pub struct Slice<T> {
-    inner: T,
-    consumable_bytes: u64,
+pub struct Take<D> {
+    inner: D,
+    max_bytes: u64,
+    remaining_bytes: u64,
 }
-impl<T> Slice<T> {
-    pub(crate) fn new(inner: T) -> Self {
-        Slice {
+impl<D> Take<D> {
+    pub(crate) fn new(inner: D, max_bytes: u64) -> Self {
+        Take {
             inner,
-            consumable_bytes: 0,
+            max_bytes,
+            remaining_bytes: max_bytes,
         }
     }
 
-    /// Returns the number of remaining bytes consumable in this slice.
-    ///
-    /// The inner decoder or encoder will be suspended if the consumable bytes reaches to `0`.
-    pub fn consumable_bytes(&self) -> u64 {
-        self.consumable_bytes
-    }
-
-    /// Set the number of remaining bytes consumable in this slice.
-    pub fn set_consumable_bytes(&mut self, n: u64) {
-        self.consumable_bytes = n;
+    /// Returns the maximum number of bytes that can be consumed for decoding an item.
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
     }
 
-    /// Returns `true` if the encoder or decoder cannot consume any more bytes, otherwise `false`.
-    ///
-    /// To resume its works, it is needed to reset the value of consumable bytes
-    /// by calling `set_consumable_bytes` method.
-    pub fn is_suspended(&self) -> bool {
-        self.consumable_bytes == 0
+    /// Returns the number of bytes that can still be consumed for decoding the current item.
+    pub fn remaining_bytes(&self) -> u64 {
+        self.remaining_bytes
     }
 
-    /// Returns a reference to the inner encoder or decoder.
-    pub fn inner_ref(&self) -> &T {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
         &self.inner
     }
 
-    /// Returns a mutable reference to the inner encoder or decoder.
-    pub fn inner_mut(&mut self) -> &mut T {
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
         &mut self.inner
     }
 
-    /// Takes ownership of this instance and returns the inner encoder or decoder.
-    pub fn into_inner(self) -> T {
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
         self.inner
     }
 }
-impl<D: Decode> Decode for Slice<D> {
+impl<D: Decode> Decode for Take<D> {
     type Item = D::Item;
 
     fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
-        let limit = cmp::min(buf.len() as u64, self.consumable_bytes) as usize;
-        let eos = eos.back((buf.len() - limit) as u64);
+        let limit = cmp::min(buf.len() as u64, self.remaining_bytes) as usize;
+        let boundary = self.remaining_bytes - limit as u64;
+        let eos = if boundary == 0 {
+            Eos::new(true)
+        } else {
+            // The item may end before the boundary, so it is not the exact number of remaining bytes
+            match eos.remaining_bytes() {
+                ByteCount::Finite(n) if n < boundary => eos,
+                _ => Eos::new(false),
+            }
+        };
+
         let size = track!(self.inner.decode(&buf[..limit], eos))?;
-        self.consumable_bytes -= size as u64;
+        track_assert!(size <= limit, ErrorKind::InconsistentState; size, limit);
+        self.remaining_bytes -= size as u64;
         Ok(size)
     }
 
     fn finish_decoding(&mut self) -> Result<Self::Item> {
+        self.remaining_bytes = self.max_bytes;
         track!(self.inner.finish_decoding())
     }
 
     fn requiring_bytes(&self) -> ByteCount {
-        self.inner.requiring_bytes()
-    }
-
-    fn is_idle(&self) -> bool {
-        self.inner.is_idle()
-    }
-}
-impl<E: Encode> Encode for Slice<E> {
-    type Item = E::Item;
-
-    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
-        let limit = cmp::min(buf.len() as u64, self.consumable_bytes) as usize;
-        let eos = eos.back((buf.len() - limit) as u64);
-        let size = track!(self.inner.encode(&mut buf[..limit], eos))?;
-        self.consumable_bytes -= size as u64;
-        Ok(size)
-    }
-
-    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
-        track!(self.inner.start_encoding(item))
+        match self.inner.requiring_bytes() {
+            ByteCount::Finite(n) => ByteCount::Finite(cmp::min(n, self.remaining_bytes)),
+            ByteCount::Infinite => ByteCount::Finite(self.remaining_bytes),
+            ByteCount::Unknown => ByteCount::Unknown,
+        }
     }
 
     fn is_idle(&self) -> bool {
         self.inner.is_idle()
     }
 
-    fn requiring_bytes(&self) -> ByteCount {
-        self.inner.requiring_bytes()
-    }
-}
-impl<E: SizedEncode> SizedEncode for Slice<E> {
-    fn exact_requiring_bytes(&self) -> u64 {
-        self.inner.exact_requiring_bytes()
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
     }
 }
 
-/// Combinator for representing encoders that accepts only one additional item.
+/// Combinator for decoding the specified number of items and collecting the result.
 ///
-/// This is created by calling `EncodeExt::last`.
+/// This is created by calling `DecodeExt::collectn` method.
 #[derive(Debug, Default)]
-pub struct Last<E: Encode> {
-    inner: E,
-    item: Option<E::Item>,
+pub struct CollectN<D, T> {
+    inner: D,
+    remaining_items: usize,
+    items: T,
 }
-impl<E: Encode> Last<E> {
-    /// Returns a reference to the inner encoder.
-    pub fn inner_ref(&self) -> &E {
+impl<D, T: Default> CollectN<D, T> {
+    /// Returns the number of remaining items expected to be decoded.
+    pub fn remaining_items(&self) -> usize {
+        self.remaining_items
+    }
+
+    /// Sets the number of remaining items expected to be decoded.
+    pub fn set_remaining_items(&mut self, n: usize) {
+        self.remaining_items = n;
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
         &self.inner
     }
 
-    /// Returns a mutable reference to the inner encoder.
-    pub fn inner_mut(&mut self) -> &mut E {
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
         &mut self.inner
     }
 
-    /// Takes ownership of this instance and returns the inner encoder.
-    pub fn into_inner(self) -> E {
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
         self.inner
     }
 
-    pub(crate) fn new(inner: E, item: E::Item) -> Self {
-        Last {
+    pub(crate) fn new(inner: D, count: usize) -> Self {
+        CollectN {
             inner,
-            item: Some(item),
+            remaining_items: count,
+            items: T::default(),
         }
     }
 }
-impl<E: Encode> Encode for Last<E> {
-    type Item = Never;
+impl<D, T> Decode for CollectN<D, T>
+where
+    D: Decode,
+    T: Default + Extend<D::Item>,
+{
+    type Item = T;
 
-    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
-        if self.inner.is_idle() {
-            if let Some(item) = self.item.take() {
-                track!(self.inner.start_encoding(item))?;
-            }
-        }
-        track!(self.inner.encode(buf, eos))
-    }
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        while self.remaining_items != 0 && offset < buf.len() {
+            bytecodec_try_decode!(self.inner, offset, buf, eos);
 
-    fn start_encoding(&mut self, _item: Self::Item) -> Result<()> {
-        unreachable!()
+            let item = track!(self.inner.finish_decoding())?;
+            self.items.extend(iter::once(item));
+            self.remaining_items -= 1;
+        }
+        if self.remaining_items != 0 {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        }
+        Ok(offset)
     }
 
-    fn is_idle(&self) -> bool {
-        self.item.is_none() && self.inner.is_idle()
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert_eq!(self.remaining_items, 0, ErrorKind::IncompleteDecoding);
+        let items = mem::take(&mut self.items);
+        Ok(items)
     }
 
     fn requiring_bytes(&self) -> ByteCount {
-        if self.item.is_some() {
-            ByteCount::Unknown
+        if self.remaining_items == 0 {
+            ByteCount::Finite(0)
         } else {
             self.inner.requiring_bytes()
         }
     }
-}
-impl<E: SizedEncode> SizedEncode for Last<E> {
-    fn exact_requiring_bytes(&self) -> u64 {
-        self.inner.exact_requiring_bytes()
-    }
-}
 
-/// Combinator that enables to peek decoded items before calling `finish_decoding` method.
+    fn is_idle(&self) -> bool {
+        self.remaining_items == 0
+    }
+}
+
+/// Combinator which tries to convert decoded values by calling the specified function.
 ///
-/// This is created by calling `DecodeExt::peekable` method.
-pub struct Peekable<D: Decode> {
+/// This is created by calling `DecodeExt::try_map` method.
+#[derive(Debug)]
+pub struct TryMap<D, T, E, F> {
     inner: D,
-    item: Option<D::Item>,
+    try_map: F,
+    _phantom: PhantomData<(T, E)>,
 }
-impl<D: Decode> Peekable<D> {
-    /// Returns a reference to the last item decoded (but not `finish_decoding` called yet) by the decoder.
-    pub fn peek(&self) -> Option<&D::Item> {
-        self.item.as_ref()
+impl<D, T, E, F> TryMap<D, T, E, F> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
     }
 
-    /// Returns a mutable reference to the last item decoded (but not `finish_decoding` called yet) by the decoder.
-    pub fn peek_mut(&mut self) -> Option<&mut D::Item> {
-        self.item.as_mut()
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D, try_map: F) -> Self {
+        TryMap {
+            inner,
+            try_map,
+            _phantom: PhantomData,
+        }
+    }
+}
+impl<D, T, E, F> Decode for TryMap<D, T, E, F>
+where
+    D: Decode,
+    F: Fn(D::Item) -> std::result::Result<T, E>,
+    Error: From<E>,
+{
+    type Item = T;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track!(self.inner.finish_decoding())?;
+        track!((self.try_map)(item).map_err(Error::from))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator which checks that decoded values are equal to the expected value.
+///
+/// This is created by calling `DecodeExt::assert_eq` method.
+#[derive(Debug)]
+pub struct AssertEq<D: Decode> {
+    inner: D,
+    expected: D::Item,
+}
+impl<D: Decode> AssertEq<D> {
+    /// Returns the expected value.
+    pub fn expected(&self) -> &D::Item {
+        &self.expected
     }
 
     /// Returns a reference to the inner decoder.
@@ -1336,176 +2077,3303 @@ impl<D: Decode> Peekable<D> {
         self.inner
     }
 
-    pub(crate) fn new(inner: D) -> Self {
-        Peekable { inner, item: None }
+    pub(crate) fn new(inner: D, expected: D::Item) -> Self {
+        AssertEq { inner, expected }
     }
 }
-impl<D: Decode + fmt::Debug> fmt::Debug for Peekable<D> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Peekable {{ inner: {:?}, item.is_some(): {:?} }}",
-            self.inner,
-            self.item.is_some()
-        )
+impl<D> Decode for AssertEq<D>
+where
+    D: Decode,
+    D::Item: PartialEq + fmt::Debug,
+{
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track!(self.inner.finish_decoding())?;
+        track_assert_eq!(item, self.expected, ErrorKind::InvalidInput);
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
     }
 }
-impl<D: Decode + Default> Default for Peekable<D> {
-    fn default() -> Self {
-        Peekable {
-            inner: D::default(),
-            item: None,
+
+/// Combinator that will fail if the number of consumed bytes exceeds the specified size.
+///
+/// This is created by calling `{DecodeExt, EncodeExt}::max_bytes` method.
+///
+/// Note that `MaxBytes` assumes the inner decoder will consume all the bytes in the target stream.
+#[derive(Debug, Default)]
+pub struct MaxBytes<C> {
+    inner: C,
+    consumed_bytes: u64,
+    max_bytes: u64,
+}
+impl<C> MaxBytes<C> {
+    pub(crate) fn new(inner: C, max_bytes: u64) -> Self {
+        MaxBytes {
+            inner,
+            consumed_bytes: 0,
+            max_bytes,
         }
     }
+
+    /// Returns the number of bytes consumed for encoding/decoding the current item.
+    pub fn consumed_bytes(&self) -> u64 {
+        self.consumed_bytes
+    }
+
+    /// Returns the maximum number of bytes that can be consumed for encoding/decoding an item.
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Sets the maximum number of bytes that can be consumed for encoding/decoding an item.
+    ///
+    /// # Error
+    ///
+    /// If `n` is smaller than `self.consumed_bytes()`, an `ErrorKind::InvalidInput` error will be returned.
+    pub fn set_max_bytes(&mut self, n: u64) -> Result<()> {
+        track_assert!(
+            self.consumed_bytes <= n,
+            ErrorKind::InvalidInput;
+            self.consumed_bytes,
+            n
+        );
+        self.max_bytes = n;
+        Ok(())
+    }
+
+    /// Returns a reference to the inner encoder or decoder.
+    pub fn inner_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder or decoder.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder or decoder.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
 }
-impl<D: Decode> Decode for Peekable<D> {
+impl<D: Decode> Decode for MaxBytes<D> {
     type Item = D::Item;
 
     fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
-        if self.item.is_none() {
-            let size = track!(self.inner.decode(buf, eos))?;
-            if self.inner.is_idle() {
-                self.item = Some(track!(self.inner.finish_decoding())?);
+        match eos.remaining_bytes() {
+            ByteCount::Infinite => {
+                track_panic!(ErrorKind::InvalidInput, "Max bytes limit exceeded";
+                             self.consumed_bytes, self.max_bytes)
             }
-            Ok(size)
-        } else {
-            Ok(0)
+            ByteCount::Unknown => {
+                let consumable_bytes = self.max_bytes - self.consumed_bytes;
+                track_assert!((buf.len() as u64) <= consumable_bytes,
+                              ErrorKind::InvalidInput, "Max bytes limit exceeded";
+                              buf.len(), self.consumed_bytes, self.max_bytes);
+            }
+            ByteCount::Finite(remaining_bytes) => {
+                let consumable_bytes = self.max_bytes - self.consumed_bytes;
+                track_assert!((buf.len() as u64) + remaining_bytes <= consumable_bytes,
+                              ErrorKind::InvalidInput, "Max bytes limit exceeded";
+                              buf.len(), remaining_bytes, self.consumed_bytes, self.max_bytes)
+            }
+        }
+
+        let size = track!(self.inner.decode(buf, eos))?;
+        self.consumed_bytes += size as u64;
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        self.consumed_bytes = 0;
+        track!(self.inner.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+impl<E: Encode> Encode for MaxBytes<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let size = track!(self.inner.encode(buf, eos))?;
+        self.consumed_bytes += size as u64;
+        track_assert!(self.consumed_bytes <= self.max_bytes,
+                      ErrorKind::InvalidInput, "Max bytes limit exceeded";
+                      self.consumed_bytes, self.max_bytes);
+        if self.is_idle() {
+            self.consumed_bytes = 0;
+        }
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.inner.start_encoding(item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+impl<E: SizedEncode> SizedEncode for MaxBytes<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Combinator that will fail if the total number of consumed bytes exceeds the specified size.
+///
+/// Unlike `MaxBytes`, the count of consumed bytes is never reset at item boundaries,
+/// so this can be used to enforce a byte budget over the whole lifetime of a decoder (e.g., a connection).
+///
+/// The inner decoder is never given more bytes than the remaining budget.
+/// If it requires more bytes than that, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// This is created by calling `DecodeExt::total_max_bytes` method.
+#[derive(Debug, Default)]
+pub struct TotalMaxBytes<D> {
+    inner: D,
+    consumed_bytes: u64,
+    max_bytes: u64,
+}
+impl<D> TotalMaxBytes<D> {
+    pub(crate) fn new(inner: D, max_bytes: u64) -> Self {
+        TotalMaxBytes {
+            inner,
+            consumed_bytes: 0,
+            max_bytes,
+        }
+    }
+
+    /// Returns the total number of bytes consumed by this decoder.
+    pub fn consumed_bytes(&self) -> u64 {
+        self.consumed_bytes
+    }
+
+    /// Returns the maximum number of bytes that can be consumed by this decoder.
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for TotalMaxBytes<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let consumable_bytes = self.max_bytes - self.consumed_bytes;
+        if (buf.len() as u64) <= consumable_bytes {
+            let size = track!(self.inner.decode(buf, eos))?;
+            self.consumed_bytes += size as u64;
+            return Ok(size);
+        }
+
+        let excess_bytes = buf.len() as u64 - consumable_bytes;
+        let buf = &buf[..consumable_bytes as usize];
+        let size = track!(self.inner.decode(buf, eos.back(excess_bytes)))?;
+        self.consumed_bytes += size as u64;
+        if size == buf.len() {
+            track_assert!(self.inner.is_idle(), ErrorKind::InvalidInput,
+                          "Total max bytes limit exceeded"; self.consumed_bytes, self.max_bytes);
+        }
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track!(self.inner.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator that will fail if the number of consumed bytes for an item is less than the specified size.
+///
+/// This is created by calling `DecodeExt::min_bytes` method.
+#[derive(Debug, Default)]
+pub struct MinBytes<D> {
+    inner: D,
+    consumed_bytes: u64,
+    min_bytes: u64,
+}
+impl<D> MinBytes<D> {
+    pub(crate) fn new(inner: D, min_bytes: u64) -> Self {
+        MinBytes {
+            inner,
+            consumed_bytes: 0,
+            min_bytes,
+        }
+    }
+
+    /// Returns the number of bytes consumed for decoding the current item.
+    pub fn consumed_bytes(&self) -> u64 {
+        self.consumed_bytes
+    }
+
+    /// Returns the minimum number of bytes that must be consumed for decoding an item.
+    pub fn min_bytes(&self) -> u64 {
+        self.min_bytes
+    }
+
+    /// Sets the minimum number of bytes that must be consumed for decoding an item.
+    pub fn set_min_bytes(&mut self, n: u64) {
+        self.min_bytes = n;
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for MinBytes<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let size = track!(self.inner.decode(buf, eos))?;
+        self.consumed_bytes += size as u64;
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let consumed_bytes = self.consumed_bytes;
+        self.consumed_bytes = 0;
+        let item = track!(self.inner.finish_decoding())?;
+        track_assert!(consumed_bytes >= self.min_bytes,
+                      ErrorKind::InvalidInput, "Min bytes limit not reached";
+                      consumed_bytes, self.min_bytes);
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator for returning the number of bytes consumed for an item alongside the item.
+///
+/// This is created by calling `DecodeExt::with_consumed` method.
+#[derive(Debug, Default)]
+pub struct WithConsumed<D> {
+    inner: D,
+    consumed_bytes: usize,
+}
+impl<D> WithConsumed<D> {
+    pub(crate) fn new(inner: D) -> Self {
+        WithConsumed {
+            inner,
+            consumed_bytes: 0,
+        }
+    }
+
+    /// Returns the number of bytes consumed for decoding the current item.
+    pub fn consumed_bytes(&self) -> usize {
+        self.consumed_bytes
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for WithConsumed<D> {
+    type Item = (usize, D::Item);
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let size = track!(self.inner.decode(buf, eos))?;
+        self.consumed_bytes += size;
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track!(self.inner.finish_decoding())?;
+        let consumed_bytes = self.consumed_bytes;
+        self.consumed_bytes = 0;
+        Ok((consumed_bytes, item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator for returning the absolute byte offset at which an item began alongside the item.
+///
+/// The offset accumulates over the whole lifetime of the decoder (i.e., it is never reset).
+/// Since the start of an item is the end of the previous one,
+/// any bytes consumed between items are counted as a part of the following item.
+///
+/// This is created by calling `DecodeExt::with_offset` method.
+#[derive(Debug, Default)]
+pub struct WithOffset<D> {
+    inner: D,
+    offset: u64,
+    item_start: u64,
+}
+impl<D> WithOffset<D> {
+    pub(crate) fn new(inner: D) -> Self {
+        WithOffset {
+            inner,
+            offset: 0,
+            item_start: 0,
+        }
+    }
+
+    /// Returns the total number of bytes consumed by this decoder.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for WithOffset<D> {
+    type Item = (u64, D::Item);
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let size = track!(self.inner.decode(buf, eos))?;
+        self.offset += size as u64;
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track!(self.inner.finish_decoding())?;
+        let item_start = self.item_start;
+        self.item_start = self.offset;
+        Ok((item_start, item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator for reporting the number of bytes consumed for an item when decoding it fails.
+///
+/// This does not keep copies of the consumed bytes.
+/// Instead, the caller can use `consumed_before_error` to rewind its own buffer to the beginning of
+/// the failed item and retry decoding from there (e.g., with another decoder).
+/// Replaying the bytes internally would require buffering every byte of an item until it is decoded,
+/// which is unbounded for large or streaming items.
+///
+/// Note that the state of the inner decoder after an error depends on the decoder.
+///
+/// This is created by calling `DecodeExt::rewind_on_error` method.
+#[derive(Debug, Default)]
+pub struct RewindOnError<D> {
+    inner: D,
+    consumed_bytes: usize,
+    consumed_before_error: Option<usize>,
+}
+impl<D> RewindOnError<D> {
+    pub(crate) fn new(inner: D) -> Self {
+        RewindOnError {
+            inner,
+            consumed_bytes: 0,
+            consumed_before_error: None,
+        }
+    }
+
+    /// Returns the number of bytes consumed for the item that failed to be decoded most recently.
+    ///
+    /// This is reset to `None` when the next item is decoded successfully.
+    pub fn consumed_before_error(&self) -> Option<usize> {
+        self.consumed_before_error
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    fn record_error(&mut self) {
+        self.consumed_before_error = Some(self.consumed_bytes);
+        self.consumed_bytes = 0;
+    }
+}
+impl<D: Decode> Decode for RewindOnError<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        match self.inner.decode(buf, eos) {
+            Ok(size) => {
+                self.consumed_bytes += size;
+                Ok(size)
+            }
+            Err(e) => {
+                self.record_error();
+                Err(track!(e))
+            }
+        }
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        match self.inner.finish_decoding() {
+            Ok(item) => {
+                self.consumed_bytes = 0;
+                self.consumed_before_error = None;
+                Ok(item)
+            }
+            Err(e) => {
+                self.record_error();
+                Err(track!(e))
+            }
+        }
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Decoder that tries the alternative decoders in priority order until one of them succeeds.
+///
+/// The bytes consumed for the current item are buffered,
+/// so when an alternative fails they are replayed to the next alternative.
+/// The size of the buffer is bounded by `max_buffer_bytes`.
+/// If an alternative finishes decoding an item with fewer bytes than the buffered ones,
+/// the rest of the buffered bytes are used for decoding the next item.
+///
+/// Note that a failed alternative is reused for decoding subsequent items,
+/// so it should be able to start decoding a new item after returning an error.
+///
+/// # Errors
+///
+/// If all of the alternatives fail, the error of the last alternative is returned.
+/// If the buffer exceeds `max_buffer_bytes` before an item is decoded,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, ErrorKind};
+/// use bytecodec::combinator::FirstMatchDecoder;
+/// use bytecodec::fixnum::{U16beDecoder, U8Decoder};
+///
+/// let mut decoder = FirstMatchDecoder::new(16);
+/// decoder.add_alternative(U8Decoder::new().try_map(|b| {
+///     if b < 0x80 { Ok(u16::from(b)) } else { Err(ErrorKind::InvalidInput) }
+/// }));
+/// decoder.add_alternative(U16beDecoder::new());
+///
+/// assert_eq!(decoder.decode_from_bytes(&[0x7f][..]).unwrap(), 0x7f);
+/// assert_eq!(decoder.decode_from_bytes(&[0x80, 0x01][..]).unwrap(), 0x8001);
+/// ```
+pub struct FirstMatchDecoder<T> {
+    alternatives: Vec<Box<dyn Decode<Item = T>>>,
+    current: usize,
+    buffer: Vec<u8>,
+    replay_offset: usize,
+    max_buffer_bytes: usize,
+    item: Option<T>,
+}
+impl<T> FirstMatchDecoder<T> {
+    /// Makes a new `FirstMatchDecoder` instance that has no alternatives.
+    pub fn new(max_buffer_bytes: usize) -> Self {
+        FirstMatchDecoder {
+            alternatives: Vec::new(),
+            current: 0,
+            buffer: Vec::new(),
+            replay_offset: 0,
+            max_buffer_bytes,
+            item: None,
+        }
+    }
+
+    /// Adds an alternative decoder that has the lowest priority among the added ones.
+    pub fn add_alternative<D>(&mut self, decoder: D)
+    where
+        D: Decode<Item = T> + 'static,
+    {
+        self.alternatives.push(Box::new(decoder));
+    }
+
+    /// Returns the number of the alternatives.
+    pub fn alternative_count(&self) -> usize {
+        self.alternatives.len()
+    }
+
+    /// Returns the maximum number of bytes buffered for replaying.
+    pub fn max_buffer_bytes(&self) -> usize {
+        self.max_buffer_bytes
+    }
+
+    fn decode_current(&mut self, buf: &[u8], offset: &mut usize, eos: Eos) -> Result<()> {
+        let decoder = &mut self.alternatives[self.current];
+        let rest = buf.len() - *offset;
+        while self.replay_offset < self.buffer.len() && !decoder.is_idle() {
+            let replay_eos = eos.back(rest as u64);
+            let size = track!(decoder.decode(&self.buffer[self.replay_offset..], replay_eos))?;
+            self.replay_offset += size;
+            if size == 0 {
+                break;
+            }
+        }
+
+        if self.replay_offset == self.buffer.len() && !decoder.is_idle() {
+            let limit = cmp::min(rest, self.max_buffer_bytes - self.buffer.len());
+            let size =
+                track!(decoder.decode(&buf[*offset..][..limit], eos.back((rest - limit) as u64)))?;
+            self.buffer.extend_from_slice(&buf[*offset..][..size]);
+            self.replay_offset += size;
+            *offset += size;
+        }
+
+        if decoder.is_idle() {
+            self.item = Some(track!(decoder.finish_decoding())?);
+        }
+        Ok(())
+    }
+}
+impl<T> fmt::Debug for FirstMatchDecoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FirstMatchDecoder {{ alternatives: {}, current: {}, buffer: {:?}, replay_offset: {}, \
+             max_buffer_bytes: {} }}",
+            self.alternatives.len(),
+            self.current,
+            self.buffer,
+            self.replay_offset,
+            self.max_buffer_bytes
+        )
+    }
+}
+impl<T> Decode for FirstMatchDecoder<T> {
+    type Item = T;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.item.is_some() {
+            return Ok(0);
+        }
+        track_assert!(
+            !self.alternatives.is_empty(),
+            ErrorKind::InconsistentState,
+            "No alternatives"
+        );
+
+        let mut offset = 0;
+        while let Err(e) = self.decode_current(buf, &mut offset, eos) {
+            self.current += 1;
+            self.replay_offset = 0;
+            if self.current == self.alternatives.len() {
+                self.current = 0;
+                self.buffer.clear();
+                return Err(track!(e, "No alternatives matched"));
+            }
+        }
+        if self.item.is_none() {
+            track_assert!(
+                self.buffer.len() < self.max_buffer_bytes,
+                ErrorKind::InvalidInput,
+                "Replay buffer limit exceeded";
+                self.max_buffer_bytes
+            );
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track_assert_some!(self.item.take(), ErrorKind::IncompleteDecoding);
+        self.buffer.drain(..self.replay_offset);
+        self.replay_offset = 0;
+        self.current = 0;
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.item.is_some() {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.item.is_some()
+    }
+}
+
+/// Combinator for pre-encoding items when `start_encoding` method is called.
+///
+/// This is created by calling `EncodeExt::pre_encode` method.
+#[derive(Debug, Default)]
+pub struct PreEncode<E> {
+    inner: E,
+    pre_encoded: BytesEncoder<Vec<u8>>,
+}
+impl<E> PreEncode<E> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: E) -> Self {
+        PreEncode {
+            inner,
+            pre_encoded: BytesEncoder::new(),
+        }
+    }
+}
+impl<E: Encode> Encode for PreEncode<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.pre_encoded.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let buf = track!(self.inner.encode_into_bytes(item))?;
+        track!(self.pre_encoded.start_encoding(buf))?;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.pre_encoded.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.pre_encoded.is_idle()
+    }
+}
+impl<E: Encode> SizedEncode for PreEncode<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.pre_encoded.exact_requiring_bytes()
+    }
+}
+
+/// Combinator for appending the length of each encoded item as a suffix.
+///
+/// Each item is pre-encoded by the inner encoder (like `PreEncode`),
+/// then the encoded bytes are followed by their length encoded by the suffix encoder.
+///
+/// This is created by calling `EncodeExt::with_length_suffix` method.
+#[derive(Debug, Default)]
+pub struct WithLengthSuffix<E, S> {
+    inner: E,
+    pre_encoded: BytesEncoder<Vec<u8>>,
+    suffix: S,
+}
+impl<E, S> WithLengthSuffix<E, S> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the suffix encoder.
+    pub fn suffix_ref(&self) -> &S {
+        &self.suffix
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder and the suffix encoder.
+    pub fn into_inner(self) -> (E, S) {
+        (self.inner, self.suffix)
+    }
+
+    pub(crate) fn new(inner: E, suffix: S) -> Self {
+        WithLengthSuffix {
+            inner,
+            pre_encoded: BytesEncoder::new(),
+            suffix,
+        }
+    }
+}
+impl<E, S> Encode for WithLengthSuffix<E, S>
+where
+    E: Encode,
+    S: Encode<Item = u64>,
+{
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        bytecodec_try_encode!(self.pre_encoded, offset, buf, eos);
+        bytecodec_try_encode!(self.suffix, offset, buf, eos);
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        let buf = track!(self.inner.encode_into_bytes(item))?;
+        track!(self.suffix.start_encoding(buf.len() as u64))?;
+        track!(self.pre_encoded.start_encoding(buf))?;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.pre_encoded
+            .requiring_bytes()
+            .add_for_encoding(self.suffix.requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.pre_encoded.is_idle() && self.suffix.is_idle()
+    }
+}
+impl<E, S> SizedEncode for WithLengthSuffix<E, S>
+where
+    E: Encode,
+    S: SizedEncode<Item = u64>,
+{
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.pre_encoded.exact_requiring_bytes() + self.suffix.exact_requiring_bytes()
+    }
+}
+
+/// Decoder for items followed by their lengths (i.e., the items encoded by `WithLengthSuffix`).
+///
+/// Because the length is located at the end, this decoder buffers all bytes until it reaches EOS.
+/// Then the trailing bytes are decoded by the suffix decoder and the rest by the inner decoder.
+/// The suffix decoder must require a fixed number of bytes (i.e., `requiring_bytes()` must be finite).
+///
+/// By default the buffer size is unlimited, so it is recommended to set
+/// an upper limit (see `with_max_buffer_bytes`) when decoding untrusted input.
+///
+/// # Errors
+///
+/// If the decoded length does not match the actual length of the body or
+/// the number of buffered bytes exceeds the limit,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, EncodeExt, ErrorKind};
+/// use bytecodec::bytes::{Utf8Decoder, Utf8Encoder};
+/// use bytecodec::combinator::LengthSuffixDecoder;
+/// use bytecodec::fixnum::{U16beDecoder, U16beEncoder};
+/// use std::convert::TryFrom;
+/// use trackable::error::ErrorKindExt;
+///
+/// let mut encoder = Utf8Encoder::new().with_length_suffix(
+///     U16beEncoder::new().try_map_from(|n| u16::try_from(n).map_err(|e| ErrorKind::InvalidInput.cause(e))),
+/// );
+/// let bytes = encoder.encode_into_bytes("foo".to_owned()).unwrap();
+/// assert_eq!(bytes, b"foo\x00\x03");
+///
+/// let mut decoder = LengthSuffixDecoder::new(Utf8Decoder::new(), U16beDecoder::new().map(u64::from));
+/// assert_eq!(decoder.decode_from_bytes(&bytes).unwrap(), "foo");
+/// ```
+#[derive(Debug)]
+pub struct LengthSuffixDecoder<D: Decode, S> {
+    inner: D,
+    suffix: S,
+    buf: Vec<u8>,
+    item: Option<D::Item>,
+    max_buffer_bytes: Option<usize>,
+}
+impl<D: Decode, S> LengthSuffixDecoder<D, S> {
+    /// Makes a new `LengthSuffixDecoder` instance.
+    pub fn new(inner: D, suffix: S) -> Self {
+        LengthSuffixDecoder {
+            inner,
+            suffix,
+            buf: Vec::new(),
+            item: None,
+            max_buffer_bytes: None,
+        }
+    }
+
+    /// Makes a new `LengthSuffixDecoder` instance that buffers at most `max_buffer_bytes` bytes.
+    ///
+    /// If the number of bytes of an item (including the suffix) exceeds the limit,
+    /// an `ErrorKind::InvalidInput` error will be returned and the buffered bytes are discarded.
+    pub fn with_max_buffer_bytes(inner: D, suffix: S, max_buffer_bytes: usize) -> Self {
+        LengthSuffixDecoder {
+            max_buffer_bytes: Some(max_buffer_bytes),
+            ..Self::new(inner, suffix)
+        }
+    }
+
+    /// Returns the maximum number of bytes that can be buffered.
+    pub fn max_buffer_bytes(&self) -> Option<usize> {
+        self.max_buffer_bytes
+    }
+
+    /// Sets the maximum number of bytes that can be buffered.
+    ///
+    /// `None` means unlimited.
+    pub fn set_max_buffer_bytes(&mut self, max_buffer_bytes: Option<usize>) {
+        self.max_buffer_bytes = max_buffer_bytes;
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the suffix decoder.
+    pub fn suffix_ref(&self) -> &S {
+        &self.suffix
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder and the suffix decoder.
+    pub fn into_inner(self) -> (D, S) {
+        (self.inner, self.suffix)
+    }
+}
+impl<D, S> LengthSuffixDecoder<D, S>
+where
+    D: Decode,
+    S: Decode<Item = u64>,
+{
+    fn decode_buffered_bytes(&mut self) -> Result<D::Item> {
+        let suffix_size = track_assert_some!(
+            self.suffix.requiring_bytes().to_u64(),
+            ErrorKind::InconsistentState,
+            "The size of the suffix is not fixed"
+        ) as usize;
+        track_assert!(self.buf.len() >= suffix_size, ErrorKind::UnexpectedEos; self.buf.len(), suffix_size);
+
+        let body_size = self.buf.len() - suffix_size;
+        let (body, suffix) = self.buf.split_at(body_size);
+        let size = track!(self.suffix.decode(suffix, Eos::new(true)))?;
+        track_assert_eq!(size, suffix_size, ErrorKind::InconsistentState);
+        let length = track!(self.suffix.finish_decoding())?;
+        track_assert_eq!(
+            length,
+            body_size as u64,
+            ErrorKind::InvalidInput,
+            "Length suffix mismatched"
+        );
+
+        let size = track!(self.inner.decode(body, Eos::new(true)))?;
+        track_assert_eq!(size, body_size, ErrorKind::InvalidInput; self.inner.is_idle());
+        track!(self.inner.finish_decoding())
+    }
+}
+impl<D, S> Decode for LengthSuffixDecoder<D, S>
+where
+    D: Decode,
+    S: Decode<Item = u64>,
+{
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.item.is_some() {
+            return Ok(0);
+        }
+
+        if let Some(limit) = self.max_buffer_bytes {
+            let buffered = self.buf.len() + buf.len();
+            if buffered > limit {
+                self.buf.clear();
+                track_panic!(ErrorKind::InvalidInput, "Max buffer bytes limit exceeded";
+                             buffered, limit);
+            }
+        }
+        self.buf.extend_from_slice(buf);
+        if eos.is_reached() {
+            let result = self.decode_buffered_bytes();
+            self.buf.clear();
+            self.item = Some(track!(result)?);
+        }
+        Ok(buf.len())
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track_assert_some!(self.item.take(), ErrorKind::IncompleteDecoding);
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.item.is_some() {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Infinite
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.item.is_some()
+    }
+}
+
+/// Decoder for items preceded by a header that declares their lengths.
+///
+/// Unlike `Length`, the inner decoder is not limited by the declared length.
+/// Instead, `finish_decoding` verifies that the number of bytes consumed by the inner decoder
+/// equals to the declared length.
+/// This is useful for detecting truncated items or extra data.
+///
+/// # Errors
+///
+/// If the declared length does not match the number of consumed bytes,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, ErrorKind};
+/// use bytecodec::bytes::Utf8Decoder;
+/// use bytecodec::combinator::LengthVerifiedDecoder;
+/// use bytecodec::fixnum::U16beDecoder;
+///
+/// let mut decoder =
+///     LengthVerifiedDecoder::new(U16beDecoder::new().map(u64::from), Utf8Decoder::new());
+/// assert_eq!(decoder.decode_from_bytes(b"\x00\x03foo").unwrap(), "foo");
+///
+/// let error = decoder.decode_from_bytes(b"\x00\x04foo").err().unwrap();
+/// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+/// ```
+#[derive(Debug)]
+pub struct LengthVerifiedDecoder<H, D> {
+    header: H,
+    inner: D,
+    declared: Option<u64>,
+    consumed: u64,
+}
+impl<H, D> LengthVerifiedDecoder<H, D> {
+    /// Makes a new `LengthVerifiedDecoder` instance.
+    pub fn new(header: H, inner: D) -> Self {
+        LengthVerifiedDecoder {
+            header,
+            inner,
+            declared: None,
+            consumed: 0,
+        }
+    }
+
+    /// Returns the declared length of the item being decoded.
+    ///
+    /// If the header has not been decoded yet, this returns `None`.
+    pub fn declared_length(&self) -> Option<u64> {
+        self.declared
+    }
+
+    /// Returns a reference to the header decoder.
+    pub fn header_ref(&self) -> &H {
+        &self.header
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the header decoder and the inner decoder.
+    pub fn into_inner(self) -> (H, D) {
+        (self.header, self.inner)
+    }
+}
+impl<H, D> Decode for LengthVerifiedDecoder<H, D>
+where
+    H: Decode<Item = u64>,
+    D: Decode,
+{
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.declared.is_none() {
+            offset += track!(self.header.decode(buf, eos))?;
+            if !self.header.is_idle() {
+                return Ok(offset);
+            }
+            self.declared = Some(track!(self.header.finish_decoding())?);
+        }
+
+        let size = track!(self.inner.decode(&buf[offset..], eos))?;
+        self.consumed += size as u64;
+        Ok(offset + size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let declared = track_assert_some!(self.declared.take(), ErrorKind::IncompleteDecoding);
+        let consumed = self.consumed;
+        self.consumed = 0;
+
+        let item = track!(self.inner.finish_decoding())?;
+        track_assert_eq!(
+            consumed,
+            declared,
+            ErrorKind::InvalidInput,
+            "Declared length mismatched"
+        );
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.declared.is_some() {
+            self.inner.requiring_bytes()
+        } else {
+            self.header
+                .requiring_bytes()
+                .add_for_decoding(self.inner.requiring_bytes())
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.declared.is_some() && self.inner.is_idle()
+    }
+}
+
+/// Combinator for pre-encoding each item into a packet that is never split.
+///
+/// This is created by calling `EncodeExt::buffered_atomic` method.
+///
+/// The encoded bytes of an item can be retrieved either by `take_packet` method
+/// or by `encode` method. The latter writes nothing until the buffer has enough room
+/// for the whole packet.
+#[derive(Debug, Default)]
+pub struct BufferedAtomic<E> {
+    inner: E,
+    packet: Option<Vec<u8>>,
+}
+impl<E> BufferedAtomic<E> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    /// Takes the encoded bytes of the current item.
+    ///
+    /// If there is no pending item, it will return `None`.
+    pub fn take_packet(&mut self) -> Option<Vec<u8>> {
+        self.packet.take()
+    }
+
+    pub(crate) fn new(inner: E) -> Self {
+        BufferedAtomic {
+            inner,
+            packet: None,
+        }
+    }
+}
+impl<E: Encode> Encode for BufferedAtomic<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let size = match self.packet {
+            None => return Ok(0),
+            Some(ref packet) if packet.len() <= buf.len() => {
+                buf[..packet.len()].copy_from_slice(packet);
+                packet.len()
+            }
+            Some(ref packet) => {
+                track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos;
+                              buf.len(), packet.len());
+                return Ok(0);
+            }
+        };
+        self.packet = None;
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        let packet = track!(self.inner.encode_into_bytes(item))?;
+        self.packet = Some(packet);
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.exact_requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.packet.is_none()
+    }
+}
+impl<E: Encode> SizedEncode for BufferedAtomic<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.packet.as_ref().map_or(0, |p| p.len() as u64)
+    }
+}
+
+/// Combinator for prefixing each item with the CRC-32 checksum of its encoded bytes.
+///
+/// The checksum is a 4-byte big-endian integer that covers the bytes following it.
+///
+/// When encoding, since the checksum has to be written before the body,
+/// the whole body of an item is pre-encoded by `Checksummed<Crc32, _>`
+/// when `start_encoding` method is called, and then the trailing checksum is moved to the front.
+/// When decoding, the checksum is verified before the decoded item is returned,
+/// so an item whose body has been corrupted is never yielded.
+///
+/// This is created by calling `{DecodeExt, EncodeExt}::crc32_header` method.
+#[derive(Debug, Default)]
+pub struct Crc32Header<C> {
+    inner: C,
+    encoded: BytesEncoder<Vec<u8>>,
+    header: CopyableBytesDecoder<[u8; 4]>,
+    expected_crc: Option<[u8; 4]>,
+    crc: Crc32,
+}
+impl<C> Crc32Header<C> {
+    pub(crate) fn new(inner: C) -> Self {
+        Crc32Header {
+            inner,
+            encoded: BytesEncoder::new(),
+            header: CopyableBytesDecoder::new([0; 4]),
+            expected_crc: None,
+            crc: Crc32::new(),
+        }
+    }
+
+    /// Returns a reference to the inner encoder or decoder.
+    pub fn inner_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder or decoder.
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder or decoder.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for Crc32Header<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.expected_crc.is_none() {
+            bytecodec_try_decode!(self.header, offset, buf, eos);
+            self.expected_crc = Some(track!(self.header.finish_decoding())?);
+        }
+
+        let size = track!(self.inner.decode(&buf[offset..], eos))?;
+        self.crc.update(&buf[offset..][..size]);
+        offset += size;
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let expected = track_assert_some!(self.expected_crc.take(), ErrorKind::IncompleteDecoding);
+        let actual = mem::take(&mut self.crc).finalize();
+        let item = track!(self.inner.finish_decoding())?;
+        track_assert_eq!(
+            actual[..],
+            expected[..],
+            ErrorKind::InvalidInput,
+            "Checksum mismatched"
+        );
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.expected_crc.is_none() {
+            self.header
+                .requiring_bytes()
+                .add_for_decoding(self.inner.requiring_bytes())
+        } else {
+            self.inner.requiring_bytes()
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.expected_crc.is_some() && self.inner.is_idle()
+    }
+}
+impl<E: Encode> Encode for Crc32Header<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.encoded.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        let mut encoder = Checksummed::<Crc32, _>::new(&mut self.inner);
+        let mut encoded = track!(encoder.encode_into_bytes(item))?;
+        encoded.rotate_right(self.crc.size());
+        track!(self.encoded.start_encoding(encoded))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.encoded.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.encoded.is_idle()
+    }
+}
+impl<E: Encode> SizedEncode for Crc32Header<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.encoded.exact_requiring_bytes()
+    }
+}
+
+/// Combinator for verifying the check byte following each item.
+///
+/// The check byte is compared with the value computed by the specified function
+/// over the raw bytes of the item. If they differ, `finish_decoding` returns an
+/// `ErrorKind::InvalidInput` error.
+///
+/// This is created by calling `DecodeExt::checked_by` method.
+#[derive(Debug)]
+pub struct CheckedBy<D, F> {
+    inner: D,
+    check: F,
+    bytes: Vec<u8>,
+    check_byte: CopyableBytesDecoder<[u8; 1]>,
+}
+impl<D, F> CheckedBy<D, F> {
+    pub(crate) fn new(inner: D, check: F) -> Self {
+        CheckedBy {
+            inner,
+            check,
+            bytes: Vec::new(),
+            check_byte: CopyableBytesDecoder::new([0; 1]),
+        }
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D, F> Decode for CheckedBy<D, F>
+where
+    D: Decode,
+    F: Fn(&[u8]) -> u8,
+{
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if !self.inner.is_idle() {
+            offset = track!(self.inner.decode(buf, eos))?;
+            self.bytes.extend_from_slice(&buf[..offset]);
+            if !self.inner.is_idle() {
+                return Ok(offset);
+            }
+        }
+        bytecodec_try_decode!(self.check_byte, offset, buf, eos);
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let [actual] = track!(self.check_byte.finish_decoding())?;
+        let bytes = mem::take(&mut self.bytes);
+        let item = track!(self.inner.finish_decoding())?;
+        let expected = (self.check)(&bytes);
+        track_assert_eq!(
+            actual,
+            expected,
+            ErrorKind::InvalidInput,
+            "Checksum mismatched"
+        );
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.inner.is_idle() {
+            self.check_byte.requiring_bytes()
+        } else {
+            self.inner
+                .requiring_bytes()
+                .add_for_decoding(self.check_byte.requiring_bytes())
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle() && self.check_byte.is_idle()
+    }
+}
+
+/// Combinator for appending a checksum of the encoded bytes to each item.
+///
+/// The checksum algorithm is specified by `C` (see the `checksum` module).
+///
+/// This is created by calling `EncodeExt::checksummed` method.
+#[derive(Debug, Default)]
+pub struct Checksummed<C, E> {
+    inner: E,
+    checksum: C,
+    trailer: BytesEncoder<Vec<u8>>,
+    trailer_pending: bool,
+}
+impl<C: Checksum + Default, E> Checksummed<C, E> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: E) -> Self {
+        Checksummed {
+            inner,
+            checksum: C::default(),
+            trailer: BytesEncoder::new(),
+            trailer_pending: false,
+        }
+    }
+}
+impl<C: Checksum + Default, E: Encode> Encode for Checksummed<C, E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.trailer_pending {
+            offset = track!(self.inner.encode(buf, eos))?;
+            self.checksum.update(&buf[..offset]);
+            if !self.inner.is_idle() {
+                return Ok(offset);
+            }
+
+            let checksum = mem::take(&mut self.checksum).finalize();
+            track!(self.trailer.start_encoding(checksum))?;
+            self.trailer_pending = false;
+        }
+        bytecodec_try_encode!(self.trailer, offset, buf, eos);
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        track!(self.inner.start_encoding(item))?;
+        self.trailer_pending = true;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.trailer_pending {
+            self.inner
+                .requiring_bytes()
+                .add_for_encoding(ByteCount::Finite(self.checksum.size() as u64))
+        } else {
+            self.trailer.requiring_bytes()
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.trailer_pending && self.trailer.is_idle()
+    }
+}
+impl<C: Checksum + Default, E: SizedEncode> SizedEncode for Checksummed<C, E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        if self.trailer_pending {
+            self.inner.exact_requiring_bytes() + self.checksum.size() as u64
+        } else {
+            self.trailer.exact_requiring_bytes()
+        }
+    }
+}
+
+/// Combinator for verifying the checksum following each item.
+///
+/// The checksum algorithm is specified by `C` (see the `checksum` module).
+/// If the checksum differs from the one computed over the raw bytes of the item,
+/// `finish_decoding` returns an `ErrorKind::InvalidInput` error.
+///
+/// This is created by calling `DecodeExt::checksum_verified` method.
+#[derive(Debug, Default)]
+pub struct ChecksumVerified<C, D> {
+    inner: D,
+    checksum: C,
+    trailer: Vec<u8>,
+}
+impl<C: Checksum + Default, D> ChecksumVerified<C, D> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D) -> Self {
+        ChecksumVerified {
+            inner,
+            checksum: C::default(),
+            trailer: Vec::new(),
+        }
+    }
+
+    fn trailer_remaining(&self) -> usize {
+        self.checksum.size() - self.trailer.len()
+    }
+}
+impl<C: Checksum + Default, D: Decode> Decode for ChecksumVerified<C, D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if !self.inner.is_idle() {
+            offset = track!(self.inner.decode(buf, eos))?;
+            self.checksum.update(&buf[..offset]);
+            if !self.inner.is_idle() {
+                return Ok(offset);
+            }
+        }
+
+        let size = cmp::min(buf.len() - offset, self.trailer_remaining());
+        self.trailer.extend_from_slice(&buf[offset..][..size]);
+        offset += size;
+        if self.trailer_remaining() != 0 {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert_eq!(self.trailer_remaining(), 0, ErrorKind::IncompleteDecoding);
+        let expected = mem::take(&mut self.trailer);
+        let actual = mem::take(&mut self.checksum).finalize();
+        let item = track!(self.inner.finish_decoding())?;
+        track_assert_eq!(
+            actual,
+            expected,
+            ErrorKind::InvalidInput,
+            "Checksum mismatched"
+        );
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        let trailer = ByteCount::Finite(self.trailer_remaining() as u64);
+        if self.inner.is_idle() {
+            trailer
+        } else {
+            self.inner.requiring_bytes().add_for_decoding(trailer)
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle() && self.trailer_remaining() == 0
+    }
+}
+
+/// Combinator for slicing an input/output byte sequence by the specified number of bytes.
+///
+/// This is created by calling `{DecodeExt, EncodeExt}::slice`.
+#[derive(Debug, Default)]
+pub struct Slice<T> {
+    inner: T,
+    consumable_bytes: u64,
+    total_consumed_bytes: u64,
+}
+impl<T> Slice<T> {
+    pub(crate) fn new(inner: T) -> Self {
+        Slice {
+            inner,
+            consumable_bytes: 0,
+            total_consumed_bytes: 0,
+        }
+    }
+
+    /// Returns the number of remaining bytes consumable in this slice.
+    ///
+    /// The inner decoder or encoder will be suspended if the consumable bytes reaches to `0`.
+    pub fn consumable_bytes(&self) -> u64 {
+        self.consumable_bytes
+    }
+
+    /// Set the number of remaining bytes consumable in this slice.
+    ///
+    /// This can be called at any time, even in the middle of an item.
+    /// The new value replaces (rather than adds to) the current number of consumable bytes.
+    pub fn set_consumable_bytes(&mut self, n: u64) {
+        self.consumable_bytes = n;
+    }
+
+    /// Returns the number of bytes consumed for the current item.
+    ///
+    /// Unlike `consumable_bytes`, this value is not reset by `set_consumable_bytes` method.
+    /// It is reset when `finish_decoding` or `start_encoding` method is called.
+    pub fn total_consumed_bytes(&self) -> u64 {
+        self.total_consumed_bytes
+    }
+
+    /// Returns `true` if the encoder or decoder cannot consume any more bytes, otherwise `false`.
+    ///
+    /// To resume its works, it is needed to reset the value of consumable bytes
+    /// by calling `set_consumable_bytes` method.
+    pub fn is_suspended(&self) -> bool {
+        self.consumable_bytes == 0
+    }
+
+    /// Returns a reference to the inner encoder or decoder.
+    pub fn inner_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder or decoder.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder or decoder.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for Slice<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let limit = cmp::min(buf.len() as u64, self.consumable_bytes) as usize;
+        let eos = eos.back((buf.len() - limit) as u64);
+        let size = track!(self.inner.decode(&buf[..limit], eos))?;
+        track_assert!(size <= limit, ErrorKind::InconsistentState; size, limit);
+        self.consumable_bytes -= size as u64;
+        self.total_consumed_bytes += size as u64;
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track!(self.inner.finish_decoding())?;
+        self.total_consumed_bytes = 0;
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        match self.inner.decode_status() {
+            DecodeStatus::NeedMoreBytes if self.is_suspended() => DecodeStatus::Suspended,
+            status => status,
+        }
+    }
+}
+impl<E: Encode> Encode for Slice<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let limit = cmp::min(buf.len() as u64, self.consumable_bytes) as usize;
+        let eos = eos.back((buf.len() - limit) as u64);
+        let size = track!(self.inner.encode(&mut buf[..limit], eos))?;
+        track_assert!(size <= limit, ErrorKind::InconsistentState; size, limit);
+        self.consumable_bytes -= size as u64;
+        self.total_consumed_bytes += size as u64;
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.inner.start_encoding(item))?;
+        self.total_consumed_bytes = 0;
+        Ok(())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+}
+impl<E: SizedEncode> SizedEncode for Slice<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Combinator for representing encoders that accepts only one additional item.
+///
+/// This is created by calling `EncodeExt::last`.
+#[derive(Debug, Default)]
+pub struct Last<E: Encode> {
+    inner: E,
+    item: Option<E::Item>,
+}
+impl<E: Encode> Last<E> {
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: E, item: E::Item) -> Self {
+        Last {
+            inner,
+            item: Some(item),
+        }
+    }
+}
+impl<E: Encode> Encode for Last<E> {
+    type Item = Never;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        if self.inner.is_idle() {
+            if let Some(item) = self.item.take() {
+                track!(self.inner.start_encoding(item))?;
+            }
+        }
+        track!(self.inner.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, _item: Self::Item) -> Result<()> {
+        unreachable!()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.item.is_none() && self.inner.is_idle()
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.item.is_some() {
+            ByteCount::Unknown
+        } else {
+            self.inner.requiring_bytes()
+        }
+    }
+}
+impl<E: SizedEncode> SizedEncode for Last<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Combinator for writing a sentinel byte once at the end of a stream.
+///
+/// This is created by calling `EncodeExt::terminated_by` method.
+///
+/// The terminator is armed by calling `finish` method.
+/// Once armed, the sentinel is written after the inner encoder completes the current item,
+/// and then no more items can be encoded.
+#[derive(Debug, Default)]
+pub struct Terminated<E> {
+    inner: E,
+    sentinel: u8,
+    armed: bool,
+    terminated: bool,
+}
+impl<E> Terminated<E> {
+    /// Arms the terminator.
+    ///
+    /// The sentinel byte will be written after the current item (if any) is encoded.
+    pub fn finish(&mut self) {
+        self.armed = true;
+    }
+
+    /// Returns `true` if the sentinel byte has been written, otherwise `false`.
+    pub fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: E, sentinel: u8) -> Self {
+        Terminated {
+            inner,
+            sentinel,
+            armed: false,
+            terminated: false,
+        }
+    }
+
+    fn pending_bytes(&self) -> u64 {
+        if self.armed && !self.terminated {
+            1
+        } else {
+            0
+        }
+    }
+}
+impl<E: Encode> Encode for Terminated<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        bytecodec_try_encode!(self.inner, offset, buf, eos);
+        if self.pending_bytes() == 1 {
+            if offset < buf.len() {
+                buf[offset] = self.sentinel;
+                offset += 1;
+                self.terminated = true;
+            } else {
+                track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+            }
+        }
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(!self.armed, ErrorKind::InvalidInput, "Already finished");
+        track!(self.inner.start_encoding(item))
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle() && self.pending_bytes() == 0
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner
+            .requiring_bytes()
+            .add_for_encoding(ByteCount::Finite(self.pending_bytes()))
+    }
+}
+impl<E: SizedEncode> SizedEncode for Terminated<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes() + self.pending_bytes()
+    }
+}
+
+/// Combinator for limiting the number of bytes written by a single `encode` call.
+///
+/// This is created by calling `EncodeExt::rate_limit` method.
+///
+/// Unlike `Slice`, the limit is automatically reset at every `encode` call.
+#[derive(Debug)]
+pub struct RateLimited<E> {
+    inner: E,
+    max_bytes_per_encode: usize,
+}
+impl<E> RateLimited<E> {
+    /// Returns the maximum number of bytes written by a single `encode` call.
+    pub fn max_bytes_per_encode(&self) -> usize {
+        self.max_bytes_per_encode
+    }
+
+    /// Sets the maximum number of bytes written by a single `encode` call.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is `0`, this method will panic.
+    pub fn set_max_bytes_per_encode(&mut self, n: usize) {
+        assert_ne!(n, 0);
+        self.max_bytes_per_encode = n;
+    }
+
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: E, max_bytes_per_encode: usize) -> Self {
+        assert_ne!(max_bytes_per_encode, 0);
+        RateLimited {
+            inner,
+            max_bytes_per_encode,
+        }
+    }
+}
+impl<E: Encode> Encode for RateLimited<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let limit = cmp::min(buf.len(), self.max_bytes_per_encode);
+        let eos = eos.back((buf.len() - limit) as u64);
+        let size = track!(self.inner.encode(&mut buf[..limit], eos))?;
+        track_assert!(size <= limit, ErrorKind::InconsistentState; size, limit);
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.inner.start_encoding(item))
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+}
+impl<E: SizedEncode> SizedEncode for RateLimited<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Combinator that enables to inspect upcoming bytes before the inner decoder consumes them.
+///
+/// This is created by calling `DecodeExt::peek_bytes` method.
+///
+/// At the beginning of each item, `PeekBytes` consumes up to the specified number of bytes
+/// from the input and stores them in its own buffer.
+/// Once the lookahead bytes are collected (or the input reaches EOS),
+/// the given function is called with the bytes and the inner decoder
+/// (so that, for instance, it can reconfigure the inner decoder).
+/// Then the buffered bytes are replayed into the inner decoder, followed by the rest of the input.
+///
+/// If the item is shorter than the lookahead bytes,
+/// the remaining buffered bytes are carried over to the lookahead bytes of the next item.
+/// Note that they are not returned to the caller because they have already been consumed.
+#[derive(Debug)]
+pub struct PeekBytes<D, F> {
+    inner: D,
+    peek_size: usize,
+    peeked: Vec<u8>,
+    peeking: bool,
+    replayed: usize,
+    on_peek: F,
+}
+impl<D, F> PeekBytes<D, F> {
+    /// Returns the lookahead bytes of the current item.
+    ///
+    /// The length of the result is shorter than the specified size
+    /// only if the input reached EOS before collecting the lookahead bytes.
+    pub fn peeked(&self) -> &[u8] {
+        &self.peeked[..cmp::min(self.peeked.len(), self.peek_size)]
+    }
+
+    /// Returns `true` if the lookahead bytes have been collected, otherwise `false`.
+    pub fn is_peeked(&self) -> bool {
+        !self.peeking
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D, peek_size: usize, on_peek: F) -> Self
+    where
+        F: FnMut(&[u8], &mut D) -> Result<()>,
+    {
+        PeekBytes {
+            inner,
+            peek_size,
+            peeked: Vec::with_capacity(peek_size),
+            peeking: true,
+            replayed: 0,
+            on_peek,
+        }
+    }
+}
+impl<D, F> Decode for PeekBytes<D, F>
+where
+    D: Decode,
+    F: FnMut(&[u8], &mut D) -> Result<()>,
+{
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.peeking {
+            offset = cmp::min(buf.len(), self.peek_size.saturating_sub(self.peeked.len()));
+            self.peeked.extend_from_slice(&buf[..offset]);
+            if self.peeked.len() < self.peek_size && !(offset == buf.len() && eos.is_reached()) {
+                return Ok(offset);
+            }
+            self.peeking = false;
+            let peeked = &self.peeked[..cmp::min(self.peeked.len(), self.peek_size)];
+            track!((self.on_peek)(peeked, &mut self.inner))?;
+        }
+
+        if self.replayed < self.peeked.len() {
+            let replay_eos = eos.back((buf.len() - offset) as u64);
+            let size = track!(self.inner.decode(&self.peeked[self.replayed..], replay_eos))?;
+            self.replayed += size;
+            if self.replayed < self.peeked.len() {
+                return Ok(offset);
+            }
+        }
+        offset += track!(self.inner.decode(&buf[offset..], eos))?;
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(!self.peeking, ErrorKind::IncompleteDecoding);
+        let item = track!(self.inner.finish_decoding())?;
+        self.peeked.drain(..self.replayed);
+        self.peeking = true;
+        self.replayed = 0;
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.peeking {
+            ByteCount::Finite(self.peek_size.saturating_sub(self.peeked.len()) as u64)
+        } else {
+            match self.inner.requiring_bytes() {
+                ByteCount::Finite(n) => {
+                    let buffered = (self.peeked.len() - self.replayed) as u64;
+                    ByteCount::Finite(n.saturating_sub(buffered))
+                }
+                n => n,
+            }
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.peeking && self.inner.is_idle()
+    }
+}
+
+/// Combinator that enables to peek decoded items before calling `finish_decoding` method.
+///
+/// This is created by calling `DecodeExt::peekable` method.
+pub struct Peekable<D: Decode> {
+    inner: D,
+    item: Option<D::Item>,
+}
+impl<D: Decode> Peekable<D> {
+    /// Returns a reference to the last item decoded (but not `finish_decoding` called yet) by the decoder.
+    pub fn peek(&self) -> Option<&D::Item> {
+        self.item.as_ref()
+    }
+
+    /// Returns a mutable reference to the last item decoded (but not `finish_decoding` called yet) by the decoder.
+    pub fn peek_mut(&mut self) -> Option<&mut D::Item> {
+        self.item.as_mut()
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D) -> Self {
+        Peekable { inner, item: None }
+    }
+}
+impl<D: Decode + fmt::Debug> fmt::Debug for Peekable<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Peekable {{ inner: {:?}, item.is_some(): {:?} }}",
+            self.inner,
+            self.item.is_some()
+        )
+    }
+}
+impl<D: Decode + Default> Default for Peekable<D> {
+    fn default() -> Self {
+        Peekable {
+            inner: D::default(),
+            item: None,
+        }
+    }
+}
+impl<D: Decode> Decode for Peekable<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.item.is_none() {
+            let size = track!(self.inner.decode(buf, eos))?;
+            if self.inner.is_idle() {
+                self.item = Some(track!(self.inner.finish_decoding())?);
+            }
+            Ok(size)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track_assert_some!(self.item.take(), ErrorKind::IncompleteDecoding);
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.item.is_some() {
+            ByteCount::Finite(0)
+        } else {
+            self.inner.requiring_bytes()
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.item.is_some()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        if self.item.is_some() {
+            DecodeStatus::Idle
+        } else {
+            self.inner.decode_status()
+        }
+    }
+}
+
+/// Combinator for ignoring EOS if there is no item being decoded.
+///
+/// This is created by calling `DecodeExt::maybe_eos`.
+#[derive(Debug, Default)]
+pub struct MaybeEos<D> {
+    inner: D,
+    started: bool,
+}
+impl<D> MaybeEos<D> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D) -> Self {
+        MaybeEos {
+            inner,
+            started: false,
+        }
+    }
+}
+impl<D: Decode> Decode for MaybeEos<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], mut eos: Eos) -> Result<usize> {
+        if !self.started && buf.is_empty() && eos.is_reached() {
+            eos = Eos::new(false);
+        }
+
+        let size = track!(self.inner.decode(buf, eos))?;
+        if size != 0 {
+            self.started = true;
+        }
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        self.started = false;
+        track!(self.inner.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+
+/// Combinator for converting the tags of a `TaggedDecode` decoder.
+///
+/// This is created by calling `DecodeExt::map_tag` method.
+#[derive(Debug)]
+pub struct MapTag<D, T, E, F> {
+    inner: D,
+    map_tag: F,
+    _phantom: PhantomData<(T, E)>,
+}
+impl<D, T, E, F> MapTag<D, T, E, F> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D, map_tag: F) -> Self {
+        MapTag {
+            inner,
+            map_tag,
+            _phantom: PhantomData,
+        }
+    }
+}
+impl<D, T, E, F> Decode for MapTag<D, T, E, F>
+where
+    D: TaggedDecode,
+    F: Fn(T) -> std::result::Result<D::Tag, E>,
+    E: Into<BoxError>,
+{
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track!(self.inner.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+
+    fn decode_status(&self) -> DecodeStatus {
+        self.inner.decode_status()
+    }
+}
+impl<D, T, E, F> TaggedDecode for MapTag<D, T, E, F>
+where
+    D: TaggedDecode,
+    F: Fn(T) -> std::result::Result<D::Tag, E>,
+    E: Into<BoxError>,
+{
+    type Tag = T;
+
+    fn start_decoding(&mut self, tag: Self::Tag) -> Result<()> {
+        let tag = track!((self.map_tag)(tag).map_err(|e| ErrorKind::InvalidInput.cause(e)))?;
+        track!(self.inner.start_decoding(tag))
+    }
+}
+
+/// Item decoded by `UnknownAsBytes` decoder.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MaybeKnown<T> {
+    /// Item tagged by a known tag.
+    Known(T),
+
+    /// Raw bytes of an item tagged by an unknown tag.
+    Unknown(Vec<u8>),
+}
+
+/// Combinator for capturing the items tagged by unknown tags as raw bytes.
+///
+/// If `TryTaggedDecode::try_start_decoding` of the inner decoder returns `Ok(false)`,
+/// the bytes of the item are decoded by a `RemainingBytesDecoder` instead of the inner decoder.
+/// Thus, the end of an unknown item has to be delimited by EOS (e.g., by using `length` combinator).
+///
+/// This is created by calling `DecodeExt::unknown_as_bytes` method.
+#[derive(Debug, Default)]
+pub struct UnknownAsBytes<D> {
+    inner: D,
+    raw: RemainingBytesDecoder,
+    known: Option<bool>,
+}
+impl<D> UnknownAsBytes<D> {
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    pub(crate) fn new(inner: D) -> Self {
+        UnknownAsBytes {
+            inner,
+            raw: RemainingBytesDecoder::new(),
+            known: None,
+        }
+    }
+}
+impl<D: TryTaggedDecode> Decode for UnknownAsBytes<D> {
+    type Item = MaybeKnown<D::Item>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        match self.known {
+            None => Ok(0),
+            Some(true) => track!(self.inner.decode(buf, eos)),
+            Some(false) => track!(self.raw.decode(buf, eos)),
+        }
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let known = track_assert_some!(self.known, ErrorKind::IncompleteDecoding);
+        let item = if known {
+            MaybeKnown::Known(track!(self.inner.finish_decoding())?)
+        } else {
+            MaybeKnown::Unknown(track!(self.raw.finish_decoding())?)
+        };
+        self.known = None;
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        match self.known {
+            None => ByteCount::Unknown,
+            Some(true) => self.inner.requiring_bytes(),
+            Some(false) => self.raw.requiring_bytes(),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        match self.known {
+            None => false,
+            Some(true) => self.inner.is_idle(),
+            Some(false) => self.raw.is_idle(),
+        }
+    }
+}
+impl<D: TryTaggedDecode> TaggedDecode for UnknownAsBytes<D> {
+    type Tag = D::Tag;
+
+    fn start_decoding(&mut self, tag: Self::Tag) -> Result<()> {
+        track_assert!(self.known.is_none(), ErrorKind::IncompleteDecoding);
+        let known = track!(self.inner.try_start_decoding(tag))?;
+        self.known = Some(known);
+        Ok(())
+    }
+}
+
+/// Combinator for concatenating the byte sequences decoded by two decoders.
+///
+/// This is created by calling `DecodeExt::concat` method.
+#[derive(Debug, Default)]
+pub struct Concat<D0, D1> {
+    head: D0,
+    tail: D1,
+    head_bytes: Option<Vec<u8>>,
+}
+impl<D0, D1> Concat<D0, D1> {
+    /// Returns references to the inner decoders.
+    pub fn inner_ref(&self) -> (&D0, &D1) {
+        (&self.head, &self.tail)
+    }
+
+    /// Returns mutable references to the inner decoders.
+    pub fn inner_mut(&mut self) -> (&mut D0, &mut D1) {
+        (&mut self.head, &mut self.tail)
+    }
+
+    /// Takes ownership of this instance and returns the inner decoders.
+    pub fn into_inner(self) -> (D0, D1) {
+        (self.head, self.tail)
+    }
+
+    pub(crate) fn new(head: D0, tail: D1) -> Self {
+        Concat {
+            head,
+            tail,
+            head_bytes: None,
+        }
+    }
+}
+impl<D0, D1> Decode for Concat<D0, D1>
+where
+    D0: Decode<Item = Vec<u8>>,
+    D1: Decode<Item = Vec<u8>>,
+{
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.head_bytes.is_none() {
+            bytecodec_try_decode!(self.head, offset, buf, eos);
+            self.head_bytes = Some(track!(self.head.finish_decoding())?);
+        }
+        offset += track!(self.tail.decode(&buf[offset..], eos))?;
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.head_bytes.is_some(), ErrorKind::IncompleteDecoding);
+        let tail = track!(self.tail.finish_decoding())?;
+        let mut bytes = track_assert_some!(self.head_bytes.take(), ErrorKind::InconsistentState);
+        bytes.extend_from_slice(&tail);
+        Ok(bytes)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.head_bytes.is_some() {
+            self.tail.requiring_bytes()
+        } else {
+            self.head
+                .requiring_bytes()
+                .add_for_decoding(self.tail.requiring_bytes())
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.head_bytes.is_some() && self.tail.is_idle()
+    }
+}
+
+/// Combinator for encoding the items of two encoders alternately in fixed-size quanta.
+///
+/// This is created by calling `EncodeExt::interleave` method.
+#[derive(Debug, Default)]
+pub struct Interleave<E0, E1> {
+    first: Slice<E0>,
+    second: Slice<E1>,
+    quantum: u64,
+    second_turn: bool,
+}
+impl<E0, E1> Interleave<E0, E1> {
+    pub(crate) fn new(first: E0, second: E1, quantum: usize) -> Self {
+        assert_ne!(quantum, 0, "The quantum must be greater than zero");
+        Interleave {
+            first: Slice::new(first),
+            second: Slice::new(second),
+            quantum: quantum as u64,
+            second_turn: false,
+        }
+    }
+
+    /// Returns the number of bytes encoded by an inner encoder in a turn.
+    pub fn quantum(&self) -> usize {
+        self.quantum as usize
+    }
+
+    /// Returns references to the inner encoders.
+    pub fn inner_ref(&self) -> (&E0, &E1) {
+        (self.first.inner_ref(), self.second.inner_ref())
+    }
+
+    /// Returns mutable references to the inner encoders.
+    pub fn inner_mut(&mut self) -> (&mut E0, &mut E1) {
+        (self.first.inner_mut(), self.second.inner_mut())
+    }
+
+    /// Takes ownership of this instance and returns the inner encoders.
+    pub fn into_inner(self) -> (E0, E1) {
+        (self.first.into_inner(), self.second.into_inner())
+    }
+}
+impl<E0: Encode, E1: Encode> Interleave<E0, E1> {
+    fn encode_turn<E: Encode>(
+        slice: &mut Slice<E>,
+        quantum: u64,
+        buf: &mut [u8],
+        eos: Eos,
+    ) -> Result<(usize, bool)> {
+        if slice.is_idle() {
+            return Ok((0, true));
+        }
+        if slice.is_suspended() {
+            slice.set_consumable_bytes(quantum);
+        }
+        let size = track!(slice.encode(buf, eos))?;
+        let turn_end = slice.is_suspended() || slice.is_idle();
+        if turn_end {
+            slice.set_consumable_bytes(0);
+        }
+        Ok((size, turn_end))
+    }
+}
+impl<E0: Encode, E1: Encode> Encode for Interleave<E0, E1> {
+    type Item = (E0::Item, E1::Item);
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        while !self.is_idle() && offset < buf.len() {
+            let (size, turn_end) = if self.second_turn {
+                track!(Self::encode_turn(
+                    &mut self.second,
+                    self.quantum,
+                    &mut buf[offset..],
+                    eos
+                ))?
+            } else {
+                track!(Self::encode_turn(
+                    &mut self.first,
+                    self.quantum,
+                    &mut buf[offset..],
+                    eos
+                ))?
+            };
+            offset += size;
+            if !turn_end {
+                break;
+            }
+            self.second_turn = !self.second_turn;
+        }
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        track!(self.first.start_encoding(item.0))?;
+        track!(self.second.start_encoding(item.1))?;
+        self.second_turn = false;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.first
+            .requiring_bytes()
+            .add_for_encoding(self.second.requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.first.is_idle() && self.second.is_idle()
+    }
+}
+impl<E0: SizedEncode, E1: SizedEncode> SizedEncode for Interleave<E0, E1> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.first.exact_requiring_bytes() + self.second.exact_requiring_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        FirstMatchDecoder, LengthSuffixDecoder, LengthVerifiedDecoder, MaybeKnown, SequenceEncoder,
+    };
+    use crate::bytes::{CopyableBytesDecoder, RemainingBytesDecoder, Utf8Decoder, Utf8Encoder};
+    use crate::checksum::{Crc32, Xor8};
+    use crate::fixnum::{U16beDecoder, U16beEncoder, U8Decoder, U8Encoder};
+    use crate::io::{IoDecodeExt, IoEncodeExt, ReadBuf};
+    use crate::null::NullEncoder;
+    use crate::tagged::StringTaggedDecoder;
+    use crate::tuple::TupleDecoder;
+    use crate::{
+        ByteCount, Decode, DecodeExt, DecodeStatus, Encode, EncodeExt, Eos, ErrorKind, SizedEncode,
+        TaggedDecode,
+    };
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+    use trackable::error::ErrorKindExt;
+
+    #[test]
+    fn collect_works() {
+        let mut decoder = U8Decoder::new().collect::<Vec<_>>();
+        let item = track_try_unwrap!(decoder.decode_exact(b"foo".as_ref()));
+        assert_eq!(item, vec![b'f', b'o', b'o']);
+    }
+
+    #[test]
+    fn collect_map_works() {
+        let mut decoder = U8Decoder::new()
+            .chain(U8Decoder::new())
+            .collect_map::<_, _, HashMap<_, _>>();
+        let item = track_try_unwrap!(decoder.decode_exact(b"a1b2a3".as_ref()));
+        assert_eq!(item.len(), 2);
+        assert_eq!(item[&b'a'], b'3');
+        assert_eq!(item[&b'b'], b'2');
+    }
+
+    #[test]
+    fn map_with_state_works() {
+        let mut decoder = U8Decoder::new()
+            .map_with_state(0, |sum, delta| {
+                *sum += delta;
+                *sum
+            })
+            .collect::<Vec<_>>();
+        let item = track_try_unwrap!(decoder.decode_exact(&[1, 1, 1][..]));
+        assert_eq!(item, [1, 2, 3]);
+        assert_eq!(*decoder.inner_ref().state_ref(), 3);
+    }
+
+    #[test]
+    fn collectn_works() {
+        let mut decoder = U8Decoder::new().collectn::<Vec<_>>(2);
+        let item = track_try_unwrap!(decoder.decode_exact(b"foo".as_ref()));
+        assert_eq!(item, vec![b'f', b'o']);
+
+        let mut decoder = U8Decoder::new().collectn::<Vec<_>>(4);
+        assert_eq!(
+            decoder
+                .decode_exact(b"foo".as_ref())
+                .err()
+                .map(|e| *e.kind()),
+            Some(ErrorKind::UnexpectedEos)
+        );
+    }
+
+    #[test]
+    fn decoder_length_works() {
+        // length=3
+        let mut decoder = Utf8Decoder::new().length(3);
+        let mut input = b"foobarba".as_ref();
+
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, "foo");
+
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, "bar");
+
+        let error = decoder.decode_exact(&mut input).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+
+        // length=0
+        let mut decoder = Utf8Decoder::new().length(0);
+        assert!(!decoder.is_idle());
+        assert!(decoder.finish_decoding().is_err());
+
+        let input = b"foobarba";
+        assert_eq!(decoder.decode(input, Eos::new(false)).ok(), Some(0));
+        assert!(decoder.is_idle());
+        assert!(decoder.finish_decoding().is_ok());
+    }
+
+    #[test]
+    fn length_and_slice_reject_inconsistent_consumption() {
+        // A broken decoder which reports more consumed bytes than given
+        struct Greedy;
+        impl Decode for Greedy {
+            type Item = ();
+
+            fn decode(&mut self, buf: &[u8], _eos: Eos) -> crate::Result<usize> {
+                Ok(buf.len() + 1)
+            }
+
+            fn finish_decoding(&mut self) -> crate::Result<Self::Item> {
+                Ok(())
+            }
+
+            fn requiring_bytes(&self) -> ByteCount {
+                ByteCount::Unknown
+            }
+        }
+
+        let mut decoder = Greedy.length(3);
+        let error = decoder.decode(b"foo", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InconsistentState);
+
+        let mut decoder = Greedy.slice();
+        decoder.set_consumable_bytes(2);
+        let error = decoder.decode(b"foo", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InconsistentState);
+    }
+
+    #[test]
+    fn length_set_expected_bytes_mid_decode_fails() {
+        let mut decoder = Utf8Decoder::new().length(3);
+        track_try_unwrap!(decoder.decode(b"fo", Eos::new(false)));
+
+        let error = decoder.set_expected_bytes(1).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::IncompleteDecoding);
+        assert_eq!(decoder.remaining_bytes(), 1);
+
+        track_try_unwrap!(decoder.decode(b"o", Eos::new(false)));
+        assert_eq!(decoder.finish_decoding().ok(), Some("foo".to_owned()));
+    }
+
+    #[test]
+    fn assert_eq_works() {
+        let mut decoder = TupleDecoder::new((U8Decoder::new().assert_eq(1), U16beDecoder::new()));
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&[1, 0, 2][..]));
+        assert_eq!(item, (1, 2));
+
+        let error = decoder.decode_from_bytes(&[2, 0, 2][..]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn checked_by_works() {
+        let xor = |bytes: &[u8]| bytes.iter().fold(0, |a, b| a ^ b);
+        let mut decoder = Utf8Decoder::new().length(3).checked_by(xor);
+
+        let input = [b'f', b'o', b'o', b'f' ^ b'o' ^ b'o'];
+        let mut offset = 0;
+        for chunk in input.chunks(3) {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+        }
+        assert_eq!(offset, 4);
+        assert!(decoder.is_idle());
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "foo");
+
+        let error = decoder.decode_from_bytes(b"foo\x00").err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        let mut decoder = U8Decoder::new()
+            .try_map(|b| {
+                if b != 0xff {
+                    Ok(b)
+                } else {
+                    Err(ErrorKind::InvalidInput)
+                }
+            })
+            .checked_by(xor);
+        let error = decoder.decode_from_bytes(&[0xff, 0xff]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&[0x05, 0x05])),
+            5
+        );
+    }
+
+    #[test]
+    fn with_offset_works() {
+        let mut decoder = U8Decoder::new().with_offset();
+        let items: Vec<_> = track_try_unwrap!(decoder.decode_all_from_bytes(b"abc"));
+        assert_eq!(items, [(0, b'a'), (1, b'b'), (2, b'c')]);
+
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"d"));
+        assert_eq!(item, (3, b'd'));
+        assert_eq!(decoder.offset(), 4);
+    }
+
+    #[test]
+    fn total_max_bytes_works() {
+        let mut decoder = U16beDecoder::new().total_max_bytes(5);
+        let mut items = Vec::new();
+        let mut offset = 0;
+        let input = [0, 1, 0, 2, 0, 3];
+        let error = loop {
+            match decoder.decode(&input[offset..], Eos::new(false)) {
+                Err(e) => break e,
+                Ok(size) => offset += size,
+            }
+            if decoder.is_idle() {
+                items.push(track_try_unwrap!(decoder.finish_decoding()));
+            }
+        };
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(items, [1, 2]);
+        assert_eq!(decoder.consumed_bytes(), 5);
+    }
+
+    #[test]
+    fn take_works() {
+        let mut decoder = RemainingBytesDecoder::new().take(3);
+        let input = b"foobar";
+        let size = track_try_unwrap!(decoder.decode(input, Eos::new(false)));
+        assert_eq!(size, 3);
+        assert!(decoder.is_idle());
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), b"foo");
+
+        let mut decoder = RemainingBytesDecoder::new().take(10);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(input)),
+            b"foobar"
+        );
+
+        let mut decoder = RemainingBytesDecoder::new().take(u64::MAX / 2);
+        let size = track_try_unwrap!(decoder.decode(b"abc", Eos::new(false)));
+        assert_eq!(size, 3);
+        assert!(!decoder.is_idle());
+        assert!(decoder.finish_decoding().is_err());
+        assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(u64::MAX / 2));
+    }
+
+    #[test]
+    fn debug_shows_progress() {
+        let mut decoder = Utf8Decoder::new().length(5);
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        let debug = format!("{:?}", decoder);
+        assert!(debug.contains("remaining_bytes: 2"), "{}", debug);
+
+        let mut decoder = CopyableBytesDecoder::new([0; 4]).max_bytes(10);
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        let debug = format!("{:?}", decoder);
+        assert!(debug.contains("offset: 3"), "{}", debug);
+        assert!(debug.contains("consumed_bytes: 3"), "{}", debug);
+    }
+
+    #[test]
+    fn sequence_encoder_works() {
+        let mut encoder = SequenceEncoder::<U8Encoder>::new();
+        for i in 1..=3 {
+            track_try_unwrap!(encoder.start_encoding(i));
+        }
+        assert_eq!(encoder.len(), 3);
+        assert_eq!(encoder.requiring_bytes(), ByteCount::Finite(3));
+
+        let mut buf = [0; 2];
+        assert_eq!(
+            track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false))),
+            2
+        );
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(encoder.exact_requiring_bytes(), 1);
+
+        track_try_unwrap!(encoder.start_encoding(4));
+        let mut output = Vec::new();
+        track_try_unwrap!(encoder.encode_all(&mut output));
+        assert_eq!(output, [3, 4]);
+        assert!(encoder.is_idle());
+    }
+
+    #[test]
+    fn collect_until_works() {
+        let mut decoder = U8Decoder::new().collect_until::<Vec<_>, _>(|&b| b == 0);
+        let mut input = &[3, 2, 1, 0, 9][..];
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, [3, 2, 1]);
+        assert_eq!(input, [9]);
+
+        let error = decoder.decode_from_bytes(&[1, 2][..]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+
+    #[test]
+    fn context_works() {
+        let mut decoder = U8Decoder::new()
+            .context("reading tag")
+            .chain(U16beDecoder::new().context("reading length"));
+        let error = decoder.decode_from_bytes(&[1, 2][..]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+
+        let history = error.to_string();
+        assert!(history.contains("-- reading length"));
+        assert!(!history.contains("-- reading tag"));
+    }
+
+    #[test]
+    fn optional_if_works() {
+        let mut decoder = U16beDecoder::new().optional_if(U8Decoder::new().map(|b| b == 1));
+
+        let mut input = &[1, 0x12, 0x34, 0, 1, 0xff][..];
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, Some(0x1234));
+
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, None);
+
+        let error = decoder.decode_exact(&mut input).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+
+    #[test]
+    fn and_then_sized_works() {
+        let mut decoder = U16beDecoder::new()
+            .and_then_sized(|len| (RemainingBytesDecoder::new(), u64::from(len)));
+        assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(2));
+
+        let size = track_try_unwrap!(decoder.decode(&[0, 5, 1], Eos::new(false)));
+        assert_eq!(size, 3);
+        assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(4));
+
+        let size = track_try_unwrap!(decoder.decode(&[2, 3, 4, 5, 6], Eos::new(false)));
+        assert_eq!(size, 4);
+        assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(0));
+        assert_eq!(
+            track_try_unwrap!(decoder.finish_decoding()),
+            [1, 2, 3, 4, 5]
+        );
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn collect_into_smallvec_works() {
+        use smallvec::SmallVec;
+
+        let mut decoder = U8Decoder::new().collect::<SmallVec<[u8; 4]>>();
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"foo"));
+        assert_eq!(&item[..], b"foo");
+        assert!(!item.spilled());
+
+        let mut decoder = U8Decoder::new().collectn::<SmallVec<[u8; 4]>>(3);
+        let item = track_try_unwrap!(decoder.decode_exact(&b"barbaz"[..]));
+        assert_eq!(&item[..], b"bar");
+        assert!(!item.spilled());
+    }
+
+    #[test]
+    fn first_match_decoder_works() {
+        let mut decoder = FirstMatchDecoder::new(8);
+        decoder.add_alternative(CopyableBytesDecoder::new([0; 2]).try_map(|b| {
+            if b == *b"ab" {
+                Ok("ab")
+            } else {
+                Err(ErrorKind::InvalidInput)
+            }
+        }));
+        decoder.add_alternative(U8Decoder::new().try_map(|b| {
+            if b == b'x' {
+                Ok("x")
+            } else {
+                Err(ErrorKind::InvalidInput)
+            }
+        }));
+        decoder.add_alternative(CopyableBytesDecoder::new([0; 3]).map(|_| "any"));
+
+        let input = b"yzwab";
+        let mut offset = 0;
+        for chunk in input[..3].chunks(1) {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
         }
-    }
+        assert_eq!(offset, 3);
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "any");
 
-    fn finish_decoding(&mut self) -> Result<Self::Item> {
-        let item = track_assert_some!(self.item.take(), ErrorKind::IncompleteDecoding);
-        Ok(item)
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&input[3..]));
+        assert_eq!(item, "ab");
+
+        let mut decoder = FirstMatchDecoder::new(2);
+        decoder.add_alternative(CopyableBytesDecoder::new([0; 3]));
+        let error = decoder.decode(b"foo", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
     }
 
-    fn requiring_bytes(&self) -> ByteCount {
-        if self.item.is_some() {
-            ByteCount::Finite(0)
-        } else {
-            self.inner.requiring_bytes()
+    #[test]
+    fn length_suffix_works() {
+        let mut encoder = Utf8Encoder::new().with_length_suffix(
+            U8Encoder::new()
+                .try_map_from(|n| u8::try_from(n).map_err(|e| ErrorKind::InvalidInput.cause(e))),
+        );
+        let error = encoder.encode_into_bytes("a".repeat(256)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        let mut encoder = Utf8Encoder::new().with_length_suffix(
+            U16beEncoder::new()
+                .try_map_from(|n| u16::try_from(n).map_err(|e| ErrorKind::InvalidInput.cause(e))),
+        );
+        track_try_unwrap!(encoder.start_encoding("foobar".to_owned()));
+        assert_eq!(encoder.exact_requiring_bytes(), 8);
+        let mut bytes = Vec::new();
+        track_try_unwrap!(encoder.encode_all(&mut bytes));
+        assert_eq!(bytes, b"foobar\x00\x06");
+
+        let mut decoder =
+            LengthSuffixDecoder::new(Utf8Decoder::new(), U16beDecoder::new().map(u64::from));
+        for chunk in bytes.chunks(3) {
+            track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
         }
-    }
+        assert!(!decoder.is_idle());
+        track_try_unwrap!(decoder.decode(&[], Eos::new(true)));
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "foobar");
 
-    fn is_idle(&self) -> bool {
-        self.item.is_some()
-    }
-}
+        let error = decoder.decode_from_bytes(b"foo\x00\x02").err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
 
-/// Combinator for ignoring EOS if there is no item being decoded.
-///
-/// This is created by calling `DecodeExt::maybe_eos`.
-#[derive(Debug, Default)]
-pub struct MaybeEos<D> {
-    inner: D,
-    started: bool,
-}
-impl<D> MaybeEos<D> {
-    /// Returns a reference to the inner decoder.
-    pub fn inner_ref(&self) -> &D {
-        &self.inner
+        let mut decoder = LengthSuffixDecoder::with_max_buffer_bytes(
+            Utf8Decoder::new(),
+            U16beDecoder::new().map(u64::from),
+            5,
+        );
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        let error = decoder.decode(b"\x00\x03!", Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"foo\x00\x03"));
+        assert_eq!(item, "foo");
     }
 
-    /// Returns a mutable reference to the inner decoder.
-    pub fn inner_mut(&mut self) -> &mut D {
-        &mut self.inner
-    }
+    #[test]
+    fn recover_works() {
+        let mut decoder = U8Decoder::new()
+            .try_map(|b| {
+                if b != 0xff {
+                    Ok(u16::from(b))
+                } else {
+                    Err(ErrorKind::InvalidInput)
+                }
+            })
+            .recover(|e| {
+                assert_eq!(*e.kind(), ErrorKind::InvalidInput);
+                u16::MAX
+            })
+            .collect::<Vec<_>>();
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&[1, 0xff, 2]));
+        assert_eq!(item, [1, u16::MAX, 2]);
+
+        let mut decoder = U16beDecoder::new().recover(|_| 0);
+        let error = decoder.decode_from_bytes(&[1]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
 
-    /// Takes ownership of this instance and returns the inner decoder.
-    pub fn into_inner(self) -> D {
-        self.inner
+        let mut decoder = U16beDecoder::new().recover(|_| 0);
+        track_try_unwrap!(decoder.decode(&[1], Eos::new(false)));
+        let error = decoder.finish_decoding().err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::IncompleteDecoding);
     }
 
-    pub(crate) fn new(inner: D) -> Self {
-        MaybeEos {
-            inner,
-            started: false,
+    #[test]
+    fn repeat_until_eos_works() {
+        let mut decoder = U16beDecoder::new().repeat_until_eos();
+        let mut buf = ReadBuf::new(vec![0; 3]);
+
+        let mut input = &[0, 1, 0, 2, 0, 3][..];
+        let mut items = Vec::new();
+        while !decoder.is_eos() {
+            track_try_unwrap!(buf.fill(&mut input));
+            if let Some(item) = track_try_unwrap!(decoder.next_item(&mut buf)) {
+                items.push(item);
+            }
         }
+        assert_eq!(items, [1, 2, 3]);
+
+        let mut decoder = U16beDecoder::new().repeat_until_eos();
+        let mut buf = ReadBuf::new(vec![0; 3]);
+        let mut input = &[0, 1, 0][..];
+        track_try_unwrap!(buf.fill(&mut input));
+        assert_eq!(track_try_unwrap!(decoder.next_item(&mut buf)), Some(1));
+        assert_eq!(track_try_unwrap!(decoder.next_item(&mut buf)), None);
+
+        track_try_unwrap!(buf.fill(&mut input));
+        assert!(buf.stream_state().is_eos());
+        let error = decoder.next_item(&mut buf).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
     }
-}
-impl<D: Decode> Decode for MaybeEos<D> {
-    type Item = D::Item;
 
-    fn decode(&mut self, buf: &[u8], mut eos: Eos) -> Result<usize> {
-        if !self.started && buf.is_empty() && eos.is_reached() {
-            eos = Eos::new(false);
-        }
+    #[test]
+    fn rate_limit_works() {
+        let mut encoder = Utf8Encoder::new().rate_limit(3);
+        track_try_unwrap!(encoder.start_encoding("foobarbaz".to_owned()));
 
-        let size = track!(self.inner.decode(buf, eos))?;
-        if size != 0 {
-            self.started = true;
+        let mut buf = [0; 16];
+        let mut offset = 0;
+        let mut sizes = Vec::new();
+        while !encoder.is_idle() {
+            let size = track_try_unwrap!(encoder.encode(&mut buf[offset..], Eos::new(false)));
+            sizes.push(size);
+            offset += size;
         }
-        Ok(size)
-    }
+        assert_eq!(sizes, [3, 3, 3]);
+        assert_eq!(&buf[..offset], b"foobarbaz");
 
-    fn finish_decoding(&mut self) -> Result<Self::Item> {
-        self.started = false;
-        track!(self.inner.finish_decoding())
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("quux".to_owned()));
+        assert_eq!(bytes, b"quux");
     }
 
-    fn requiring_bytes(&self) -> ByteCount {
-        self.inner.requiring_bytes()
+    #[test]
+    fn dedup_works() {
+        let mut decoder = U8Decoder::new().dedup::<Vec<_>>();
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&[1, 1, 2, 2, 3]));
+        assert_eq!(item, [1, 2, 3]);
+
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&[3, 3, 1]));
+        assert_eq!(item, [3, 1]);
     }
 
-    fn is_idle(&self) -> bool {
-        self.inner.is_idle()
+    #[test]
+    fn checksummed_works_with_xor8() {
+        let mut encoder = Utf8Encoder::new().checksummed::<Xor8>();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("foo".to_owned()));
+        assert_eq!(bytes, b"foo\x66");
+
+        let mut decoder = U8Decoder::new()
+            .chain(U8Decoder::new())
+            .checksum_verified::<Xor8>();
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&[0x0f, 0xf0, 0xff])),
+            (0x0f, 0xf0)
+        );
+        let error = decoder
+            .decode_from_bytes(&[0x0f, 0xf0, 0x00])
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use crate::bytes::{Utf8Decoder, Utf8Encoder};
-    use crate::fixnum::{U16beDecoder, U8Decoder, U8Encoder};
-    use crate::io::{IoDecodeExt, IoEncodeExt};
-    use crate::tuple::TupleDecoder;
-    use crate::{Decode, DecodeExt, Encode, EncodeExt, Eos, ErrorKind};
+    #[test]
+    fn checksummed_works_with_crc32() {
+        let mut encoder = Utf8Encoder::new().checksummed::<Crc32>();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("123456789".to_owned()));
+        assert_eq!(&bytes[9..], [0xCB, 0xF4, 0x39, 0x26]);
+
+        let mut decoder = Utf8Decoder::new().length(9).checksum_verified::<Crc32>();
+        let mut input = &bytes[..];
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_exact(&mut input)),
+            "123456789"
+        );
+        assert!(input.is_empty());
+
+        let mut corrupted = bytes;
+        corrupted[0] = b'0';
+        let error = decoder.decode_exact(&corrupted[..]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
 
     #[test]
-    fn collect_works() {
-        let mut decoder = U8Decoder::new().collect::<Vec<_>>();
-        let item = track_try_unwrap!(decoder.decode_exact(b"foo".as_ref()));
-        assert_eq!(item, vec![b'f', b'o', b'o']);
+    fn checksum_verified_resets_state_on_error() {
+        let mut decoder = U8Decoder::new()
+            .try_map(|b| {
+                if b != 0 {
+                    Ok(b)
+                } else {
+                    Err(ErrorKind::InvalidInput)
+                }
+            })
+            .checksum_verified::<Xor8>();
+        let error = decoder.decode_from_bytes(&[0x00, 0x00]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&[0x05, 0x05])),
+            5
+        );
     }
 
     #[test]
-    fn collectn_works() {
-        let mut decoder = U8Decoder::new().collectn::<Vec<_>>(2);
-        let item = track_try_unwrap!(decoder.decode_exact(b"foo".as_ref()));
-        assert_eq!(item, vec![b'f', b'o']);
+    fn unknown_as_bytes_works() {
+        let mut inner = StringTaggedDecoder::new();
+        inner.register("short", U16beDecoder::new());
+        let mut decoder = inner.unknown_as_bytes();
 
-        let mut decoder = U8Decoder::new().collectn::<Vec<_>>(4);
+        let mut input = &b"\x05short\x01\x02\x04long\x01\x02\x03\x04"[..];
+        let mut name_decoder = U8Decoder::new().and_then(|n| Utf8Decoder::new().length(n.into()));
+
+        let name = track_try_unwrap!(name_decoder.decode_exact(&mut input));
+        track_try_unwrap!(decoder.start_decoding(name));
+        assert!(!decoder.is_idle());
         assert_eq!(
-            decoder
-                .decode_exact(b"foo".as_ref())
-                .err()
-                .map(|e| *e.kind()),
-            Some(ErrorKind::UnexpectedEos)
+            track_try_unwrap!(decoder.decode_exact(&mut input)),
+            MaybeKnown::Known(0x0102)
         );
+
+        let name = track_try_unwrap!(name_decoder.decode_exact(&mut input));
+        track_try_unwrap!(decoder.start_decoding(name));
+        let item = track_try_unwrap!(decoder.decode_from_bytes(input));
+        assert_eq!(item, MaybeKnown::Unknown(vec![1, 2, 3, 4]));
     }
 
     #[test]
-    fn decoder_length_works() {
-        // length=3
-        let mut decoder = Utf8Decoder::new().length(3);
-        let mut input = b"foobarba".as_ref();
-
-        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+    fn length_verified_decoder_works() {
+        let mut decoder =
+            LengthVerifiedDecoder::new(U16beDecoder::new().map(u64::from), Utf8Decoder::new());
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"\x00\x03foo"));
         assert_eq!(item, "foo");
 
-        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
-        assert_eq!(item, "bar");
+        let mut decoder = LengthVerifiedDecoder::new(
+            U16beDecoder::new().map(u64::from),
+            CopyableBytesDecoder::new([0; 2]),
+        );
+        track_try_unwrap!(decoder.decode(b"\x00\x03ab", Eos::new(false)));
+        assert!(decoder.is_idle());
+        assert_eq!(decoder.declared_length(), Some(3));
+        let error = decoder.finish_decoding().err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
 
-        let error = decoder.decode_exact(&mut input).err().unwrap();
-        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    #[test]
+    fn conditional_works() {
+        let mut decoder = U8Decoder::new().conditional(false);
+        assert!(decoder.is_idle());
+        assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(0));
+        assert_eq!(track_try_unwrap!(decoder.decode(b"ab", Eos::new(false))), 0);
+        let error = decoder.finish_decoding().err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InconsistentState);
 
-        // length=0
-        let mut decoder = Utf8Decoder::new().length(0);
+        decoder.set_enabled(true);
         assert!(!decoder.is_idle());
-        assert!(decoder.finish_decoding().is_err());
+        assert_eq!(track_try_unwrap!(decoder.decode(b"ab", Eos::new(false))), 1);
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), b'a');
 
-        let input = b"foobarba";
-        assert_eq!(decoder.decode(input, Eos::new(false)).ok(), Some(0));
-        assert!(decoder.is_idle());
-        assert!(decoder.finish_decoding().is_ok());
+        decoder.set_enabled(false);
+        assert_eq!(track_try_unwrap!(decoder.decode(b"b", Eos::new(false))), 0);
+
+        decoder.set_enabled(true);
+        assert_eq!(track_try_unwrap!(decoder.decode(b"b", Eos::new(false))), 1);
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), b'b');
     }
 
     #[test]
@@ -1546,6 +5414,127 @@ mod test {
         assert!(decoder.decode_from_bytes(b"1234").is_err());
     }
 
+    #[test]
+    fn decoder_min_bytes_works() {
+        let mut decoder = Utf8Decoder::new().min_bytes(3);
+
+        let item = track_try_unwrap!(decoder.decode_exact(b"foo".as_ref()));
+        assert_eq!(item, "foo");
+
+        let error = decoder.decode_exact(b"hi".as_ref()).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(decoder.consumed_bytes(), 0);
+    }
+
+    #[test]
+    fn with_consumed_works() {
+        let mut decoder = U8Decoder::new()
+            .and_then(|n| Utf8Decoder::new().length(u64::from(n)))
+            .with_consumed();
+
+        let mut input = &b"\x03foo\x05hello"[..];
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, (4, "foo".to_owned()));
+        assert_eq!(decoder.consumed_bytes(), 0);
+
+        let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(item, (6, "hello".to_owned()));
+    }
+
+    #[test]
+    fn rewind_on_error_works() {
+        let mut decoder = U16beDecoder::new()
+            .try_map(|n| -> crate::Result<_> {
+                track_assert_ne!(n, 0, ErrorKind::InvalidInput);
+                Ok(n)
+            })
+            .rewind_on_error();
+
+        assert_eq!(track_try_unwrap!(decoder.decode(&[0], Eos::new(false))), 1);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(&[0, 1], Eos::new(false))),
+            1
+        );
+        let error = decoder.finish_decoding().err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(decoder.consumed_before_error(), Some(2));
+
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&[0, 1]));
+        assert_eq!(item, 1);
+        assert_eq!(decoder.consumed_before_error(), None);
+    }
+
+    #[test]
+    fn crc32_header_works() {
+        let mut encoder = Utf8Encoder::new().crc32_header();
+        let mut bytes = track_try_unwrap!(encoder.encode_into_bytes("foo"));
+        assert_eq!(bytes, b"\x8c\x73\x65\x21foo");
+
+        let mut decoder = Utf8Decoder::new().length(3).crc32_header();
+        let mut input = &bytes[..];
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), "foo");
+        assert!(input.is_empty());
+
+        bytes[5] = b'x';
+        let error = decoder.decode_from_bytes(&bytes).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(b"\x8c\x73\x65\x21foo")),
+            "foo"
+        );
+    }
+
+    #[test]
+    fn repeat_exact_works() {
+        let mut encoder = U16beEncoder::new().repeat_exact();
+        track_try_unwrap!(encoder.start_encoding(0..4));
+        assert_eq!(encoder.exact_requiring_bytes(), 8);
+
+        let mut buf = [0; 3];
+        let size = track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false)));
+        assert_eq!(size, 3);
+        assert_eq!(encoder.exact_requiring_bytes(), 5);
+
+        let mut output = buf.to_vec();
+        track_try_unwrap!(encoder.encode_all(&mut output));
+        assert_eq!(output, [0, 0, 0, 1, 0, 2, 0, 3]);
+        assert_eq!(encoder.exact_requiring_bytes(), 0);
+        assert!(encoder.is_idle());
+
+        track_try_unwrap!(encoder.start_encoding(0..2));
+        let mut buf = [0; 4];
+        let size = track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false)));
+        assert_eq!(size, 4);
+        assert!(encoder.is_idle());
+
+        let mut encoder = NullEncoder.repeat_exact();
+        track_try_unwrap!(encoder.start_encoding(vec![()].into_iter()));
+        assert!(encoder.is_idle());
+
+        let mut encoder = Utf8Encoder::new().repeat_exact();
+        let error = encoder
+            .encode_into_bytes(vec!["foo", "ba"].into_iter())
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn flat_map_works() {
+        let mut decoder = U8Decoder::new().flat_map(|len| {
+            track_assert!(len <= 3, ErrorKind::InvalidInput; len);
+            Ok(Utf8Decoder::new().length(u64::from(len)))
+        });
+
+        let mut input = &b"\x03foo\x04barbaz"[..];
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), "foo");
+
+        let error = decoder.decode_exact(&mut input).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(input, b"barbaz");
+    }
+
     #[test]
     fn encoder_max_bytes_works() {
         let mut encoder = Utf8Encoder::new().max_bytes(3);
@@ -1583,6 +5572,61 @@ mod test {
         assert_eq!(decoder1.finish_decoding().ok(), Some("bar".to_owned()));
     }
 
+    #[test]
+    fn slice_total_consumed_bytes_works() {
+        let mut decoder = Utf8Decoder::new().length(6).slice();
+        let mut input = &b"foobar"[..];
+        for &(n, total) in &[(1, 1), (2, 3), (3, 6)] {
+            decoder.set_consumable_bytes(n);
+            let size = track_try_unwrap!(decoder.decode(input, Eos::new(false)));
+            input = &input[size..];
+            assert_eq!(decoder.consumable_bytes(), 0);
+            assert_eq!(decoder.total_consumed_bytes(), total);
+        }
+        assert_eq!(decoder.finish_decoding().ok(), Some("foobar".to_owned()));
+        assert_eq!(decoder.total_consumed_bytes(), 0);
+    }
+
+    #[test]
+    fn decoder_slice_status_works() {
+        let mut decoder = Utf8Decoder::new().length(3).slice();
+        assert_eq!(decoder.decode_status(), DecodeStatus::Suspended);
+
+        decoder.set_consumable_bytes(2);
+        assert_eq!(decoder.decode_status(), DecodeStatus::NeedMoreBytes);
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        assert_eq!(decoder.decode_status(), DecodeStatus::Suspended);
+
+        decoder.set_consumable_bytes(2);
+        track_try_unwrap!(decoder.decode(b"o", Eos::new(false)));
+        assert_eq!(decoder.decode_status(), DecodeStatus::Idle);
+        assert_eq!(decoder.finish_decoding().ok(), Some("foo".to_owned()));
+    }
+
+    #[test]
+    fn decode_status_is_forwarded_by_wrappers() {
+        let mut decoder = Utf8Decoder::new().length(3).slice().peekable();
+        assert_eq!(decoder.decode_status(), DecodeStatus::Suspended);
+
+        decoder.inner_mut().set_consumable_bytes(3);
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        assert_eq!(decoder.decode_status(), DecodeStatus::Idle);
+        assert_eq!(decoder.finish_decoding().ok(), Some("foo".to_owned()));
+
+        let mut decoder = TupleDecoder::new((U8Decoder::new(), U8Decoder::new().slice()));
+        assert_eq!(decoder.decode_status(), DecodeStatus::NeedMoreBytes);
+        track_try_unwrap!(decoder.decode(b"a", Eos::new(false)));
+        assert_eq!(decoder.decode_status(), DecodeStatus::Suspended);
+
+        let mut decoder = U8Decoder::new().slice().collect::<Vec<_>>();
+        assert_eq!(decoder.decode_status(), DecodeStatus::Suspended);
+        decoder.inner_mut().set_consumable_bytes(1);
+        track_try_unwrap!(decoder.decode(b"a", Eos::new(false)));
+        assert_eq!(decoder.decode_status(), DecodeStatus::Suspended);
+        track_try_unwrap!(decoder.decode(b"", Eos::new(true)));
+        assert_eq!(decoder.decode_status(), DecodeStatus::Idle);
+    }
+
     #[test]
     fn encoder_slice_works() {
         let mut encoder = Utf8Encoder::new().slice();
@@ -1612,6 +5656,122 @@ mod test {
         assert_eq!(output.as_ref(), b"foobarbazqux");
     }
 
+    #[test]
+    fn buffered_atomic_works() {
+        let mut encoder = Utf8Encoder::new().buffered_atomic();
+        assert_eq!(encoder.take_packet(), None);
+
+        track_try_unwrap!(encoder.start_encoding("foo"));
+        assert!(!encoder.is_idle());
+        assert!(encoder.start_encoding("bar").is_err());
+        assert_eq!(encoder.take_packet(), Some(b"foo".to_vec()));
+        assert!(encoder.is_idle());
+
+        track_try_unwrap!(encoder.start_encoding("barbaz"));
+        let mut buf = [0; 8];
+        assert_eq!(
+            track_try_unwrap!(encoder.encode(&mut buf[..4], Eos::new(false))),
+            0
+        );
+        assert_eq!(
+            track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false))),
+            6
+        );
+        assert_eq!(&buf[..6], b"barbaz");
+        assert_eq!(encoder.take_packet(), None);
+    }
+
+    #[test]
+    fn concat_works() {
+        let header = CopyableBytesDecoder::new([0; 3]).map(|b| b.to_vec());
+        let mut decoder = header.concat(RemainingBytesDecoder::new());
+
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"abcdefg"));
+        assert_eq!(item, b"abcdefg");
+
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"xyz"));
+        assert_eq!(item, b"xyz");
+
+        assert!(decoder.decode_from_bytes(b"xy").is_err());
+    }
+
+    #[test]
+    fn peek_bytes_works() {
+        let mut branches = StringTaggedDecoder::new();
+        branches.register("short", U8Decoder::new().map(u16::from));
+        branches.register("long", U16beDecoder::new().map(|n| n & 0x7FFF));
+        let mut decoder = branches.peek_bytes(1, |peeked, inner| {
+            let tag = if peeked[0] < 0x80 { "short" } else { "long" };
+            track!(inner.start_decoding(tag.to_owned()))
+        });
+
+        let input = [0x05, 0x81, 0x02];
+        let mut offset = 0;
+        for expected in &[0x05, 0x0102] {
+            offset += track_try_unwrap!(decoder.decode(&input[offset..], Eos::new(true)));
+            assert!(decoder.is_idle());
+            assert_eq!(track_try_unwrap!(decoder.finish_decoding()), *expected);
+        }
+        assert_eq!(offset, input.len());
+    }
+
+    #[test]
+    fn peek_bytes_carries_over_bytes_beyond_item() {
+        let mut decoder = U8Decoder::new().peek_bytes(2, |_, _| Ok(()));
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&[1, 2])), 1);
+
+        let size = track_try_unwrap!(decoder.decode(&[3], Eos::new(false)));
+        assert_eq!(size, 1);
+        assert_eq!(decoder.peeked(), [2, 3]);
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), 2);
+
+        track_try_unwrap!(decoder.decode(&[], Eos::new(true)));
+        assert_eq!(decoder.peeked(), [3]);
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), 3);
+    }
+
+    #[test]
+    fn terminated_by_works() {
+        let mut encoder = U8Encoder::new().terminated_by(0x00);
+        let mut output = Vec::new();
+        for item in 1..=3 {
+            track_try_unwrap!(encoder.start_encoding(item));
+            track_try_unwrap!(encoder.encode_all(&mut output));
+        }
+        assert_eq!(output, [1, 2, 3]);
+        assert!(!encoder.is_terminated());
+
+        encoder.finish();
+        assert_eq!(encoder.requiring_bytes(), ByteCount::Finite(1));
+        track_try_unwrap!(encoder.encode_all(&mut output));
+        assert_eq!(output, [1, 2, 3, 0]);
+        assert!(encoder.is_terminated());
+        assert!(encoder.start_encoding(4).is_err());
+
+        let mut encoder = U8Encoder::new().terminated_by(0x00);
+        assert_eq!(track_try_unwrap!(encoder.encode_into_bytes(1)), [1]);
+        assert_eq!(track_try_unwrap!(encoder.encode_into_bytes(2)), [2]);
+        encoder.finish();
+        let mut output = Vec::new();
+        track_try_unwrap!(encoder.encode_all(&mut output));
+        assert_eq!(output, [0]);
+    }
+
+    #[test]
+    fn interleave_works() {
+        let mut encoder = Utf8Encoder::new().interleave(Utf8Encoder::new(), 2);
+        track_try_unwrap!(encoder.start_encoding(("abcde", "12345678")));
+        assert_eq!(encoder.exact_requiring_bytes(), 13);
+
+        let mut output = Vec::new();
+        let mut buf = [0; 3];
+        while !encoder.is_idle() {
+            let size = track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false)));
+            output.extend_from_slice(&buf[..size]);
+        }
+        assert_eq!(output, b"ab12cd34e5678");
+    }
+
     #[test]
     fn and_then_works() {
         let mut decoder =
@@ -1,9 +1,14 @@
+use crate::checksum::Checksum;
 use crate::combinator::{
-    AndThen, Collect, CollectN, Length, Map, MapErr, MaxBytes, MaybeEos, Omittable, Peekable,
-    Slice, TryMap,
+    AndThen, AndThenSized, AssertEq, CheckedBy, ChecksumVerified, Collect, CollectN, CollectUntil,
+    Concat, Conditional, Context, Crc32Header, DedupCollect, FlatMap, Length, Map, MapErr, MapTag,
+    MapWithState, MaxBytes, MaybeEos, MinBytes, Omittable, PeekBytes, Peekable, PresenceGated,
+    Recover, RepeatUntilEos, RewindOnError, Slice, Take, TotalMaxBytes, TryMap, UnknownAsBytes,
+    WithConsumed, WithOffset,
 };
 use crate::tuple::TupleDecoder;
 use crate::{ByteCount, Eos, Error, ErrorKind, Result};
+use trackable::error::BoxError;
 
 /// This trait allows for decoding items from a byte sequence incrementally.
 pub trait Decode {
@@ -81,8 +86,26 @@ pub trait Decode {
     fn is_idle(&self) -> bool {
         self.requiring_bytes() == ByteCount::Finite(0)
     }
+
+    /// Returns the current status of the decoder.
+    ///
+    /// This gives schedulers a single signal to decide what to do next with the decoder.
+    ///
+    /// The default implementation derives the status from `is_idle` and `requiring_bytes` methods:
+    /// - `DecodeStatus::Idle` if `self.is_idle()` returns `true`
+    /// - `DecodeStatus::Terminated` if `self.requiring_bytes()` returns `ByteCount::Finite(0)`
+    /// - `DecodeStatus::NeedMoreBytes` otherwise
+    fn decode_status(&self) -> DecodeStatus {
+        if self.is_idle() {
+            DecodeStatus::Idle
+        } else if self.requiring_bytes() == ByteCount::Finite(0) {
+            DecodeStatus::Terminated
+        } else {
+            DecodeStatus::NeedMoreBytes
+        }
+    }
 }
-impl<'a, D: ?Sized + Decode> Decode for &'a mut D {
+impl<D: ?Sized + Decode> Decode for &mut D {
     type Item = D::Item;
 
     fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
@@ -100,6 +123,10 @@ impl<'a, D: ?Sized + Decode> Decode for &'a mut D {
     fn is_idle(&self) -> bool {
         (**self).is_idle()
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        (**self).decode_status()
+    }
 }
 impl<D: ?Sized + Decode> Decode for Box<D> {
     type Item = D::Item;
@@ -119,6 +146,29 @@ impl<D: ?Sized + Decode> Decode for Box<D> {
     fn is_idle(&self) -> bool {
         (**self).is_idle()
     }
+
+    fn decode_status(&self) -> DecodeStatus {
+        (**self).decode_status()
+    }
+}
+
+/// Status of a decoder.
+///
+/// This is returned by `Decode::decode_status` method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeStatus {
+    /// An item has been decoded and is waiting for `finish_decoding` to be called.
+    Idle,
+
+    /// The decoder requires more bytes to decode the next item.
+    NeedMoreBytes,
+
+    /// The decoder cannot consume any more bytes until it is resumed by the client
+    /// (e.g., the consumable bytes of a `Slice` reached `0`).
+    Suspended,
+
+    /// The decoder cannot decode any more items.
+    Terminated,
 }
 
 /// An extension of `Decode` trait.
@@ -143,6 +193,56 @@ pub trait DecodeExt: Decode + Sized {
         Map::new(self, f)
     }
 
+    /// Creates a decoder that checks whether decoded values are equal to `expected`.
+    ///
+    /// If a decoded value differs from `expected`, `finish_decoding` returns an
+    /// `ErrorKind::InvalidInput` error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new().assert_eq(1);
+    /// assert_eq!(decoder.decode_from_bytes(&[1][..]).unwrap(), 1);
+    ///
+    /// let error = decoder.decode_from_bytes(&[2][..]).err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// ```
+    fn assert_eq(self, expected: Self::Item) -> AssertEq<Self>
+    where
+        Self::Item: PartialEq + std::fmt::Debug,
+    {
+        AssertEq::new(self, expected)
+    }
+
+    /// Creates a decoder that converts decoded values by calling the given function with a mutable state.
+    ///
+    /// The state (initialized by `init`) is threaded through the conversions of successive items.
+    ///
+    /// # Examples
+    ///
+    /// Delta decoding:
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new().map_with_state(0, |prev, delta| {
+    ///     *prev += delta;
+    ///     *prev
+    /// });
+    /// let items: Vec<_> = decoder.decode_all_from_bytes(&[1, 1, 1][..]).unwrap();
+    /// assert_eq!(items, [1, 2, 3]);
+    /// ```
+    fn map_with_state<S, T, F>(self, init: S, f: F) -> MapWithState<Self, S, T, F>
+    where
+        F: FnMut(&mut S, Self::Item) -> T,
+    {
+        MapWithState::new(self, init, f)
+    }
+
     /// Creates a decoder that tries to convert decoded values by calling the given function.
     ///
     /// # Examples
@@ -195,11 +295,11 @@ pub trait DecodeExt: Decode + Sized {
     /// UnexpectedEos (cause; assertion failed: `!eos.is_reached()`; \
     ///                self.offset=1, self.bytes.as_ref().len()=2)
     /// HISTORY:
-    ///   [0] at src/bytes.rs:152
+    ///   [0] at src/bytes.rs:158
     ///   [1] at src/fixnum.rs:200
-    ///   [2] at src/decode.rs:10 -- oops!
-    ///   [3] at src/io.rs:45
-    ///   [4] at src/decode.rs:14\n");
+    ///   [2] at src/decode.rs:11 -- oops!
+    ///   [3] at src/io.rs:46
+    ///   [4] at src/decode.rs:15\n");
     /// ```
     fn map_err<E, F>(self, f: F) -> MapErr<Self, E, F>
     where
@@ -209,6 +309,50 @@ pub trait DecodeExt: Decode + Sized {
         MapErr::new(self, f)
     }
 
+    /// Creates a decoder that recovers from the errors returned by `finish_decoding` of `self`
+    /// by producing the item returned by `f`.
+    ///
+    /// Note that the errors returned by `decode` (e.g., `UnexpectedEos`) and
+    /// `IncompleteDecoding` errors returned by `finish_decoding` are not recovered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new()
+    ///     .try_map(|b| if b < 10 { Ok(b) } else { Err(ErrorKind::InvalidInput) })
+    ///     .recover(|_| 0);
+    /// assert_eq!(decoder.decode_from_bytes(&[3][..]).unwrap(), 3);
+    /// assert_eq!(decoder.decode_from_bytes(&[30][..]).unwrap(), 0);
+    /// assert!(decoder.decode_from_bytes(&[][..]).is_err());
+    /// ```
+    fn recover<F>(self, f: F) -> Recover<Self, F>
+    where
+        F: Fn(Error) -> Self::Item,
+    {
+        Recover::new(self, f)
+    }
+
+    /// Creates a decoder that attaches the static context label to decoding errors produced by `self`.
+    ///
+    /// This is equivalent to `self.map_err(move |e| track!(e, label))`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U16beDecoder;
+    ///
+    /// let mut decoder = U16beDecoder::new().context("reading length");
+    /// let error = decoder.decode_from_bytes(&[0][..]).err().unwrap();
+    /// assert!(error.to_string().contains("-- reading length"));
+    /// ```
+    fn context(self, label: &'static str) -> Context<Self> {
+        Context::new(self, label)
+    }
+
     /// Creates a decoder that enables conditional decoding.
     ///
     /// If the first item is successfully decoded,
@@ -236,6 +380,138 @@ pub trait DecodeExt: Decode + Sized {
         AndThen::new(self, f)
     }
 
+    /// Creates a decoder that enables conditional decoding with an explicit size of the second item.
+    ///
+    /// If the first item is successfully decoded,
+    /// it will start decoding the second item by using the decoder returned by `f` function.
+    /// The second decoder must consume exactly the number of bytes returned by `f` together with it.
+    ///
+    /// Unlike `and_then`, `requiring_bytes` method of the resulting decoder
+    /// returns the precise number of the remaining bytes once the first item is decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{ByteCount, Decode, DecodeExt, Eos};
+    /// use bytecodec::bytes::Utf8Decoder;
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new().and_then_sized(|len| (Utf8Decoder::new(), u64::from(len)));
+    /// decoder.decode(b"\x03f", Eos::new(false)).unwrap();
+    /// assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(2));
+    ///
+    /// decoder.decode(b"oobar", Eos::new(false)).unwrap();
+    /// assert_eq!(decoder.finish_decoding().unwrap(), "foo");
+    /// ```
+    fn and_then_sized<D, F>(self, f: F) -> AndThenSized<Self, D, F>
+    where
+        F: Fn(Self::Item) -> (D, u64),
+        D: Decode,
+    {
+        AndThenSized::new(self, f)
+    }
+
+    /// Creates a decoder that enables conditional decoding with a fallible continuation.
+    ///
+    /// This is the same as `and_then` except that `f` can reject the first item
+    /// (e.g., an out-of-range length) by returning an error before decoding the second item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::bytes::Utf8Decoder;
+    /// use bytecodec::fixnum::U8Decoder;
+    /// use bytecodec::io::IoDecodeExt;
+    ///
+    /// let mut decoder = U8Decoder::new().flat_map(|len| {
+    ///     if len > 3 {
+    ///         Err(ErrorKind::InvalidInput.into())
+    ///     } else {
+    ///         Ok(Utf8Decoder::new().length(u64::from(len)))
+    ///     }
+    /// });
+    /// let item = decoder.decode_exact(b"\x03foobar".as_ref()).unwrap();
+    /// assert_eq!(item, "foo");
+    ///
+    /// let error = decoder.decode_exact(b"\x04foobar".as_ref()).err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// ```
+    fn flat_map<D, F>(self, f: F) -> FlatMap<Self, D, F>
+    where
+        F: Fn(Self::Item) -> Result<D>,
+        D: Decode,
+    {
+        FlatMap::new(self, f)
+    }
+
+    /// Creates a decoder that converts the tags passed to `start_decoding` method
+    /// into ones suited to the `self` decoder by calling the given function.
+    ///
+    /// If the conversion fails, `start_decoding` method will return an `ErrorKind::InvalidInput` error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, DecodeExt, ErrorKind, TaggedDecode};
+    /// use bytecodec::fixnum::{U16beDecoder, U8Decoder};
+    /// use bytecodec::tagged::StringTaggedDecoder;
+    ///
+    /// let mut inner = StringTaggedDecoder::new();
+    /// inner.register("byte", U8Decoder::new().map(u16::from));
+    /// inner.register("short", U16beDecoder::new());
+    ///
+    /// let mut decoder = inner.map_tag(|tag: u8| match tag {
+    ///     0 => Ok("byte".to_owned()),
+    ///     1 => Ok("short".to_owned()),
+    ///     _ => Err(format!("unknown tag: {}", tag)),
+    /// });
+    ///
+    /// decoder.start_decoding(1).unwrap();
+    /// assert_eq!(decoder.decode_from_bytes(&[1, 2][..]).unwrap(), 0x0102);
+    ///
+    /// let error = decoder.start_decoding(2).err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// ```
+    fn map_tag<T, E, F>(self, f: F) -> MapTag<Self, T, E, F>
+    where
+        Self: TaggedDecode,
+        F: Fn(T) -> std::result::Result<<Self as TaggedDecode>::Tag, E>,
+        E: Into<BoxError>,
+    {
+        MapTag::new(self, f)
+    }
+
+    /// Creates a decoder that captures the items tagged by unknown tags as raw bytes.
+    ///
+    /// This is useful for decoding extensible formats in a forward compatible manner.
+    /// See `UnknownAsBytes` for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, TaggedDecode};
+    /// use bytecodec::combinator::MaybeKnown;
+    /// use bytecodec::fixnum::U8Decoder;
+    /// use bytecodec::tagged::StringTaggedDecoder;
+    ///
+    /// let mut inner = StringTaggedDecoder::new();
+    /// inner.register("byte", U8Decoder::new());
+    /// let mut decoder = inner.unknown_as_bytes();
+    ///
+    /// decoder.start_decoding("byte".to_owned()).unwrap();
+    /// assert_eq!(decoder.decode_from_bytes(&[1][..]).unwrap(), MaybeKnown::Known(1));
+    ///
+    /// decoder.start_decoding("short".to_owned()).unwrap();
+    /// assert_eq!(decoder.decode_from_bytes(&[1, 2][..]).unwrap(), MaybeKnown::Unknown(vec![1, 2]));
+    /// ```
+    fn unknown_as_bytes(self) -> UnknownAsBytes<Self>
+    where
+        Self: TryTaggedDecode,
+    {
+        UnknownAsBytes::new(self)
+    }
+
     /// Creates a decoder for collecting decoded items.
     ///
     /// # Examples
@@ -256,6 +532,51 @@ pub trait DecodeExt: Decode + Sized {
         Collect::new(self)
     }
 
+    /// Creates a decoder for collecting decoded items except consecutive duplicates.
+    ///
+    /// This is the same as `collect` except that an item equal to the previous one is skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new().dedup::<Vec<_>>();
+    /// let item = decoder.decode_from_bytes(b"aabca").unwrap();
+    /// assert_eq!(item, b"abca");
+    /// ```
+    fn dedup<T>(self) -> DedupCollect<Self, T>
+    where
+        Self::Item: PartialEq + Clone,
+        T: Extend<Self::Item> + Default,
+    {
+        DedupCollect::new(self)
+    }
+
+    /// Creates a decoder for collecting decoded key-value pairs into a map.
+    ///
+    /// This is the same as `collect` except that the item type of `self` is restricted to pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U8Decoder;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut decoder = U8Decoder::new().chain(U8Decoder::new()).collect_map();
+    /// let map: BTreeMap<_, _> = decoder.decode_from_bytes(b"a1b2").unwrap();
+    /// assert_eq!(map.get(&b'b'), Some(&b'2'));
+    /// ```
+    fn collect_map<K, V, M>(self) -> Collect<Self, M>
+    where
+        Self: Decode<Item = (K, V)>,
+        M: Extend<(K, V)> + Default,
+    {
+        Collect::new(self)
+    }
+
     /// Creates a decoder that decodes `n` items by using `self` and collecting the result.
     ///
     /// # Examples
@@ -276,6 +597,51 @@ pub trait DecodeExt: Decode + Sized {
         CollectN::new(self, n)
     }
 
+    /// Creates a decoder that collects decoded items until an item for which `f` returns `true` is decoded.
+    ///
+    /// The terminating item is not included in the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new().collect_until::<Vec<_>, _>(|&b| b == 0);
+    /// let item = decoder.decode_from_bytes(b"foo\x00").unwrap();
+    /// assert_eq!(item, b"foo");
+    /// ```
+    fn collect_until<T, F>(self, f: F) -> CollectUntil<Self, T, F>
+    where
+        T: Extend<Self::Item> + Default,
+        F: Fn(&Self::Item) -> bool,
+    {
+        CollectUntil::new(self, f)
+    }
+
+    /// Creates a decoder for pulling decoded items one at a time from a read buffer until it reaches EOS.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U8Decoder;
+    /// use bytecodec::io::ReadBuf;
+    ///
+    /// let mut buf = ReadBuf::new(vec![0; 16]);
+    /// buf.fill(&b"foo"[..]).unwrap();
+    ///
+    /// let mut decoder = U8Decoder::new().repeat_until_eos();
+    /// assert_eq!(decoder.next_item(&mut buf).unwrap(), Some(b'f'));
+    /// assert_eq!(decoder.next_item(&mut buf).unwrap(), Some(b'o'));
+    /// assert_eq!(decoder.next_item(&mut buf).unwrap(), Some(b'o'));
+    /// assert_eq!(decoder.next_item(&mut buf).unwrap(), None);
+    /// assert!(decoder.is_eos());
+    /// ```
+    fn repeat_until_eos(self) -> RepeatUntilEos<Self> {
+        RepeatUntilEos::new(self)
+    }
+
     /// Creates a decoder that consumes the specified number of bytes exactly.
     ///
     /// # Examples
@@ -301,6 +667,25 @@ pub trait DecodeExt: Decode + Sized {
         Length::new(self, expected_bytes)
     }
 
+    /// Creates a decoder that decodes an item from at most `bytes` bytes.
+    ///
+    /// The inner decoder regards the `bytes`-th byte as EOS.
+    /// Unlike `length`, the item does not have to consume exactly `bytes` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, DecodeExt, Eos};
+    /// use bytecodec::bytes::RemainingBytesDecoder;
+    ///
+    /// let mut decoder = RemainingBytesDecoder::new().take(3);
+    /// assert_eq!(decoder.decode(b"foobar", Eos::new(false)).unwrap(), 3);
+    /// assert_eq!(decoder.finish_decoding().unwrap(), b"foo");
+    /// ```
+    fn take(self, bytes: u64) -> Take<Self> {
+        Take::new(self, bytes)
+    }
+
     /// Creates a decoder that will omit decoding items if `do_omit = true` is specified.
     ///
     /// # Examples
@@ -324,6 +709,52 @@ pub trait DecodeExt: Decode + Sized {
         Omittable::new(self, do_omit)
     }
 
+    /// Creates a decoder that decodes an item only if `presence` decodes `true` beforehand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, DecodeExt};
+    /// use bytecodec::fixnum::U8Decoder;
+    /// use bytecodec::io::IoDecodeExt;
+    ///
+    /// let mut input = &[1, 10, 0, 20][..];
+    /// let mut decoder = U8Decoder::new().optional_if(U8Decoder::new().map(|b| b != 0));
+    ///
+    /// assert_eq!(decoder.decode_exact(&mut input).unwrap(), Some(10));
+    /// assert_eq!(decoder.decode_exact(&mut input).unwrap(), None);
+    /// assert_eq!(input, [20]);
+    /// ```
+    fn optional_if<P>(self, presence: P) -> PresenceGated<P, Self>
+    where
+        P: Decode<Item = bool>,
+    {
+        PresenceGated::new(self, presence)
+    }
+
+    /// Creates a decoder that can be enabled or disabled at runtime.
+    ///
+    /// Unlike `omit`, the item type is not wrapped in `Option`.
+    /// While disabled, the decoder consumes no bytes and `finish_decoding` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, DecodeExt, Eos};
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new().conditional(false);
+    /// assert_eq!(decoder.decode(b"foo", Eos::new(false)).unwrap(), 0);
+    /// assert!(decoder.is_idle());
+    ///
+    /// decoder.set_enabled(true);
+    /// assert_eq!(decoder.decode(b"foo", Eos::new(false)).unwrap(), 1);
+    /// assert_eq!(decoder.finish_decoding().unwrap(), b'f');
+    /// ```
+    fn conditional(self, enabled: bool) -> Conditional<Self> {
+        Conditional::new(self, enabled)
+    }
+
     /// Creates a decoder that will fail if the number of consumed bytes exceeds `bytes`.
     ///
     /// # Examples
@@ -345,6 +776,185 @@ pub trait DecodeExt: Decode + Sized {
         MaxBytes::new(self, bytes)
     }
 
+    /// Creates a decoder that will fail if the total number of consumed bytes exceeds `bytes`.
+    ///
+    /// Unlike `max_bytes`, the count of consumed bytes is not reset per item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let mut decoder = U8Decoder::new().total_max_bytes(2);
+    /// assert_eq!(decoder.decode_from_bytes(&[1][..]).unwrap(), 1);
+    /// assert_eq!(decoder.decode_from_bytes(&[2][..]).unwrap(), 2);
+    ///
+    /// let error = decoder.decode_from_bytes(&[3][..]).err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// ```
+    fn total_max_bytes(self, bytes: u64) -> TotalMaxBytes<Self> {
+        TotalMaxBytes::new(self, bytes)
+    }
+
+    /// Creates a decoder that will fail if the number of consumed bytes for an item is less than `bytes`.
+    ///
+    /// This is checked when `finish_decoding` method is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, DecodeExt, ErrorKind};
+    /// use bytecodec::bytes::Utf8Decoder;
+    /// use bytecodec::io::IoDecodeExt;
+    ///
+    /// let mut decoder = Utf8Decoder::new().min_bytes(3);
+    ///
+    /// let item = decoder.decode_exact(b"foo".as_ref()).unwrap();
+    /// assert_eq!(item, "foo"); // OK
+    ///
+    /// let error = decoder.decode_exact(b"hi".as_ref()).err();
+    /// assert_eq!(error.map(|e| *e.kind()), Some(ErrorKind::InvalidInput)); // Error
+    /// ```
+    fn min_bytes(self, bytes: u64) -> MinBytes<Self> {
+        MinBytes::new(self, bytes)
+    }
+
+    /// Creates a decoder that returns the number of bytes consumed for each item alongside the item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, DecodeExt};
+    /// use bytecodec::fixnum::U8Decoder;
+    /// use bytecodec::bytes::Utf8Decoder;
+    /// use bytecodec::io::IoDecodeExt;
+    ///
+    /// let mut decoder = U8Decoder::new()
+    ///     .and_then(|n| Utf8Decoder::new().length(u64::from(n)))
+    ///     .with_consumed();
+    ///
+    /// let item = decoder.decode_exact(b"\x03foobar".as_ref()).unwrap();
+    /// assert_eq!(item, (4, "foo".to_owned()));
+    /// ```
+    fn with_consumed(self) -> WithConsumed<Self> {
+        WithConsumed::new(self)
+    }
+
+    /// Creates a decoder that returns the absolute byte offset at which each item began alongside the item.
+    ///
+    /// The offset accumulates over the whole lifetime of the decoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U16beDecoder;
+    ///
+    /// let mut decoder = U16beDecoder::new().with_offset();
+    /// let items: Vec<_> = decoder.decode_all_from_bytes(&[0, 1, 0, 2][..]).unwrap();
+    /// assert_eq!(items, [(0, 1), (2, 2)]);
+    /// ```
+    fn with_offset(self) -> WithOffset<Self> {
+        WithOffset::new(self)
+    }
+
+    /// Creates a decoder that reports the number of bytes consumed for an item when decoding it fails.
+    ///
+    /// The reported count can be used to rewind the caller's buffer and retry decoding from
+    /// the beginning of the failed item. See `RewindOnError` for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::fixnum::U8Decoder;
+    /// use bytecodec::bytes::Utf8Decoder;
+    /// use bytecodec::io::IoDecodeExt;
+    ///
+    /// let mut decoder = U8Decoder::new()
+    ///     .and_then(|n| Utf8Decoder::new().length(u64::from(n)))
+    ///     .rewind_on_error();
+    ///
+    /// let input = b"\x02\xff\xfe";
+    /// let error = decoder.decode_exact(&input[..]).err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// assert_eq!(decoder.consumed_before_error(), Some(3));
+    /// ```
+    fn rewind_on_error(self) -> RewindOnError<Self> {
+        RewindOnError::new(self)
+    }
+
+    /// Creates a decoder that verifies the check byte following each item.
+    ///
+    /// The expected check byte is computed by applying `f` to the raw bytes of the item.
+    /// This is useful for simple protocols that use 1-byte XOR or sum checks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::fixnum::U16beDecoder;
+    ///
+    /// let sum = |bytes: &[u8]| bytes.iter().fold(0u8, |a, b| a.wrapping_add(*b));
+    /// let mut decoder = U16beDecoder::new().checked_by(sum);
+    /// assert_eq!(decoder.decode_from_bytes(&[1, 2, 3][..]).unwrap(), 0x0102);
+    ///
+    /// let error = decoder.decode_from_bytes(&[1, 2, 4][..]).err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// ```
+    fn checked_by<F>(self, f: F) -> CheckedBy<Self, F>
+    where
+        F: Fn(&[u8]) -> u8,
+    {
+        CheckedBy::new(self, f)
+    }
+
+    /// Creates a decoder that verifies the CRC-32 checksum header preceding each item.
+    ///
+    /// See `Crc32Header` for the layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::bytes::Utf8Decoder;
+    ///
+    /// let mut decoder = Utf8Decoder::new().crc32_header();
+    /// let item = decoder.decode_from_bytes(b"\x8c\x73\x65\x21foo").unwrap();
+    /// assert_eq!(item, "foo");
+    ///
+    /// let error = decoder.decode_from_bytes(b"\x8c\x73\x65\x21bar").err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// ```
+    fn crc32_header(self) -> Crc32Header<Self> {
+        Crc32Header::new(self)
+    }
+
+    /// Creates a decoder that verifies the checksum following each item.
+    ///
+    /// The checksum algorithm is specified by the type parameter `C`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, ErrorKind};
+    /// use bytecodec::checksum::Xor8;
+    /// use bytecodec::fixnum::U16beDecoder;
+    ///
+    /// let mut decoder = U16beDecoder::new().checksum_verified::<Xor8>();
+    /// assert_eq!(decoder.decode_from_bytes(&[1, 2, 3][..]).unwrap(), 0x0102);
+    ///
+    /// let error = decoder.decode_from_bytes(&[1, 2, 4][..]).err().unwrap();
+    /// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    /// ```
+    fn checksum_verified<C>(self) -> ChecksumVerified<C, Self>
+    where
+        C: Checksum + Default,
+    {
+        ChecksumVerified::new(self)
+    }
+
     /// Takes two decoders and creates a new decoder that decodes both items in sequence.
     ///
     /// This is equivalent to call `TupleDecoder::new((self, other))`.
@@ -352,6 +962,27 @@ pub trait DecodeExt: Decode + Sized {
         TupleDecoder::new((self, other))
     }
 
+    /// Takes two byte sequence decoders and creates a new decoder that
+    /// decodes both in sequence and returns the concatenated bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::bytes::{BytesDecoder, RemainingBytesDecoder};
+    ///
+    /// let mut decoder = BytesDecoder::new(vec![0; 3]).concat(RemainingBytesDecoder::new());
+    /// let item = decoder.decode_from_bytes(b"foobar").unwrap();
+    /// assert_eq!(item, b"foobar");
+    /// ```
+    fn concat<D>(self, other: D) -> Concat<Self, D>
+    where
+        Self: Decode<Item = Vec<u8>>,
+        D: Decode<Item = Vec<u8>>,
+    {
+        Concat::new(self, other)
+    }
+
     /// Creates a decoder that makes it possible to slice the input byte sequence in arbitrary units.
     ///
     /// Slicing an input byte sequence makes it easier to demultiplex multiple sequences from it.
@@ -409,6 +1040,37 @@ pub trait DecodeExt: Decode + Sized {
         Peekable::new(self)
     }
 
+    /// Creates a decoder that enables to inspect the first `bytes` bytes of each item
+    /// before they are consumed by `self`.
+    ///
+    /// Once the bytes are collected, `f` is called with them and `self`.
+    /// See the documentation of `PeekBytes` for the details of the buffering model.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{DecodeExt, TaggedDecode};
+    /// use bytecodec::fixnum::{U16beDecoder, U8Decoder};
+    /// use bytecodec::tagged::StringTaggedDecoder;
+    ///
+    /// let mut branches = StringTaggedDecoder::new();
+    /// branches.register("short", U8Decoder::new().map(u16::from));
+    /// branches.register("long", U16beDecoder::new().map(|n| n & 0x7FFF));
+    /// let mut decoder = branches.peek_bytes(1, |peeked, inner| {
+    ///     let tag = if peeked[0] < 0x80 { "short" } else { "long" };
+    ///     inner.start_decoding(tag.to_owned())
+    /// });
+    ///
+    /// assert_eq!(decoder.decode_from_bytes(&[0x05][..]).unwrap(), 0x05);
+    /// assert_eq!(decoder.decode_from_bytes(&[0x81, 0x02][..]).unwrap(), 0x0102);
+    /// ```
+    fn peek_bytes<F>(self, bytes: usize, f: F) -> PeekBytes<Self, F>
+    where
+        F: FnMut(&[u8], &mut Self) -> Result<()>,
+    {
+        PeekBytes::new(self, bytes, f)
+    }
+
     /// Creates a decoder that ignores EOS if there is no item being decoded.
     ///
     /// # Examples
@@ -449,6 +1111,116 @@ pub trait DecodeExt: Decode + Sized {
         track_assert_eq!(size, buf.len(), ErrorKind::InvalidInput; self.is_idle());
         track!(self.finish_decoding())
     }
+
+    /// Decodes an item from the beginning of the given bytes.
+    ///
+    /// Unlike `decode_from_bytes`, the bytes following the item are left unconsumed.
+    /// This returns the decoded item and the number of consumed bytes.
+    ///
+    /// The end of the given bytes is regarded as EOS.
+    /// If the bytes end in the middle of an item, an `ErrorKind::UnexpectedEos` error will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U16beDecoder;
+    ///
+    /// let mut decoder = U16beDecoder::new();
+    /// let (item, size) = decoder.decode_one_from_bytes(&[0x12, 0x34, 0x56][..]).unwrap();
+    /// assert_eq!(item, 0x1234);
+    /// assert_eq!(size, 2);
+    /// ```
+    fn decode_one_from_bytes(&mut self, buf: &[u8]) -> Result<(Self::Item, usize)> {
+        let size = track!(self.decode(buf, Eos::new(true)))?;
+        track_assert!(self.is_idle(), ErrorKind::UnexpectedEos; size, buf.len());
+        let item = track!(self.finish_decoding())?;
+        Ok((item, size))
+    }
+
+    /// Decodes items repeatedly until the whole part of the given bytes is consumed,
+    /// and returns the collected items.
+    ///
+    /// If the bytes end in the middle of an item, an `ErrorKind::UnexpectedEos` error will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U16beDecoder;
+    ///
+    /// let mut decoder = U16beDecoder::new();
+    /// let items: Vec<_> = decoder.decode_all_from_bytes(&[0x12, 0x34, 0x56, 0x78][..]).unwrap();
+    /// assert_eq!(items, [0x1234, 0x5678]);
+    ///
+    /// let result: bytecodec::Result<Vec<_>> = decoder.decode_all_from_bytes(&[0x12, 0x34, 0x56][..]);
+    /// assert!(result.is_err());
+    /// ```
+    fn decode_all_from_bytes<T>(&mut self, buf: &[u8]) -> Result<T>
+    where
+        T: Extend<Self::Item> + Default,
+    {
+        let mut items = T::default();
+        let mut offset = 0;
+        while offset < buf.len() {
+            let size = track!(self.decode(&buf[offset..], Eos::new(true)); offset)?;
+            track_assert_ne!(size, 0, ErrorKind::InconsistentState; offset, buf.len());
+            offset += size;
+            if self.is_idle() {
+                let item = track!(self.finish_decoding(); offset)?;
+                items.extend(std::iter::once(item));
+            } else {
+                track_assert_ne!(offset, buf.len(), ErrorKind::UnexpectedEos);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Creates a stream that yields the items decoded from the given asynchronous reader.
+    ///
+    /// The stream ends when the reader reaches EOS. See `DecodeStream` for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::DecodeExt;
+    /// use bytecodec::fixnum::U8Decoder;
+    ///
+    /// let reader: &[u8] = &[1, 2, 3];
+    /// let stream = U8Decoder::new().into_stream(reader);
+    /// ```
+    #[cfg(feature = "tokio-async")]
+    fn into_stream<R>(self, reader: R) -> crate::io_async::DecodeStream<Self, R>
+    where
+        R: tokio::io::AsyncRead,
+    {
+        crate::io_async::DecodeStream::new(self, reader)
+    }
+
+    /// Converts this decoder into a boxed trait object.
+    ///
+    /// This is useful for storing heterogeneous decoders of the same item type
+    /// (e.g., in a `Vec` or a dispatch table).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Decode, DecodeExt};
+    /// use bytecodec::fixnum::{U16beDecoder, U8Decoder};
+    ///
+    /// let mut decoders = vec![
+    ///     U8Decoder::new().boxed(),
+    ///     U16beDecoder::new().map(|n| n as u8).boxed(),
+    /// ];
+    /// assert_eq!(decoders[0].decode_from_bytes(&[1][..]).unwrap(), 1);
+    /// assert_eq!(decoders[1].decode_from_bytes(&[0, 2][..]).unwrap(), 2);
+    /// ```
+    fn boxed<'a>(self) -> Box<dyn Decode<Item = Self::Item> + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(self)
+    }
 }
 impl<T: Decode> DecodeExt for T {}
 
@@ -472,7 +1244,7 @@ pub trait TaggedDecode: Decode {
     ///   - Other errors has occurred
     fn start_decoding(&mut self, tag: Self::Tag) -> Result<()>;
 }
-impl<'a, D: ?Sized + TaggedDecode> TaggedDecode for &'a mut D {
+impl<D: ?Sized + TaggedDecode> TaggedDecode for &mut D {
     type Tag = D::Tag;
 
     fn start_decoding(&mut self, tag: Self::Tag) -> Result<()> {
@@ -507,7 +1279,7 @@ pub trait TryTaggedDecode: Decode {
     ///   - Other errors has occurred
     fn try_start_decoding(&mut self, tag: Self::Tag) -> Result<bool>;
 }
-impl<'a, D: ?Sized + TryTaggedDecode> TryTaggedDecode for &'a mut D {
+impl<D: ?Sized + TryTaggedDecode> TryTaggedDecode for &mut D {
     type Tag = D::Tag;
 
     fn try_start_decoding(&mut self, tag: Self::Tag) -> Result<bool> {
@@ -525,7 +1297,26 @@ impl<D: ?Sized + TryTaggedDecode> TryTaggedDecode for Box<D> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::fixnum::U16beDecoder;
+    use crate::fixnum::{U16beDecoder, U8Decoder};
+    use crate::io::IoDecodeExt;
+    use crate::tagged::StringTaggedDecoder;
+
+    #[test]
+    fn boxed_works() {
+        let mut decoders: Vec<Box<dyn Decode<Item = u8>>> = vec![
+            U8Decoder::new().boxed(),
+            U16beDecoder::new().map(|n| (n >> 8) as u8).boxed(),
+        ];
+
+        let mut input = &[1, 2, 3][..];
+        let mut items = Vec::new();
+        for decoder in &mut decoders {
+            let item = track_try_unwrap!(decoder.decode_exact(&mut input));
+            items.push(item);
+        }
+        assert_eq!(items, [1, 2]);
+        assert!(input.is_empty());
+    }
 
     #[test]
     fn decode_from_bytes_works() {
@@ -535,4 +1326,66 @@ mod test {
             0x1234
         );
     }
+
+    #[test]
+    fn decode_one_from_bytes_works() {
+        let mut decoder = U16beDecoder::new();
+        let input = [0x12, 0x34, 0x56, 0x78, 0x9a];
+
+        let (item, size) = track_try_unwrap!(decoder.decode_one_from_bytes(&input));
+        assert_eq!(item, 0x1234);
+        assert_eq!(size, 2);
+
+        let (item, size) = track_try_unwrap!(decoder.decode_one_from_bytes(&input[2..]));
+        assert_eq!(item, 0x5678);
+        assert_eq!(size, 2);
+
+        let error = decoder.decode_one_from_bytes(&input[4..]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+
+    #[test]
+    fn decode_all_from_bytes_works() {
+        let mut decoder = U8Decoder::new();
+        let items: Vec<_> = decoder.decode_all_from_bytes(b"abc").unwrap();
+        assert_eq!(items, b"abc");
+
+        let mut decoder = U16beDecoder::new();
+        let result: Result<Vec<_>> = decoder.decode_all_from_bytes(b"abc");
+        assert_eq!(
+            result.err().map(|e| *e.kind()),
+            Some(ErrorKind::UnexpectedEos)
+        );
+    }
+
+    #[test]
+    fn map_tag_works() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum Kind {
+            Byte,
+            Short,
+            Unknown,
+        }
+
+        let mut inner = StringTaggedDecoder::new();
+        inner.register("byte", U8Decoder::new().map(u16::from));
+        inner.register("short", U16beDecoder::new());
+        let mut decoder = inner.map_tag(|kind: Kind| match kind {
+            Kind::Byte => Ok("byte".to_owned()),
+            Kind::Short => Ok("short".to_owned()),
+            Kind::Unknown => Err("unknown kind"),
+        });
+
+        track_try_unwrap!(decoder.start_decoding(Kind::Byte));
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&[7][..])), 7);
+
+        track_try_unwrap!(decoder.start_decoding(Kind::Short));
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&[1, 2][..])),
+            0x0102
+        );
+
+        let error = decoder.start_decoding(Kind::Unknown).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
 }
@@ -1,5 +1,8 @@
+use crate::checksum::Checksum;
 use crate::combinator::{
-    Last, Length, MapErr, MapFrom, MaxBytes, Optional, PreEncode, Repeat, Slice, TryMapFrom,
+    BufferedAtomic, Checksummed, Crc32Header, Interleave, Last, Length, MapErr, MapFrom, MaxBytes,
+    Optional, PreEncode, RateLimited, Repeat, RepeatExact, Slice, Terminated, TryMapFrom,
+    WithLengthSuffix,
 };
 use crate::io::IoEncodeExt;
 use crate::tuple::TupleEncoder;
@@ -70,7 +73,7 @@ pub trait Encode {
         self.requiring_bytes() == ByteCount::Finite(0)
     }
 }
-impl<'a, E: ?Sized + Encode> Encode for &'a mut E {
+impl<E: ?Sized + Encode> Encode for &mut E {
     type Item = E::Item;
 
     fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
@@ -114,7 +117,7 @@ pub trait SizedEncode: Encode {
     /// Returns the exact number of bytes required to encode all the items remaining in the encoder.
     fn exact_requiring_bytes(&self) -> u64;
 }
-impl<'a, E: ?Sized + SizedEncode> SizedEncode for &'a mut E {
+impl<E: ?Sized + SizedEncode> SizedEncode for &mut E {
     fn exact_requiring_bytes(&self) -> u64 {
         (**self).exact_requiring_bytes()
     }
@@ -151,6 +154,33 @@ pub trait EncodeExt: Encode + Sized {
         Ok(this)
     }
 
+    /// Creates a new encoder instance that has the given borrowed item.
+    ///
+    /// This is a variant of `with_item` for encoders whose item type is a reference
+    /// (e.g., `BytesEncoder<&[u8]>`).
+    /// The item is encoded directly from the referenced data without being copied into an owned buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Encode, EncodeExt};
+    /// use bytecodec::bytes::BytesEncoder;
+    /// use bytecodec::io::IoEncodeExt;
+    ///
+    /// let data = vec![1, 2, 3];
+    /// let mut output = Vec::new();
+    /// let mut encoder = BytesEncoder::<&[u8]>::with_item_ref(&data[..]).unwrap();
+    /// encoder.encode_all(&mut output).unwrap();
+    /// assert_eq!(output, data);
+    /// ```
+    fn with_item_ref<'a, T>(item: &'a T) -> Result<Self>
+    where
+        T: ?Sized,
+        Self: Encode<Item = &'a T> + Default,
+    {
+        track!(Self::with_item(item))
+    }
+
     /// Creates an encoder for modifying encoding errors produced by `self`.
     ///
     /// # Examples
@@ -170,10 +200,10 @@ pub trait EncodeExt: Encode + Sized {
     /// UnexpectedEos (cause; assertion failed: `!eos.is_reached()`; \
     ///                buf.len()=0, size=0, self.offset=0, b.as_ref().len()=1)
     /// HISTORY:
-    ///   [0] at src/bytes.rs:53
+    ///   [0] at src/bytes.rs:59
     ///   [1] at src/fixnum.rs:116
-    ///   [2] at src/encode.rs:9 -- oops!
-    ///   [3] at src/encode.rs:10\n");
+    ///   [2] at src/encode.rs:10 -- oops!
+    ///   [3] at src/encode.rs:11\n");
     /// ```
     fn map_err<E, F>(self, f: F) -> MapErr<Self, E, F>
     where
@@ -346,6 +376,29 @@ pub trait EncodeExt: Encode + Sized {
         Repeat::new(self)
     }
 
+    /// Creates an encoder that repeats encoding of fixed-size `Self::Item`s.
+    ///
+    /// Unlike `repeat`, the resulting encoder implements `SizedEncode`.
+    /// See `RepeatExact` for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Encode, EncodeExt, SizedEncode};
+    /// use bytecodec::fixnum::U16beEncoder;
+    ///
+    /// let mut encoder = U16beEncoder::new().repeat_exact();
+    /// encoder.start_encoding(0..4).unwrap();
+    /// assert_eq!(encoder.exact_requiring_bytes(), 8);
+    /// ```
+    fn repeat_exact<I>(self) -> RepeatExact<Self, I>
+    where
+        Self: SizedEncode,
+        I: ExactSizeIterator<Item = Self::Item>,
+    {
+        RepeatExact::new(self)
+    }
+
     /// Creates an encoder that pre-encodes items when `start_encoding` method is called.
     ///
     /// Although the number of memory copies increases,
@@ -373,6 +426,106 @@ pub trait EncodeExt: Encode + Sized {
         PreEncode::new(self)
     }
 
+    /// Creates an encoder that appends the length of each encoded item as a suffix.
+    ///
+    /// The length is encoded by `suffix`.
+    /// Note that each item is pre-encoded into an intermediate buffer (like `pre_encode`)
+    /// for determining its length.
+    ///
+    /// The encoded items can be decoded by `combinator::LengthSuffixDecoder`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{EncodeExt, ErrorKind};
+    /// use bytecodec::bytes::Utf8Encoder;
+    /// use bytecodec::fixnum::U8Encoder;
+    /// use std::convert::TryFrom;
+    /// use trackable::error::ErrorKindExt;
+    ///
+    /// let mut encoder = Utf8Encoder::new().with_length_suffix(
+    ///     U8Encoder::new().try_map_from(|n| u8::try_from(n).map_err(|e| ErrorKind::InvalidInput.cause(e))),
+    /// );
+    /// let bytes = encoder.encode_into_bytes("foo".to_owned()).unwrap();
+    /// assert_eq!(bytes, b"foo\x03");
+    ///
+    /// // The length of the body must fit in a byte
+    /// assert!(encoder.encode_into_bytes("a".repeat(256)).is_err());
+    /// ```
+    fn with_length_suffix<S>(self, suffix: S) -> WithLengthSuffix<Self, S>
+    where
+        S: Encode<Item = u64>,
+    {
+        WithLengthSuffix::new(self, suffix)
+    }
+
+    /// Creates an encoder that pre-encodes each item into a packet which is never split.
+    ///
+    /// This is useful for datagram oriented protocols (e.g., UDP)
+    /// that require each item to be sent as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Encode, EncodeExt};
+    /// use bytecodec::bytes::Utf8Encoder;
+    ///
+    /// let mut encoder = Utf8Encoder::new().buffered_atomic();
+    ///
+    /// encoder.start_encoding("foo").unwrap();
+    /// assert_eq!(encoder.is_idle(), false);
+    /// assert_eq!(encoder.take_packet(), Some(b"foo".to_vec()));
+    ///
+    /// encoder.start_encoding("bar").unwrap();
+    /// assert_eq!(encoder.take_packet(), Some(b"bar".to_vec()));
+    /// assert_eq!(encoder.is_idle(), true);
+    /// ```
+    fn buffered_atomic(self) -> BufferedAtomic<Self> {
+        BufferedAtomic::new(self)
+    }
+
+    /// Creates an encoder that prefixes each item with the CRC-32 checksum of its encoded bytes.
+    ///
+    /// The whole body of an item is pre-encoded when `start_encoding` method is called.
+    /// See `Crc32Header` for the layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::EncodeExt;
+    /// use bytecodec::bytes::Utf8Encoder;
+    ///
+    /// let mut encoder = Utf8Encoder::new().crc32_header();
+    /// let bytes = encoder.encode_into_bytes("foo").unwrap();
+    /// assert_eq!(bytes, b"\x8c\x73\x65\x21foo");
+    /// ```
+    fn crc32_header(self) -> Crc32Header<Self> {
+        Crc32Header::new(self)
+    }
+
+    /// Creates an encoder that appends a checksum of the encoded bytes to each item.
+    ///
+    /// The checksum algorithm is specified by the type parameter `C`.
+    /// The encoded items can be decoded by `DecodeExt::checksum_verified`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::EncodeExt;
+    /// use bytecodec::bytes::Utf8Encoder;
+    /// use bytecodec::checksum::Xor8;
+    ///
+    /// let mut encoder = Utf8Encoder::new().checksummed::<Xor8>();
+    /// let bytes = encoder.encode_into_bytes("foo").unwrap();
+    /// assert_eq!(bytes, b"foo\x66");
+    /// ```
+    fn checksummed<C>(self) -> Checksummed<C, Self>
+    where
+        C: Checksum + Default,
+    {
+        Checksummed::new(self)
+    }
+
     /// Creates an encoder that makes it possible to slice the encoded byte sequence in arbitrary units.
     ///
     /// Slicing encoded byte sequences makes it easier to multiplex them into a single sequence.
@@ -409,11 +562,87 @@ pub trait EncodeExt: Encode + Sized {
         Slice::new(self)
     }
 
+    /// Creates an encoder that encodes the items of `self` and `other` alternately,
+    /// `quantum` bytes at a time.
+    ///
+    /// If one of the encoders finishes earlier, the rest of the other one is encoded continuously.
+    ///
+    /// # Panics
+    ///
+    /// If `quantum` is `0`, this method will panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::EncodeExt;
+    /// use bytecodec::bytes::Utf8Encoder;
+    ///
+    /// let mut encoder = Utf8Encoder::new().interleave(Utf8Encoder::new(), 2);
+    /// let bytes = encoder.encode_into_bytes(("foo", "barbaz")).unwrap();
+    /// assert_eq!(bytes, b"fobaorbaz");
+    /// ```
+    fn interleave<E: Encode>(self, other: E, quantum: usize) -> Interleave<Self, E> {
+        Interleave::new(self, other, quantum)
+    }
+
     /// Creates an encoder that cannot accept any more items except the given one.
     fn last(self, item: Self::Item) -> Last<Self> {
         Last::new(self, item)
     }
 
+    /// Creates an encoder that writes `sentinel` once at the end of the stream.
+    ///
+    /// The end of the stream is signaled by calling `Terminated::finish` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Encode, EncodeExt};
+    /// use bytecodec::bytes::Utf8Encoder;
+    /// use bytecodec::io::IoEncodeExt;
+    ///
+    /// let mut output = Vec::new();
+    /// let mut encoder = Utf8Encoder::new().terminated_by(0);
+    ///
+    /// encoder.start_encoding("foo").unwrap();
+    /// encoder.encode_all(&mut output).unwrap();
+    /// encoder.start_encoding("bar").unwrap();
+    /// encoder.finish();
+    /// encoder.encode_all(&mut output).unwrap();
+    /// assert_eq!(output, b"foobar\x00");
+    /// ```
+    fn terminated_by(self, sentinel: u8) -> Terminated<Self> {
+        Terminated::new(self, sentinel)
+    }
+
+    /// Creates an encoder that writes at most `max_bytes_per_encode` bytes in a single `encode` call.
+    ///
+    /// The rest of the encoded bytes are left for the subsequent calls,
+    /// even if the given buffer has enough space.
+    ///
+    /// # Panics
+    ///
+    /// If `max_bytes_per_encode` is `0`, this method will panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Encode, EncodeExt, Eos};
+    /// use bytecodec::bytes::Utf8Encoder;
+    ///
+    /// let mut encoder = Utf8Encoder::new().rate_limit(2);
+    /// encoder.start_encoding("foo").unwrap();
+    ///
+    /// let mut buf = [0; 8];
+    /// assert_eq!(encoder.encode(&mut buf, Eos::new(false)).unwrap(), 2);
+    /// assert_eq!(encoder.encode(&mut buf[2..], Eos::new(false)).unwrap(), 1);
+    /// assert_eq!(&buf[..3], b"foo");
+    /// assert!(encoder.is_idle());
+    /// ```
+    fn rate_limit(self, max_bytes_per_encode: usize) -> RateLimited<Self> {
+        RateLimited::new(self, max_bytes_per_encode)
+    }
+
     /// Encodes the given item and returns the resulting bytes.
     ///
     /// # Examples
@@ -426,24 +655,84 @@ pub trait EncodeExt: Encode + Sized {
     /// assert_eq!(encoder.encode_into_bytes(0x1234).unwrap(), [0x12, 0x34]);
     /// ```
     fn encode_into_bytes(&mut self, item: Self::Item) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        track!(self.encode_to_vec(item, &mut buf))?;
+        Ok(buf)
+    }
+
+    /// Encodes the given item and appends the resulting bytes to `out`.
+    ///
+    /// Unlike `encode_into_bytes`, this reuses the capacity of the given vector,
+    /// so it is suited to batching many items into a single output buffer.
+    ///
+    /// If an error occurs, `out` is restored to its original length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::EncodeExt;
+    /// use bytecodec::fixnum::U16beEncoder;
+    ///
+    /// let mut encoder = U16beEncoder::new();
+    /// let mut out = vec![0xFF];
+    /// encoder.encode_to_vec(0x1234, &mut out).unwrap();
+    /// assert_eq!(out, [0xFF, 0x12, 0x34]);
+    /// ```
+    fn encode_to_vec(&mut self, item: Self::Item, out: &mut Vec<u8>) -> Result<()> {
         track!(self.start_encoding(item))?;
 
-        match self.requiring_bytes() {
+        let offset = out.len();
+        let result = match self.requiring_bytes() {
             ByteCount::Finite(size) => {
-                track_assert!(size <= std::usize::MAX as u64, ErrorKind::Other; size);
+                track_assert!(size <= usize::MAX as u64, ErrorKind::Other; size);
 
-                let mut buf = vec![0; size as usize];
-                track!(self.encode(&mut buf, Eos::new(true)))?;
-                track_assert!(self.is_idle(), ErrorKind::InconsistentState);
-                Ok(buf)
-            }
-            ByteCount::Unknown => {
-                let mut buf = Vec::new();
-                track!(self.encode_all(&mut buf))?;
-                Ok(buf)
+                out.resize(offset + size as usize, 0);
+                let mut written = 0;
+                loop {
+                    // Some encoders (e.g., `RateLimited`) may write fewer bytes than the buffer has.
+                    match track!(self.encode(&mut out[offset + written..], Eos::new(true))) {
+                        Err(e) => break Err(e),
+                        Ok(0) => break Ok(()),
+                        Ok(n) => written += n,
+                    }
+                    if self.is_idle() {
+                        break Ok(());
+                    }
+                }
+                .and_then(|_| {
+                    track_assert!(self.is_idle(), ErrorKind::InconsistentState);
+                    Ok(())
+                })
             }
+            ByteCount::Unknown => track!(self.encode_all(&mut *out)),
             ByteCount::Infinite => track_panic!(ErrorKind::InvalidInput),
+        };
+        if result.is_err() {
+            out.truncate(offset);
         }
+        result
+    }
+
+    /// Converts this encoder into a boxed trait object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Encode, EncodeExt};
+    /// use bytecodec::fixnum::{U16beEncoder, U8Encoder};
+    ///
+    /// let mut encoders = vec![
+    ///     U8Encoder::new().boxed(),
+    ///     U16beEncoder::new().map_from(u16::from).boxed(),
+    /// ];
+    /// assert_eq!(encoders[0].encode_into_bytes(1).unwrap(), [1]);
+    /// assert_eq!(encoders[1].encode_into_bytes(2).unwrap(), [0, 2]);
+    /// ```
+    fn boxed<'a>(self) -> Box<dyn Encode<Item = Self::Item> + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(self)
     }
 }
 impl<T: Encode> EncodeExt for T {}
@@ -458,4 +747,14 @@ mod test {
         let mut encoder = U16beEncoder::new();
         assert_eq!(encoder.encode_into_bytes(0x1234).unwrap(), [0x12, 0x34]);
     }
+
+    #[test]
+    fn encode_to_vec_works() {
+        let mut encoder = U16beEncoder::new();
+        let mut out = Vec::with_capacity(6);
+        for n in [0x0102, 0x0304, 0x0506].iter() {
+            encoder.encode_to_vec(*n, &mut out).unwrap();
+        }
+        assert_eq!(out, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+    }
 }
@@ -19,6 +19,22 @@ impl Eos {
         Eos(n)
     }
 
+    /// Makes a new `Eos` instance from the number of remaining bytes in a stream.
+    ///
+    /// This is equivalent to `Eos::with_remaining_bytes(n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{ByteCount, Eos};
+    ///
+    /// assert_eq!(Eos::from_byte_count(ByteCount::Finite(0)), Eos::new(true));
+    /// assert_eq!(Eos::from_byte_count(ByteCount::Unknown), Eos::new(false));
+    /// ```
+    pub fn from_byte_count(n: ByteCount) -> Self {
+        Eos(n)
+    }
+
     /// Returns `true` if the target stream has reached to the end, otherwise `false`.
     pub fn is_reached(&self) -> bool {
         self.0 == ByteCount::Finite(0)
@@ -32,6 +48,13 @@ impl Eos {
     /// Returns a new `Eos` instance that has moved backward from
     /// the end of the target stream by the specified number of bytes.
     ///
+    /// If the number of remaining bytes is finite, `bytes` is added to it.
+    /// Otherwise (i.e., `ByteCount::Unknown` or `ByteCount::Infinite`), the instance is returned as it is.
+    ///
+    /// # Panics
+    ///
+    /// If the resulting number of remaining bytes overflows `u64`, this method will panic.
+    ///
     /// # Examples
     ///
     /// ```
@@ -47,9 +70,94 @@ impl Eos {
     /// ```
     pub fn back(&self, bytes: u64) -> Self {
         if let ByteCount::Finite(n) = self.0 {
-            Eos(ByteCount::Finite(n + bytes))
+            let n = n.checked_add(bytes).expect("Remaining bytes overflow");
+            Eos(ByteCount::Finite(n))
         } else {
             *self
         }
     }
+
+    /// Returns a new `Eos` instance that has moved forward toward
+    /// the end of the target stream by the specified number of bytes.
+    ///
+    /// If the number of remaining bytes is finite, `bytes` is subtracted from it
+    /// (saturating at `0`, i.e., the end of the stream).
+    /// Otherwise (i.e., `ByteCount::Unknown` or `ByteCount::Infinite`), the instance is returned as it is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{ByteCount, Eos};
+    ///
+    /// let eos = Eos::with_remaining_bytes(ByteCount::Finite(5));
+    /// assert_eq!(eos.forward(3).remaining_bytes(), ByteCount::Finite(2));
+    /// assert!(eos.forward(10).is_reached());
+    ///
+    /// let eos = Eos::new(false);
+    /// assert_eq!(eos.forward(3), eos);
+    /// ```
+    pub fn forward(&self, bytes: u64) -> Self {
+        if let ByteCount::Finite(n) = self.0 {
+            Eos(ByteCount::Finite(n.saturating_sub(bytes)))
+        } else {
+            *self
+        }
+    }
+}
+impl From<ByteCount> for Eos {
+    fn from(f: ByteCount) -> Self {
+        Eos::from_byte_count(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn eos_from_byte_count_works() {
+        let eos = Eos::from_byte_count(ByteCount::Finite(0));
+        assert!(eos.is_reached());
+        assert_eq!(eos, Eos::new(true));
+        assert_eq!(eos.remaining_bytes(), ByteCount::Finite(0));
+
+        let eos = Eos::from_byte_count(ByteCount::Finite(3));
+        assert!(!eos.is_reached());
+        assert_eq!(eos, Eos::new(true).back(3));
+        assert_ne!(eos, Eos::new(false));
+        assert_eq!(eos.remaining_bytes(), ByteCount::Finite(3));
+
+        let eos = Eos::from_byte_count(ByteCount::Unknown);
+        assert!(!eos.is_reached());
+        assert_eq!(eos, Eos::new(false));
+        assert_eq!(eos.remaining_bytes(), ByteCount::Unknown);
+
+        let eos = Eos::from(ByteCount::Infinite);
+        assert!(!eos.is_reached());
+        assert_ne!(eos, Eos::new(false));
+        assert_eq!(eos, Eos::with_remaining_bytes(ByteCount::Infinite));
+        assert_eq!(eos.remaining_bytes(), ByteCount::Infinite);
+    }
+
+    #[test]
+    fn eos_back_and_forward_works() {
+        let eos = Eos::with_remaining_bytes(ByteCount::Finite(3));
+        assert_eq!(eos.back(2).remaining_bytes(), ByteCount::Finite(5));
+        assert_eq!(eos.back(2).forward(2), eos);
+
+        assert!(!eos.forward(2).is_reached());
+        assert!(eos.forward(3).is_reached());
+        assert!(eos.forward(4).is_reached());
+
+        let eos = Eos::with_remaining_bytes(ByteCount::Infinite);
+        assert_eq!(eos.back(2), eos);
+        assert_eq!(eos.forward(2), eos);
+    }
+
+    #[test]
+    #[should_panic]
+    fn eos_back_overflow_panics() {
+        let eos = Eos::with_remaining_bytes(ByteCount::Finite(u64::MAX));
+        let _ = eos.back(1);
+    }
 }
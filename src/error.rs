@@ -4,6 +4,24 @@ use trackable::error::{Failure, TrackableError};
 /// This crate specific `Error` type.
 #[derive(Debug, Clone, TrackableError)]
 pub struct Error(TrackableError<ErrorKind>);
+impl Error {
+    /// Returns the kind of the original I/O error if this error has been converted from `std::io::Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Error, ErrorKind};
+    /// use std::io;
+    ///
+    /// let error = Error::from(io::Error::new(io::ErrorKind::Interrupted, "foo"));
+    /// assert_eq!(*error.kind(), ErrorKind::Other);
+    /// assert_eq!(error.io_error_kind(), Some(io::ErrorKind::Interrupted));
+    /// ```
+    pub fn io_error_kind(&self) -> Option<std::io::ErrorKind> {
+        self.concrete_cause::<std::io::Error>()
+            .map(std::io::Error::kind)
+    }
+}
 impl From<Failure> for Error {
     fn from(f: Failure) -> Self {
         ErrorKind::Other.takes_over(f).into()
@@ -55,3 +73,24 @@ pub enum ErrorKind {
     Other,
 }
 impl TrackableErrorKind for ErrorKind {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn io_error_kind_works() {
+        let error = Error::from(io::Error::new(io::ErrorKind::WouldBlock, "would block"));
+        let error = track!(error);
+        assert_eq!(*error.kind(), ErrorKind::Other);
+        assert_eq!(error.io_error_kind(), Some(io::ErrorKind::WouldBlock));
+
+        let error = Error::from(io::Error::new(io::ErrorKind::UnexpectedEof, "eof"));
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+        assert_eq!(error.io_error_kind(), Some(io::ErrorKind::UnexpectedEof));
+
+        let error: Error = ErrorKind::InvalidInput.into();
+        assert_eq!(error.io_error_kind(), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Encoders and decoders for fixed-point decimal numbers represented as scaled integers.
+//!
+//! A decimal value is stored as a signed integer `value` and interpreted as `value × 10^-scale`
+//! (e.g., `12345` at scale `2` represents `123.45`).
+use crate::bytes::{BytesEncoder, CopyableBytesDecoder};
+use crate::framing::Endianness;
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use std::fmt;
+
+/// Fixed-point decimal number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    /// Scaled integer value.
+    pub value: i64,
+
+    /// Number of decimal digits after the decimal point.
+    pub scale: u32,
+}
+impl Decimal {
+    /// Makes a new `Decimal` instance.
+    pub fn new(value: i64, scale: u32) -> Self {
+        Decimal { value, scale }
+    }
+}
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.value.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let sign = if self.value < 0 { "-" } else { "" };
+        if scale == 0 {
+            write!(f, "{}{}", sign, digits)
+        } else if digits.len() > scale {
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            write!(f, "{}{}.{}", sign, int, frac)
+        } else {
+            write!(f, "{}0.{:0>width$}", sign, digits, width = scale)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Word {
+    bytes: [u8; 8],
+    width: usize,
+}
+impl Word {
+    fn new(width: usize) -> Self {
+        assert!(
+            0 < width && width <= 8,
+            "Unsupported integer width: {}",
+            width
+        );
+        Word {
+            bytes: [0; 8],
+            width,
+        }
+    }
+}
+impl AsRef<[u8]> for Word {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.width]
+    }
+}
+impl AsMut<[u8]> for Word {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[..self.width]
+    }
+}
+
+/// Decoder which decodes `Decimal` values represented as signed scaled integers.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::fixed_point::{Decimal, ScaledIntDecoder};
+/// use bytecodec::framing::Endianness;
+///
+/// let mut decoder = ScaledIntDecoder::new(2, 4, Endianness::Big);
+/// let item = decoder.decode_from_bytes(&[0, 0, 0x30, 0x39][..]).unwrap();
+/// assert_eq!(item, Decimal::new(12345, 2));
+/// assert_eq!(item.to_string(), "123.45");
+/// ```
+#[derive(Debug)]
+pub struct ScaledIntDecoder {
+    bytes: CopyableBytesDecoder<Word>,
+    scale: u32,
+    endianness: Endianness,
+}
+impl ScaledIntDecoder {
+    /// Makes a new `ScaledIntDecoder` instance.
+    ///
+    /// `width` is the number of bytes of the underlying integer.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is not in the range of `1..=8`, this function will panic.
+    pub fn new(scale: u32, width: usize, endianness: Endianness) -> Self {
+        ScaledIntDecoder {
+            bytes: CopyableBytesDecoder::new(Word::new(width)),
+            scale,
+            endianness,
+        }
+    }
+
+    /// Returns the scale of the decoded values.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+}
+impl Decode for ScaledIntDecoder {
+    type Item = Decimal;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.bytes.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let word = track!(self.bytes.finish_decoding())?;
+        let shift = 64 - word.width as u32 * 8;
+        let n = self.endianness.read_uint(word.as_ref());
+        let value = ((n << shift) as i64) >> shift;
+        Ok(Decimal::new(value, self.scale))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.bytes.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.bytes.is_idle()
+    }
+}
+
+/// Encoder which encodes `Decimal` values as signed scaled integers.
+///
+/// The scale of an item must be equal to the one of the encoder,
+/// and the scaled value must fit in the integer width.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixed_point::{Decimal, ScaledIntEncoder};
+/// use bytecodec::framing::Endianness;
+///
+/// let mut encoder = ScaledIntEncoder::new(2, 4, Endianness::Big);
+/// let bytes = encoder.encode_into_bytes(Decimal::new(12345, 2)).unwrap();
+/// assert_eq!(bytes, [0, 0, 0x30, 0x39]);
+///
+/// assert!(encoder.encode_into_bytes(Decimal::new(12345, 3)).is_err());
+/// ```
+#[derive(Debug)]
+pub struct ScaledIntEncoder {
+    bytes: BytesEncoder<Word>,
+    word: Word,
+    scale: u32,
+    endianness: Endianness,
+}
+impl ScaledIntEncoder {
+    /// Makes a new `ScaledIntEncoder` instance.
+    ///
+    /// `width` is the number of bytes of the underlying integer.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is not in the range of `1..=8`, this function will panic.
+    pub fn new(scale: u32, width: usize, endianness: Endianness) -> Self {
+        ScaledIntEncoder {
+            bytes: BytesEncoder::new(),
+            word: Word::new(width),
+            scale,
+            endianness,
+        }
+    }
+
+    /// Returns the scale of the encoded values.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+}
+impl Encode for ScaledIntEncoder {
+    type Item = Decimal;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.bytes.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert_eq!(item.scale, self.scale, ErrorKind::InvalidInput);
+
+        let bits = self.word.width as u32 * 8;
+        let min = i64::MIN >> (64 - bits);
+        let max = i64::MAX >> (64 - bits);
+        track_assert!(min <= item.value && item.value <= max, ErrorKind::InvalidInput;
+                      item.value, self.word.width);
+
+        let mut word = self.word;
+        let mask = u64::MAX >> (64 - bits);
+        self.endianness
+            .write_uint(word.as_mut(), item.value as u64 & mask);
+        track!(self.bytes.start_encoding(word))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.bytes.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.bytes.is_idle()
+    }
+}
+impl SizedEncode for ScaledIntEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.bytes.exact_requiring_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn scaled_int_works() {
+        let mut encoder = ScaledIntEncoder::new(2, 3, Endianness::Little);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(Decimal::new(12345, 2)));
+        assert_eq!(bytes, [0x39, 0x30, 0]);
+
+        let mut decoder = ScaledIntDecoder::new(2, 3, Endianness::Little);
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&bytes));
+        assert_eq!(item, Decimal::new(12345, 2));
+        assert_eq!(item.to_string(), "123.45");
+
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(Decimal::new(-5, 2)));
+        let item = track_try_unwrap!(decoder.decode_from_bytes(&bytes));
+        assert_eq!(item.to_string(), "-0.05");
+    }
+
+    #[test]
+    fn scaled_int_encoder_rejects_out_of_range_values() {
+        let mut encoder = ScaledIntEncoder::new(0, 1, Endianness::Big);
+        assert!(encoder.encode_into_bytes(Decimal::new(127, 0)).is_ok());
+        assert!(encoder.encode_into_bytes(Decimal::new(-128, 0)).is_ok());
+
+        let error = encoder
+            .encode_into_bytes(Decimal::new(128, 0))
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -1,7 +1,7 @@
 //! Encoders and decoders for numbers which have fixed length binary representation.
 use crate::bytes::{BytesEncoder, CopyableBytesDecoder};
 use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
-use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use byteorder::{BigEndian, ByteOrder, LittleEndian, NativeEndian};
 
 macro_rules! impl_decode {
     ($ty:ty, $item:ty) => {
@@ -1578,6 +1578,462 @@ impl F64leEncoder {
 }
 impl_encode!(F64leEncoder, f64);
 
+/// Decoder which decodes `u16` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::U16neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = U16neDecoder::new();
+/// let item = decoder.decode_exact(0x0102u16.to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, 0x0102u16);
+/// ```
+#[derive(Debug, Default)]
+pub struct U16neDecoder(CopyableBytesDecoder<[u8; 2]>);
+impl U16neDecoder {
+    /// Makes a new `U16neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 2]) -> u16 {
+        NativeEndian::read_u16(&b)
+    }
+}
+impl_decode!(U16neDecoder, u16);
+
+/// Encoder which encodes `u16` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::U16neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = U16neEncoder::with_item(0x0102u16).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, 0x0102u16.to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct U16neEncoder(BytesEncoder<[u8; 2]>);
+impl U16neEncoder {
+    /// Makes a new `U16neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: u16, b: &mut [u8; 2]) -> Result<()> {
+        NativeEndian::write_u16(b, n);
+        Ok(())
+    }
+}
+impl_encode!(U16neEncoder, u16);
+
+/// Decoder which decodes `i16` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::I16neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = I16neDecoder::new();
+/// let item = decoder.decode_exact((-2i16).to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, -2i16);
+/// ```
+#[derive(Debug, Default)]
+pub struct I16neDecoder(CopyableBytesDecoder<[u8; 2]>);
+impl I16neDecoder {
+    /// Makes a new `I16neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 2]) -> i16 {
+        NativeEndian::read_i16(&b)
+    }
+}
+impl_decode!(I16neDecoder, i16);
+
+/// Encoder which encodes `i16` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::I16neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = I16neEncoder::with_item(-2i16).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, (-2i16).to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct I16neEncoder(BytesEncoder<[u8; 2]>);
+impl I16neEncoder {
+    /// Makes a new `I16neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: i16, b: &mut [u8; 2]) -> Result<()> {
+        NativeEndian::write_i16(b, n);
+        Ok(())
+    }
+}
+impl_encode!(I16neEncoder, i16);
+
+/// Decoder which decodes `u32` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::U32neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = U32neDecoder::new();
+/// let item = decoder.decode_exact(0x0102_0304u32.to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, 0x0102_0304u32);
+/// ```
+#[derive(Debug, Default)]
+pub struct U32neDecoder(CopyableBytesDecoder<[u8; 4]>);
+impl U32neDecoder {
+    /// Makes a new `U32neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 4]) -> u32 {
+        NativeEndian::read_u32(&b)
+    }
+}
+impl_decode!(U32neDecoder, u32);
+
+/// Encoder which encodes `u32` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::U32neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = U32neEncoder::with_item(0x0102_0304u32).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, 0x0102_0304u32.to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct U32neEncoder(BytesEncoder<[u8; 4]>);
+impl U32neEncoder {
+    /// Makes a new `U32neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: u32, b: &mut [u8; 4]) -> Result<()> {
+        NativeEndian::write_u32(b, n);
+        Ok(())
+    }
+}
+impl_encode!(U32neEncoder, u32);
+
+/// Decoder which decodes `i32` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::I32neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = I32neDecoder::new();
+/// let item = decoder.decode_exact((-2i32).to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, -2i32);
+/// ```
+#[derive(Debug, Default)]
+pub struct I32neDecoder(CopyableBytesDecoder<[u8; 4]>);
+impl I32neDecoder {
+    /// Makes a new `I32neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 4]) -> i32 {
+        NativeEndian::read_i32(&b)
+    }
+}
+impl_decode!(I32neDecoder, i32);
+
+/// Encoder which encodes `i32` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::I32neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = I32neEncoder::with_item(-2i32).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, (-2i32).to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct I32neEncoder(BytesEncoder<[u8; 4]>);
+impl I32neEncoder {
+    /// Makes a new `I32neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: i32, b: &mut [u8; 4]) -> Result<()> {
+        NativeEndian::write_i32(b, n);
+        Ok(())
+    }
+}
+impl_encode!(I32neEncoder, i32);
+
+/// Decoder which decodes `u64` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::U64neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = U64neDecoder::new();
+/// let item = decoder.decode_exact(0x0102_0304_0506_0708u64.to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, 0x0102_0304_0506_0708u64);
+/// ```
+#[derive(Debug, Default)]
+pub struct U64neDecoder(CopyableBytesDecoder<[u8; 8]>);
+impl U64neDecoder {
+    /// Makes a new `U64neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 8]) -> u64 {
+        NativeEndian::read_u64(&b)
+    }
+}
+impl_decode!(U64neDecoder, u64);
+
+/// Encoder which encodes `u64` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::U64neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = U64neEncoder::with_item(0x0102_0304_0506_0708u64).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, 0x0102_0304_0506_0708u64.to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct U64neEncoder(BytesEncoder<[u8; 8]>);
+impl U64neEncoder {
+    /// Makes a new `U64neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: u64, b: &mut [u8; 8]) -> Result<()> {
+        NativeEndian::write_u64(b, n);
+        Ok(())
+    }
+}
+impl_encode!(U64neEncoder, u64);
+
+/// Decoder which decodes `i64` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::I64neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = I64neDecoder::new();
+/// let item = decoder.decode_exact((-2i64).to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, -2i64);
+/// ```
+#[derive(Debug, Default)]
+pub struct I64neDecoder(CopyableBytesDecoder<[u8; 8]>);
+impl I64neDecoder {
+    /// Makes a new `I64neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 8]) -> i64 {
+        NativeEndian::read_i64(&b)
+    }
+}
+impl_decode!(I64neDecoder, i64);
+
+/// Encoder which encodes `i64` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::I64neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = I64neEncoder::with_item(-2i64).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, (-2i64).to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct I64neEncoder(BytesEncoder<[u8; 8]>);
+impl I64neEncoder {
+    /// Makes a new `I64neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: i64, b: &mut [u8; 8]) -> Result<()> {
+        NativeEndian::write_i64(b, n);
+        Ok(())
+    }
+}
+impl_encode!(I64neEncoder, i64);
+
+/// Decoder which decodes `f32` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::F32neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = F32neDecoder::new();
+/// let item = decoder.decode_exact(123.4_f32.to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, 123.4_f32);
+/// ```
+#[derive(Debug, Default)]
+pub struct F32neDecoder(CopyableBytesDecoder<[u8; 4]>);
+impl F32neDecoder {
+    /// Makes a new `F32neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 4]) -> f32 {
+        NativeEndian::read_f32(&b)
+    }
+}
+impl_decode!(F32neDecoder, f32);
+
+/// Encoder which encodes `f32` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::F32neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = F32neEncoder::with_item(123.4_f32).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, 123.4_f32.to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct F32neEncoder(BytesEncoder<[u8; 4]>);
+impl F32neEncoder {
+    /// Makes a new `F32neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: f32, b: &mut [u8; 4]) -> Result<()> {
+        NativeEndian::write_f32(b, n);
+        Ok(())
+    }
+}
+impl_encode!(F32neEncoder, f32);
+
+/// Decoder which decodes `f64` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Decode;
+/// use bytecodec::fixnum::F64neDecoder;
+/// use bytecodec::io::IoDecodeExt;
+///
+/// let mut decoder = F64neDecoder::new();
+/// let item = decoder.decode_exact(123.456_f64.to_ne_bytes().as_ref()).unwrap();
+/// assert_eq!(item, 123.456_f64);
+/// ```
+#[derive(Debug, Default)]
+pub struct F64neDecoder(CopyableBytesDecoder<[u8; 8]>);
+impl F64neDecoder {
+    /// Makes a new `F64neDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 8]) -> f64 {
+        NativeEndian::read_f64(&b)
+    }
+}
+impl_decode!(F64neDecoder, f64);
+
+/// Encoder which encodes `f64` values by native-endian byte order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::F64neEncoder;
+/// use bytecodec::io::IoEncodeExt;
+///
+/// let mut output = Vec::new();
+/// let mut encoder = F64neEncoder::with_item(123.456_f64).unwrap();
+/// encoder.encode_all(&mut output).unwrap();
+/// assert_eq!(output, 123.456_f64.to_ne_bytes());
+/// ```
+#[derive(Debug, Default)]
+pub struct F64neEncoder(BytesEncoder<[u8; 8]>);
+impl F64neEncoder {
+    /// Makes a new `F64neEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn encode_item(n: f64, b: &mut [u8; 8]) -> Result<()> {
+        NativeEndian::write_f64(b, n);
+        Ok(())
+    }
+}
+impl_encode!(F64neEncoder, f64);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1697,4 +2153,41 @@ mod test {
             [119, 190, 159, 26, 47, 221, 94, 192]
         );
     }
+
+    #[test]
+    fn native_endian_fixnum_works() {
+        assert_encode_decode!(U16neEncoder, U16neDecoder, 0x0102, 0x0102u16.to_ne_bytes());
+        assert_encode_decode!(I16neEncoder, I16neDecoder, -2, (-2i16).to_ne_bytes());
+        assert_encode_decode!(
+            U32neEncoder,
+            U32neDecoder,
+            0x0102_0304,
+            0x0102_0304u32.to_ne_bytes()
+        );
+        assert_encode_decode!(I32neEncoder, I32neDecoder, -2, (-2i32).to_ne_bytes());
+        assert_encode_decode!(
+            U64neEncoder,
+            U64neDecoder,
+            0x0102_0304_0506_0708,
+            0x0102_0304_0506_0708u64.to_ne_bytes()
+        );
+        assert_encode_decode!(I64neEncoder, I64neDecoder, -2, (-2i64).to_ne_bytes());
+        assert_encode_decode!(
+            F32neEncoder,
+            F32neDecoder,
+            -123.4,
+            (-123.4f32).to_ne_bytes()
+        );
+        assert_encode_decode!(
+            F64neEncoder,
+            F64neDecoder,
+            -123.456,
+            (-123.456f64).to_ne_bytes()
+        );
+
+        #[cfg(target_endian = "little")]
+        assert_encode_decode!(U16neEncoder, U16neDecoder, 0x0102, [0x02, 0x01]);
+        #[cfg(target_endian = "big")]
+        assert_encode_decode!(U16neEncoder, U16neDecoder, 0x0102, [0x01, 0x02]);
+    }
 }
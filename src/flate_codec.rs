@@ -0,0 +1,295 @@
+//! `#[cfg(feature = "flate_codec")]` Encoder and decoder that compress/decompress byte streams using [flate2].
+//!
+//! Each item is compressed as an independent raw deflate stream.
+//!
+//! [flate2]: https://crates.io/crates/flate2
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result};
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+use trackable::error::ErrorKindExt;
+
+const BUF_SIZE: usize = 4096;
+
+/// Encoder that compresses the bytes produced by the inner encoder.
+///
+/// The deflate stream of an item is finished (i.e., its trailer is written)
+/// when the inner encoder completes encoding the item.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, EncodeExt};
+/// use bytecodec::bytes::{Utf8Decoder, Utf8Encoder};
+/// use bytecodec::flate_codec::{DeflateDecoder, DeflateEncoder};
+///
+/// let mut encoder = DeflateEncoder::new(Utf8Encoder::new());
+/// let bytes = encoder.encode_into_bytes("foo".repeat(100)).unwrap();
+/// assert!(bytes.len() < 300);
+///
+/// let mut decoder = DeflateDecoder::new(Utf8Decoder::new());
+/// assert_eq!(decoder.decode_from_bytes(&bytes).unwrap(), "foo".repeat(100));
+/// ```
+#[derive(Debug)]
+pub struct DeflateEncoder<E> {
+    inner: E,
+    compress: Compress,
+    plain: Vec<u8>,
+    plain_head: usize,
+    in_progress: bool,
+}
+impl<E> DeflateEncoder<E> {
+    /// Makes a new `DeflateEncoder` instance with the default compression level.
+    pub fn new(inner: E) -> Self {
+        Self::with_level(inner, Compression::default().level())
+    }
+
+    /// Makes a new `DeflateEncoder` instance with the given compression level (`0..=9`).
+    pub fn with_level(inner: E, level: u32) -> Self {
+        DeflateEncoder {
+            inner,
+            compress: Compress::new(Compression::new(level), false),
+            plain: Vec::new(),
+            plain_head: 0,
+            in_progress: false,
+        }
+    }
+
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+impl<E: Encode> Encode for DeflateEncoder<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        while self.in_progress && offset < buf.len() {
+            if self.plain_head == self.plain.len() && !self.inner.is_idle() {
+                self.plain.resize(BUF_SIZE, 0);
+                let size = track!(self.inner.encode(&mut self.plain, Eos::new(false)))?;
+                self.plain.truncate(size);
+                self.plain_head = 0;
+            }
+
+            let flush = if self.plain_head == self.plain.len() && self.inner.is_idle() {
+                FlushCompress::Finish
+            } else {
+                FlushCompress::None
+            };
+            let total_in = self.compress.total_in();
+            let total_out = self.compress.total_out();
+            let status = track!(self
+                .compress
+                .compress(&self.plain[self.plain_head..], &mut buf[offset..], flush)
+                .map_err(|e| ErrorKind::Other.cause(e)))?;
+            let consumed = (self.compress.total_in() - total_in) as usize;
+            let produced = (self.compress.total_out() - total_out) as usize;
+            self.plain_head += consumed;
+            offset += produced;
+
+            if status == Status::StreamEnd {
+                self.compress.reset();
+                self.in_progress = false;
+            } else if consumed == 0 && produced == 0 {
+                break;
+            }
+        }
+        if self.in_progress {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        }
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        track!(self.inner.start_encoding(item))?;
+        self.plain.clear();
+        self.plain_head = 0;
+        self.in_progress = true;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.in_progress {
+            ByteCount::Unknown
+        } else {
+            ByteCount::Finite(0)
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.in_progress
+    }
+}
+
+/// Decoder that decompresses the input bytes and passes them to the inner decoder.
+///
+/// When the deflate stream of an item ends, the inner decoder will be notified of EOS.
+#[derive(Debug)]
+pub struct DeflateDecoder<D> {
+    inner: D,
+    decompress: Decompress,
+    plain: Vec<u8>,
+    plain_head: usize,
+    stream_end: bool,
+}
+impl<D> DeflateDecoder<D> {
+    /// Makes a new `DeflateDecoder` instance.
+    pub fn new(inner: D) -> Self {
+        DeflateDecoder {
+            inner,
+            decompress: Decompress::new(false),
+            plain: Vec::new(),
+            plain_head: 0,
+            stream_end: false,
+        }
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for DeflateDecoder<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        loop {
+            let has_plain = self.plain_head < self.plain.len();
+            if (has_plain || self.stream_end) && !self.inner.is_idle() {
+                let inner_eos = Eos::new(self.stream_end);
+                let size = track!(self.inner.decode(&self.plain[self.plain_head..], inner_eos))?;
+                self.plain_head += size;
+            }
+            if self.plain_head < self.plain.len() {
+                track_assert!(
+                    !self.inner.is_idle(),
+                    ErrorKind::InvalidInput,
+                    "Trailing bytes after an item"
+                );
+                break;
+            }
+            if self.stream_end {
+                break;
+            }
+
+            self.plain.resize(BUF_SIZE, 0);
+            let total_in = self.decompress.total_in();
+            let total_out = self.decompress.total_out();
+            let status = track!(self
+                .decompress
+                .decompress(&buf[offset..], &mut self.plain, FlushDecompress::None)
+                .map_err(|e| ErrorKind::InvalidInput.cause(e)))?;
+            let consumed = (self.decompress.total_in() - total_in) as usize;
+            let produced = (self.decompress.total_out() - total_out) as usize;
+            self.plain.truncate(produced);
+            self.plain_head = 0;
+            offset += consumed;
+
+            if status == Status::StreamEnd {
+                self.stream_end = true;
+            } else if consumed == 0 && produced == 0 {
+                break;
+            }
+        }
+        if !self.stream_end && offset == buf.len() {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.stream_end, ErrorKind::IncompleteDecoding);
+        let item = track!(self.inner.finish_decoding())?;
+        self.decompress.reset(false);
+        self.plain.clear();
+        self.plain_head = 0;
+        self.stream_end = false;
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.is_idle() {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.stream_end && self.inner.is_idle()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bytes::{Utf8Decoder, Utf8Encoder};
+    use crate::io::{IoDecodeExt, IoEncodeExt};
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn deflate_works() {
+        let text = (0..1000).map(|i| i.to_string()).collect::<String>();
+
+        let mut encoder = DeflateEncoder::new(Utf8Encoder::new());
+        let mut bytes = Vec::new();
+        for item in &[text.clone(), "bar".to_owned()] {
+            track_try_unwrap!(encoder.start_encoding(item.clone()));
+            track_try_unwrap!(encoder.encode_all(&mut bytes));
+        }
+        assert!(bytes.len() < text.len());
+
+        let mut decoder = DeflateDecoder::new(Utf8Decoder::new());
+        let mut input = &bytes[..];
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), text);
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), "bar");
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn deflate_decoder_rejects_truncated_stream() {
+        let mut encoder = DeflateEncoder::new(Utf8Encoder::new());
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("foobar".to_owned()));
+
+        let mut decoder = DeflateDecoder::new(Utf8Decoder::new());
+        let error = decoder
+            .decode(&bytes[..bytes.len() - 1], Eos::new(true))
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+
+    #[test]
+    fn deflate_decoder_drains_buffered_output() {
+        let text = "a".repeat(1024 * 1024);
+
+        let mut encoder = DeflateEncoder::new(Utf8Encoder::new());
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(text.clone()));
+        assert!(bytes.len() < BUF_SIZE);
+
+        let mut decoder = DeflateDecoder::new(Utf8Decoder::new());
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), text);
+    }
+}
@@ -0,0 +1,929 @@
+//! Encoders and decoders for checksummed frames.
+//!
+//! A frame has the following layout:
+//!
+//! ```text
+//! [magic][length][body][checksum]
+//! ```
+//!
+//! - `magic`: fixed bytes identifying the beginning of a frame (may be empty)
+//! - `length`: the number of bytes of `body` (the width and byte order are configurable)
+//! - `body`: the bytes produced by the inner encoder (or consumed by the inner decoder)
+//! - `checksum`: optional CRC-32 of `body` (4 bytes, in the same byte order as `length`)
+use crate::checksum::{Checksum, Crc32};
+use crate::combinator::Length;
+use crate::{ByteCount, Decode, DecodeExt, Encode, Eos, ErrorKind, Result, SizedEncode};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use std::cmp;
+use std::fmt;
+use std::mem;
+
+const CHECKSUM_SIZE: usize = 4;
+
+/// Byte order of numeric fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum Endianness {
+    Big,
+    Little,
+}
+impl Endianness {
+    pub(crate) fn read_uint(self, buf: &[u8]) -> u64 {
+        match self {
+            Endianness::Big => BigEndian::read_uint(buf, buf.len()),
+            Endianness::Little => LittleEndian::read_uint(buf, buf.len()),
+        }
+    }
+
+    pub(crate) fn write_uint(self, buf: &mut [u8], n: u64) {
+        let width = buf.len();
+        match self {
+            Endianness::Big => BigEndian::write_uint(buf, n, width),
+            Endianness::Little => LittleEndian::write_uint(buf, n, width),
+        }
+    }
+}
+
+/// Builder of `FrameEncoder` and `FrameDecoder`.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, EncodeExt};
+/// use bytecodec::bytes::{Utf8Decoder, Utf8Encoder};
+/// use bytecodec::framing::{Endianness, Frame};
+///
+/// let frame = Frame::new()
+///     .magic(*b"BC")
+///     .length_field(2, Endianness::Big)
+///     .checksum(true);
+///
+/// let mut encoder = frame.clone().encoder(Utf8Encoder::new());
+/// let bytes = encoder.encode_into_bytes("foo".to_owned()).unwrap();
+/// assert_eq!(&bytes[..7], b"BC\x00\x03foo");
+/// assert_eq!(bytes.len(), 11);
+///
+/// let mut decoder = frame.decoder(Utf8Decoder::new());
+/// assert_eq!(decoder.decode_from_bytes(&bytes).unwrap(), "foo");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Frame {
+    magic: Vec<u8>,
+    length_width: usize,
+    endianness: Endianness,
+    checksum: bool,
+}
+impl Frame {
+    /// Makes a new `Frame` instance.
+    ///
+    /// The default layout has no magic, a 4-byte big-endian length field and no checksum.
+    pub fn new() -> Self {
+        Frame {
+            magic: Vec::new(),
+            length_width: 4,
+            endianness: Endianness::Big,
+            checksum: false,
+        }
+    }
+
+    /// Sets the magic bytes prefixed to each frame.
+    pub fn magic<B: Into<Vec<u8>>>(mut self, magic: B) -> Self {
+        self.magic = magic.into();
+        self
+    }
+
+    /// Sets the width (in bytes) and the byte order of the length field.
+    ///
+    /// The byte order is also applied to the checksum field.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is not in the range of `1..=8`, this method will panic.
+    pub fn length_field(mut self, width: usize, endianness: Endianness) -> Self {
+        assert!((1..=8).contains(&width), "width={}", width);
+        self.length_width = width;
+        self.endianness = endianness;
+        self
+    }
+
+    /// Sets whether each frame has a trailing CRC-32 checksum of the body.
+    pub fn checksum(mut self, enabled: bool) -> Self {
+        self.checksum = enabled;
+        self
+    }
+
+    /// Makes a new `FrameEncoder` that uses `body` for encoding the frame bodies.
+    pub fn encoder<E: SizedEncode>(self, body: E) -> FrameEncoder<E> {
+        FrameEncoder {
+            frame: self,
+            header: Vec::new(),
+            header_offset: 0,
+            body,
+            crc: Crc32::new(),
+            trailer: [0; CHECKSUM_SIZE],
+            trailer_offset: CHECKSUM_SIZE,
+            trailer_pending: false,
+        }
+    }
+
+    /// Makes a new `FrameDecoder` that uses `body` for decoding the frame bodies.
+    pub fn decoder<D: Decode>(self, body: D) -> FrameDecoder<D> {
+        let header_size = self.header_size();
+        FrameDecoder {
+            frame: self,
+            header: Vec::with_capacity(header_size),
+            body: body.length(0),
+            crc: Crc32::new(),
+            item: None,
+            trailer: Vec::with_capacity(CHECKSUM_SIZE),
+        }
+    }
+
+    fn header_size(&self) -> usize {
+        self.magic.len() + self.length_width
+    }
+
+    fn trailer_size(&self) -> usize {
+        if self.checksum {
+            CHECKSUM_SIZE
+        } else {
+            0
+        }
+    }
+
+    fn max_length(&self) -> u64 {
+        if self.length_width == 8 {
+            u64::MAX
+        } else {
+            (1 << (self.length_width * 8)) - 1
+        }
+    }
+}
+impl Default for Frame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encoder for frames.
+///
+/// This is created by calling `Frame::encoder` method.
+#[derive(Debug)]
+pub struct FrameEncoder<E> {
+    frame: Frame,
+    header: Vec<u8>,
+    header_offset: usize,
+    body: E,
+    crc: Crc32,
+    trailer: [u8; CHECKSUM_SIZE],
+    trailer_offset: usize,
+    trailer_pending: bool,
+}
+impl<E> FrameEncoder<E> {
+    /// Returns the layout of the frames.
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// Returns a reference to the inner body encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.body
+    }
+
+    /// Returns a mutable reference to the inner body encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.body
+    }
+
+    /// Takes ownership of this instance and returns the inner body encoder.
+    pub fn into_inner(self) -> E {
+        self.body
+    }
+
+    fn header_remaining(&self) -> usize {
+        self.header.len() - self.header_offset
+    }
+
+    fn trailer_remaining(&self) -> usize {
+        if self.trailer_pending {
+            CHECKSUM_SIZE
+        } else {
+            CHECKSUM_SIZE - self.trailer_offset
+        }
+    }
+}
+impl<E: SizedEncode> Encode for FrameEncoder<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.header_remaining() != 0 {
+            let size = cmp::min(buf.len(), self.header_remaining());
+            buf[..size].copy_from_slice(&self.header[self.header_offset..][..size]);
+            self.header_offset += size;
+            offset += size;
+            if self.header_remaining() != 0 {
+                track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+                return Ok(offset);
+            }
+        }
+
+        if !self.body.is_idle() {
+            let size = track!(self.body.encode(&mut buf[offset..], eos))?;
+            self.crc.update(&buf[offset..][..size]);
+            offset += size;
+            if !self.body.is_idle() {
+                return Ok(offset);
+            }
+        }
+
+        if self.trailer_pending {
+            let crc = self.crc.value();
+            self.frame
+                .endianness
+                .write_uint(&mut self.trailer, u64::from(crc));
+            self.trailer_offset = 0;
+            self.trailer_pending = false;
+        }
+        if self.trailer_remaining() != 0 {
+            let size = cmp::min(buf.len() - offset, self.trailer_remaining());
+            buf[offset..][..size].copy_from_slice(&self.trailer[self.trailer_offset..][..size]);
+            self.trailer_offset += size;
+            offset += size;
+            if self.trailer_remaining() != 0 {
+                track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+            }
+        }
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        track!(self.body.start_encoding(item))?;
+
+        let length = self.body.exact_requiring_bytes();
+        if length > self.frame.max_length() {
+            // Discards the encoded body so that this encoder can be reused
+            let mut scratch = [0; 1024];
+            while !self.body.is_idle() {
+                if track!(self.body.encode(&mut scratch, Eos::new(false)))? == 0 {
+                    break;
+                }
+            }
+            track_panic!(ErrorKind::InvalidInput; length, self.frame.length_width);
+        }
+
+        self.header.clear();
+        self.header.extend_from_slice(&self.frame.magic);
+        self.header.resize(self.frame.header_size(), 0);
+        let width = self.frame.length_width;
+        let header_size = self.header.len();
+        self.frame
+            .endianness
+            .write_uint(&mut self.header[header_size - width..], length);
+        self.header_offset = 0;
+
+        self.crc = Crc32::new();
+        self.trailer_pending = self.frame.checksum;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.exact_requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.header_remaining() == 0 && self.body.is_idle() && self.trailer_remaining() == 0
+    }
+}
+impl<E: SizedEncode> SizedEncode for FrameEncoder<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.header_remaining() as u64
+            + self.body.exact_requiring_bytes()
+            + self.trailer_remaining() as u64
+    }
+}
+
+/// Decoder for frames.
+///
+/// This is created by calling `Frame::decoder` method.
+#[derive(Debug)]
+pub struct FrameDecoder<D: Decode> {
+    frame: Frame,
+    header: Vec<u8>,
+    body: Length<D>,
+    crc: Crc32,
+    item: Option<D::Item>,
+    trailer: Vec<u8>,
+}
+impl<D: Decode> FrameDecoder<D> {
+    /// Returns the layout of the frames.
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// Returns a reference to the inner body decoder.
+    pub fn inner_ref(&self) -> &D {
+        self.body.inner_ref()
+    }
+
+    /// Returns a mutable reference to the inner body decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        self.body.inner_mut()
+    }
+
+    /// Takes ownership of this instance and returns the inner body decoder.
+    pub fn into_inner(self) -> D {
+        self.body.into_inner()
+    }
+
+    fn header_remaining(&self) -> usize {
+        self.frame.header_size() - self.header.len()
+    }
+
+    fn trailer_remaining(&self) -> usize {
+        self.frame.trailer_size() - self.trailer.len()
+    }
+
+    fn decode_header(&mut self) -> Result<()> {
+        let magic_size = self.frame.magic.len();
+        if self.header[..magic_size] != self.frame.magic[..] {
+            let header = mem::take(&mut self.header);
+            track_panic!(ErrorKind::InvalidInput, "Unexpected magic bytes"; header);
+        }
+        let length = self.frame.endianness.read_uint(&self.header[magic_size..]);
+        track!(self.body.set_expected_bytes(length))?;
+        self.crc = Crc32::new();
+        Ok(())
+    }
+
+    fn verify_checksum(&mut self) -> Result<()> {
+        let expected = self.frame.endianness.read_uint(&self.trailer);
+        let actual = u64::from(self.crc.value());
+        if actual != expected {
+            // Discards the unverified item (the body decoder has already finished it)
+            self.header.clear();
+            self.trailer.clear();
+            self.item = None;
+            track_panic!(ErrorKind::InvalidInput, "Checksum mismatched"; actual, expected);
+        }
+        Ok(())
+    }
+}
+impl<D: Decode> Decode for FrameDecoder<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if self.header_remaining() != 0 {
+            let size = cmp::min(buf.len(), self.header_remaining());
+            self.header.extend_from_slice(&buf[..size]);
+            offset += size;
+            if self.header_remaining() != 0 {
+                track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+                return Ok(offset);
+            }
+            track!(self.decode_header())?;
+        }
+
+        if self.item.is_none() {
+            let size = track!(self.body.decode(&buf[offset..], eos))?;
+            self.crc.update(&buf[offset..][..size]);
+            offset += size;
+            if !self.body.is_idle() {
+                return Ok(offset);
+            }
+            self.item = Some(track!(self.body.finish_decoding())?);
+        }
+
+        if self.trailer_remaining() != 0 {
+            let size = cmp::min(buf.len() - offset, self.trailer_remaining());
+            self.trailer.extend_from_slice(&buf[offset..][..size]);
+            offset += size;
+            if self.trailer_remaining() != 0 {
+                track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+                return Ok(offset);
+            }
+            track!(self.verify_checksum())?;
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert_eq!(self.trailer_remaining(), 0, ErrorKind::IncompleteDecoding);
+        let item = track_assert_some!(self.item.take(), ErrorKind::IncompleteDecoding);
+        self.header.clear();
+        self.trailer.clear();
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        let trailer = ByteCount::Finite(self.trailer_remaining() as u64);
+        if self.header_remaining() != 0 {
+            ByteCount::Finite(self.header_remaining() as u64).add_for_decoding(trailer)
+        } else if self.item.is_none() {
+            self.body.requiring_bytes().add_for_decoding(trailer)
+        } else {
+            trailer
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.item.is_some() && self.trailer_remaining() == 0
+    }
+}
+
+/// Encoder which prefixes the encoded bytes of the inner encoder with their length.
+///
+/// The length prefix is an unsigned integer of the configured width (1 to 8 bytes) and byte order.
+/// This is a shorthand of a `FrameEncoder` that has neither magic bytes nor a checksum.
+///
+/// # Errors
+///
+/// If the length of a body does not fit in the prefix, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bytes::Utf8Encoder;
+/// use bytecodec::framing::{Endianness, VarWidthLengthPrefixEncoder};
+///
+/// let mut encoder = VarWidthLengthPrefixEncoder::new(Utf8Encoder::new(), 3, Endianness::Big);
+/// let bytes = encoder.encode_into_bytes("foo".to_owned()).unwrap();
+/// assert_eq!(bytes, b"\x00\x00\x03foo");
+/// ```
+#[derive(Debug)]
+pub struct VarWidthLengthPrefixEncoder<E>(FrameEncoder<E>);
+impl<E: SizedEncode> VarWidthLengthPrefixEncoder<E> {
+    /// Makes a new `VarWidthLengthPrefixEncoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is not in the range of `1..=8`, this function will panic.
+    pub fn new(inner: E, width: usize, endianness: Endianness) -> Self {
+        VarWidthLengthPrefixEncoder(Frame::new().length_field(width, endianness).encoder(inner))
+    }
+}
+impl<E> VarWidthLengthPrefixEncoder<E> {
+    /// Returns the width (in bytes) of the length prefix.
+    pub fn width(&self) -> usize {
+        self.0.frame().length_width
+    }
+
+    /// Returns the byte order of the length prefix.
+    pub fn endianness(&self) -> Endianness {
+        self.0.frame().endianness
+    }
+
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        self.0.inner_ref()
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        self.0.inner_mut()
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.0.into_inner()
+    }
+}
+impl<E: SizedEncode> Encode for VarWidthLengthPrefixEncoder<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.0.start_encoding(item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl<E: SizedEncode> SizedEncode for VarWidthLengthPrefixEncoder<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+/// Decoder which decodes bodies prefixed with their length.
+///
+/// This is the counterpart of `VarWidthLengthPrefixEncoder`.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::bytes::Utf8Decoder;
+/// use bytecodec::framing::{Endianness, VarWidthLengthPrefixDecoder};
+///
+/// let mut decoder = VarWidthLengthPrefixDecoder::new(Utf8Decoder::new(), 3, Endianness::Big);
+/// let item = decoder.decode_from_bytes(b"\x00\x00\x03foo").unwrap();
+/// assert_eq!(item, "foo");
+/// ```
+pub struct VarWidthLengthPrefixDecoder<D: Decode>(FrameDecoder<D>);
+impl<D: Decode> VarWidthLengthPrefixDecoder<D> {
+    /// Makes a new `VarWidthLengthPrefixDecoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is not in the range of `1..=8`, this function will panic.
+    pub fn new(inner: D, width: usize, endianness: Endianness) -> Self {
+        VarWidthLengthPrefixDecoder(Frame::new().length_field(width, endianness).decoder(inner))
+    }
+
+    /// Returns the width (in bytes) of the length prefix.
+    pub fn width(&self) -> usize {
+        self.0.frame().length_width
+    }
+
+    /// Returns the byte order of the length prefix.
+    pub fn endianness(&self) -> Endianness {
+        self.0.frame().endianness
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        self.0.inner_ref()
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        self.0.inner_mut()
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.0.into_inner()
+    }
+}
+impl<D: Decode + fmt::Debug> fmt::Debug for VarWidthLengthPrefixDecoder<D>
+where
+    D::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("VarWidthLengthPrefixDecoder")
+            .field(&self.0)
+            .finish()
+    }
+}
+impl<D: Decode> Decode for VarWidthLengthPrefixDecoder<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.0.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track!(self.0.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+
+/// Decoder which reassembles length-prefixed messages before decoding them.
+///
+/// This reads a length prefix and buffers the following body bytes across `decode` calls
+/// until the whole body is received.
+/// Then the inner decoder is invoked exactly once with the complete (contiguous) body.
+/// This is useful for inner decoders that are not fully incremental.
+///
+/// # Errors
+///
+/// If the inner decoder does not consume the whole body or
+/// does not finish decoding an item at the end of the body,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// If the length prefix exceeds `max_length`,
+/// an `ErrorKind::InvalidInput` error will be returned before the body is buffered.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, Eos};
+/// use bytecodec::bytes::Utf8Decoder;
+/// use bytecodec::framing::{Endianness, FrameReassembler};
+///
+/// let mut decoder = FrameReassembler::new(Utf8Decoder::new(), 1, Endianness::Big);
+/// decoder.decode(b"\x03fo", Eos::new(false)).unwrap();
+/// assert!(!decoder.is_idle());
+/// decoder.decode(b"o", Eos::new(false)).unwrap();
+/// assert_eq!(decoder.finish_decoding().unwrap(), "foo");
+/// ```
+#[derive(Debug)]
+pub struct FrameReassembler<D: Decode> {
+    inner: D,
+    length_width: usize,
+    endianness: Endianness,
+    header: Vec<u8>,
+    body: Vec<u8>,
+    length: Option<usize>,
+    max_length: Option<usize>,
+    item: Option<D::Item>,
+}
+impl<D: Decode> FrameReassembler<D> {
+    /// Makes a new `FrameReassembler` instance.
+    ///
+    /// `length_width` and `endianness` specify the layout of the length prefix.
+    ///
+    /// # Panics
+    ///
+    /// If `length_width` is not in the range of `1..=8`, this function will panic.
+    pub fn new(inner: D, length_width: usize, endianness: Endianness) -> Self {
+        assert!(
+            (1..=8).contains(&length_width),
+            "length_width={}",
+            length_width
+        );
+        FrameReassembler {
+            inner,
+            length_width,
+            endianness,
+            header: Vec::with_capacity(length_width),
+            body: Vec::new(),
+            length: None,
+            max_length: None,
+            item: None,
+        }
+    }
+
+    /// Makes a new `FrameReassembler` instance that rejects bodies longer than `max_length`.
+    ///
+    /// # Panics
+    ///
+    /// If `length_width` is not in the range of `1..=8`, this function will panic.
+    pub fn with_max_length(
+        inner: D,
+        length_width: usize,
+        endianness: Endianness,
+        max_length: usize,
+    ) -> Self {
+        let mut this = Self::new(inner, length_width, endianness);
+        this.max_length = Some(max_length);
+        this
+    }
+
+    /// Returns the maximum length of a body.
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Sets the maximum length of a body.
+    ///
+    /// `None` means unlimited.
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    fn decode_body(&mut self) -> Result<()> {
+        let size = track!(self.inner.decode(&self.body, Eos::new(true)))?;
+        track_assert_eq!(
+            size,
+            self.body.len(),
+            ErrorKind::InvalidInput,
+            "The inner decoder did not consume the whole body"
+        );
+        track_assert!(
+            self.inner.is_idle(),
+            ErrorKind::InvalidInput,
+            "The inner decoder did not finish decoding at the end of the body"
+        );
+        self.item = Some(track!(self.inner.finish_decoding())?);
+        Ok(())
+    }
+}
+impl<D: Decode> Decode for FrameReassembler<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.item.is_some() {
+            return Ok(0);
+        }
+
+        let mut offset = 0;
+        let length = if let Some(length) = self.length {
+            length
+        } else {
+            let size = cmp::min(buf.len(), self.length_width - self.header.len());
+            self.header.extend_from_slice(&buf[..size]);
+            offset += size;
+            if self.header.len() < self.length_width {
+                track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+                return Ok(offset);
+            }
+
+            let length = self.endianness.read_uint(&self.header);
+            let max_length = self.max_length.unwrap_or(usize::MAX) as u64;
+            if length > max_length {
+                self.header.clear();
+                track_panic!(ErrorKind::InvalidInput; length, max_length);
+            }
+            self.length = Some(length as usize);
+            length as usize
+        };
+
+        let size = cmp::min(buf.len() - offset, length - self.body.len());
+        self.body.extend_from_slice(&buf[offset..][..size]);
+        offset += size;
+        if self.body.len() < length {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.body.len(), length);
+            return Ok(offset);
+        }
+        track!(self.decode_body())?;
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track_assert_some!(self.item.take(), ErrorKind::IncompleteDecoding);
+        self.header.clear();
+        self.body.clear();
+        self.length = None;
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.item.is_some() {
+            ByteCount::Finite(0)
+        } else if let Some(length) = self.length {
+            ByteCount::Finite((length - self.body.len()) as u64)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.item.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bytes::{Utf8Decoder, Utf8Encoder};
+    use crate::io::{IoDecodeExt, IoEncodeExt};
+    use crate::EncodeExt;
+
+    #[test]
+    fn frame_works() {
+        let frame = Frame::new()
+            .magic(*b"BC")
+            .length_field(3, Endianness::Little)
+            .checksum(true);
+
+        let mut encoder = frame.clone().encoder(Utf8Encoder::new());
+        let mut buf = Vec::new();
+        track_try_unwrap!(encoder.start_encoding("foo".to_owned()));
+        assert_eq!(encoder.exact_requiring_bytes(), 12);
+        track_try_unwrap!(encoder.encode_all(&mut buf));
+        track_try_unwrap!(encoder.start_encoding("barbaz".to_owned()));
+        track_try_unwrap!(encoder.encode_all(&mut buf));
+        assert_eq!(&buf[..8], b"BC\x03\x00\x00foo");
+        assert_eq!(buf.len(), 12 + 15);
+
+        let mut decoder = frame.decoder(Utf8Decoder::new());
+        let mut input = &buf[..];
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), "foo");
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_exact(&mut input)),
+            "barbaz"
+        );
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn frame_decoder_rejects_corrupted_frames() {
+        let frame = Frame::new().magic(*b"BC").checksum(true);
+        let mut encoder = frame.clone().encoder(Utf8Encoder::new());
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("foo".to_owned()));
+
+        let mut decoder = frame.clone().decoder(Utf8Decoder::new());
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'X';
+        let error = decoder.decode_from_bytes(&corrupted).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        assert!(!decoder.is_idle());
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), "foo");
+
+        let mut decoder = frame.decoder(Utf8Decoder::new());
+        let mut corrupted = bytes.clone();
+        corrupted[6] = b'x';
+        let error = decoder.decode(&corrupted, Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert!(!decoder.is_idle());
+        assert!(decoder.finish_decoding().is_err());
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), "foo");
+    }
+
+    #[test]
+    fn frame_encoder_rejects_too_long_body() {
+        let mut encoder = Frame::new()
+            .length_field(1, Endianness::Big)
+            .encoder(Utf8Encoder::new());
+        let error = encoder.start_encoding("a".repeat(256)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert!(encoder.is_idle());
+
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("a".to_owned()));
+        assert_eq!(bytes, b"\x01a");
+    }
+
+    #[test]
+    fn var_width_length_prefix_works() {
+        let mut encoder =
+            VarWidthLengthPrefixEncoder::new(Utf8Encoder::new(), 2, Endianness::Little);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("foobar".to_owned()));
+        assert_eq!(bytes, b"\x06\x00foobar");
+
+        let mut decoder =
+            VarWidthLengthPrefixDecoder::new(Utf8Decoder::new(), 2, Endianness::Little);
+        let mut input = &bytes[..];
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_exact(&mut input)),
+            "foobar"
+        );
+        assert!(input.is_empty());
+
+        let mut encoder = VarWidthLengthPrefixEncoder::new(Utf8Encoder::new(), 1, Endianness::Big);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("a".repeat(255)));
+        assert_eq!(bytes.len(), 256);
+        let error = encoder.start_encoding("a".repeat(256)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("a".to_owned()));
+        assert_eq!(bytes, b"\x01a");
+    }
+
+    #[test]
+    fn frame_reassembler_works() {
+        let mut decoder = FrameReassembler::new(Utf8Decoder::new(), 2, Endianness::Big);
+        let input = b"\x00\x06foobar";
+
+        let mut offset = 0;
+        for chunk in input.chunks(2) {
+            assert!(!decoder.is_idle());
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+        }
+        assert_eq!(offset, input.len());
+        assert_eq!(decoder.requiring_bytes(), ByteCount::Finite(0));
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "foobar");
+
+        let mut decoder = FrameReassembler::new(Utf8Decoder::new(), 2, Endianness::Little);
+        let error = decoder.decode_from_bytes(b"\x06\x00foo").err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+
+    #[test]
+    fn frame_reassembler_max_length_works() {
+        let mut decoder =
+            FrameReassembler::with_max_length(Utf8Decoder::new(), 8, Endianness::Big, 3);
+        assert_eq!(decoder.max_length(), Some(3));
+
+        let error = decoder
+            .decode(b"\x7f\xff\xff\xff\xff\xff\xff\xfffoo", Eos::new(false))
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(decoder.body.capacity(), 0);
+
+        let mut input = &b"\x00\x00\x00\x00\x00\x00\x00\x03foo"[..];
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), "foo");
+
+        decoder.set_max_length(None);
+        let mut input = &b"\x00\x00\x00\x00\x00\x00\x00\x06foobar"[..];
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_exact(&mut input)),
+            "foobar"
+        );
+    }
+}
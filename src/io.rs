@@ -1,4 +1,5 @@
 //! I/O (i.e., `Read` and `Write` traits) related module.
+use crate::combinator::Slice;
 use crate::{ByteCount, Decode, Encode, Eos, Error, ErrorKind, Result};
 #[cfg(feature = "tokio-async")]
 use pin_project::pin_project;
@@ -50,6 +51,39 @@ pub trait IoDecodeExt: Decode {
             }
         }
     }
+
+    /// Decodes an item from the read buffer of the given `BufferedIo`.
+    ///
+    /// If the buffered bytes are insufficient, `BufferedIo::execute_io` is called to refill the buffer.
+    /// Unlike `decode_exact`, no intermediate buffer is used.
+    ///
+    /// If the inner stream would block before an item is decoded, this returns `Ok(None)`.
+    /// In that case, the decoding can be resumed by calling this method again later.
+    fn decode_exact_from_buffered<T>(
+        &mut self,
+        io: &mut BufferedIo<T>,
+    ) -> Result<Option<Self::Item>>
+    where
+        T: Read + Write,
+    {
+        let mut io_executed = false;
+        loop {
+            track!(self.decode_from_read_buf(&mut io.rbuf))?;
+            if self.is_idle() {
+                let item = track!(self.finish_decoding())?;
+                return Ok(Some(item));
+            }
+            track_assert!(io.rbuf.is_empty(), ErrorKind::InconsistentState; io.rbuf.len());
+
+            let state = io.rbuf.stream_state();
+            track_assert!(!state.is_eos(), ErrorKind::UnexpectedEos);
+            if io_executed && state.would_block() {
+                return Ok(None);
+            }
+            track!(io.execute_io())?;
+            io_executed = true;
+        }
+    }
 }
 impl<T: Decode> IoDecodeExt for T {}
 
@@ -67,6 +101,44 @@ pub trait IoEncodeExt: Encode {
         Ok(())
     }
 
+    /// Encodes the items remaining in the encoder and
+    /// writes the encoded bytes to the given growable write buffer.
+    ///
+    /// If the buffer becomes full before the encoder becomes idle,
+    /// the buffer is grown and the encoding continues.
+    /// The encoding stops when the buffer cannot grow any more (i.e., it has reached the maximum capacity).
+    fn encode_to_growable_write_buf(&mut self, buf: &mut GrowableWriteBuf) -> Result<()> {
+        loop {
+            track!(self.encode_to_write_buf(&mut buf.inner))?;
+            if self.is_idle() || buf.inner.room() != 0 || !buf.grow() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Encodes the items remaining in the encoder and
+    /// writes the encoded bytes to the writer of the given sink.
+    ///
+    /// The encoded bytes are accumulated in the buffer of the sink,
+    /// which is flushed only when it becomes full.
+    /// So small items are coalesced into fewer `write` calls
+    /// (call `WriterSink::flush` to write the remaining bytes).
+    ///
+    /// Note that this is a blocking method.
+    fn encode_to_writer_buffered<W: Write>(&mut self, sink: &mut WriterSink<W>) -> Result<()> {
+        while !self.is_idle() {
+            if sink.buf.is_full() {
+                track!(sink.flush_buf())?;
+            }
+            let before = sink.buf.tail;
+            track!(self.encode_to_write_buf(&mut sink.buf))?;
+            if !self.is_idle() {
+                track_assert_ne!(sink.buf.tail, before, ErrorKind::Other);
+            }
+        }
+        Ok(())
+    }
+
     /// Encodes the items remaining in the encoder and
     /// writes the encoded bytes to the given write buffer.
     /// If the write buffer is full and the writing cannot be performed,
@@ -103,9 +175,49 @@ pub trait IoEncodeExt: Encode {
         }
         Ok(())
     }
+
+    /// Encodes all of the items remaining in the encoder and
+    /// appends the encoded bytes to the given vector.
+    ///
+    /// If `requiring_bytes()` is `ByteCount::Finite` and it does not exceed 16 MiB,
+    /// the capacity of the vector is grown exactly by that size and
+    /// the encoder writes into it directly (i.e., no intermediate buffer is used).
+    /// Otherwise, this falls back to the chunked encoding of `encode_all` method
+    /// (so that a bogus byte count does not cause a huge allocation up front).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytecodec::{Encode, EncodeExt};
+    /// use bytecodec::bytes::BytesEncoder;
+    /// use bytecodec::io::IoEncodeExt;
+    ///
+    /// let mut output = b"foo".to_vec();
+    /// let mut encoder = BytesEncoder::with_item(b"bar").unwrap();
+    /// encoder.encode_all_to_vec(&mut output).unwrap();
+    /// assert!(encoder.is_idle());
+    /// assert_eq!(output, b"foobar");
+    /// ```
+    fn encode_all_to_vec(&mut self, out: &mut Vec<u8>) -> Result<()> {
+        match self.requiring_bytes() {
+            ByteCount::Finite(n) if n <= MAX_DIRECT_ENCODE_BYTES => {
+                let start = out.len();
+                out.reserve_exact(n as usize);
+                out.resize(start + n as usize, 0);
+                let result = self.encode(&mut out[start..], Eos::new(false));
+                out.truncate(start + *result.as_ref().unwrap_or(&0));
+                track!(result)?;
+            }
+            _ => {}
+        }
+        track!(self.encode_all(out))
+    }
 }
 impl<T: Encode> IoEncodeExt for T {}
 
+/// The maximum number of bytes that `IoEncodeExt::encode_all_to_vec` reserves up front.
+const MAX_DIRECT_ENCODE_BYTES: u64 = 16 * 1024 * 1024;
+
 /// State of I/O streams.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]
@@ -199,13 +311,38 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> ReadBuf<B> {
     /// - The read buffer became full
     /// - A read operation returned a `WouldBlock` error
     /// - The input stream has reached EOS
-    pub fn fill<R: Read>(&mut self, mut reader: R) -> Result<()> {
-        while !self.is_full() {
-            match reader.read(&mut self.inner.as_mut()[self.tail..]) {
+    ///
+    /// Read operations that returned an `Interrupted` error are retried.
+    pub fn fill<R: Read>(&mut self, reader: R) -> Result<()> {
+        let end = self.capacity();
+        track!(self.fill_until(reader, end))
+    }
+
+    /// Fills the read buffer by reading bytes from the given reader
+    /// until the buffer contains at least `n` bytes.
+    ///
+    /// Bytes beyond the `n`-th are never read, so the rest of the buffer is left as room.
+    ///
+    /// The fill process continues until one of the following condition is satisfied:
+    /// - The length of the read buffer became `n` (or the buffer became full)
+    /// - A read operation returned a `WouldBlock` error
+    /// - The input stream has reached EOS
+    ///
+    /// Read operations that returned an `Interrupted` error are retried.
+    pub fn fill_at_least<R: Read>(&mut self, reader: R, n: usize) -> Result<()> {
+        let end = cmp::min(self.head.saturating_add(n), self.capacity());
+        track!(self.fill_until(reader, end))
+    }
+
+    fn fill_until<R: Read>(&mut self, mut reader: R, end: usize) -> Result<()> {
+        while self.tail < end {
+            match reader.read(&mut self.inner.as_mut()[self.tail..end]) {
                 Err(e) => {
                     if e.kind() == io::ErrorKind::WouldBlock {
                         self.stream_state = StreamState::WouldBlock;
                         break;
+                    } else if e.kind() == io::ErrorKind::Interrupted {
+                        continue;
                     } else {
                         self.stream_state = StreamState::Error;
                         return Err(track!(Error::from(e)));
@@ -242,7 +379,7 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> ReadBuf<B> {
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Read for ReadBuf<B> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         let size = cmp::min(buf.len(), self.len());
-        (&mut buf[..size]).copy_from_slice(&self.inner.as_ref()[self.head..][..size]);
+        buf[..size].copy_from_slice(&self.inner.as_ref()[self.head..][..size]);
         self.head += size;
         if self.head == self.tail {
             self.head = 0;
@@ -320,6 +457,8 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> WriteBuf<B> {
     /// - The write buffer became empty
     /// - A write operation returned a `WouldBlock` error
     /// - The output stream has reached EOS
+    ///
+    /// Write operations that returned an `Interrupted` error are retried.
     pub fn flush<W: Write>(&mut self, mut writer: W) -> Result<()> {
         while !self.is_empty() {
             match writer.write(&self.inner.as_ref()[self.head..self.tail]) {
@@ -327,6 +466,8 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> WriteBuf<B> {
                     if e.kind() == io::ErrorKind::WouldBlock {
                         self.stream_state = StreamState::WouldBlock;
                         break;
+                    } else if e.kind() == io::ErrorKind::Interrupted {
+                        continue;
                     } else {
                         self.stream_state = StreamState::Error;
                         return Err(track!(Error::from(e)));
@@ -373,7 +514,7 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> WriteBuf<B> {
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Write for WriteBuf<B> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         let size = cmp::min(buf.len(), self.room());
-        (&mut self.inner.as_mut()[self.tail..][..size]).copy_from_slice(&buf[..size]);
+        self.inner.as_mut()[self.tail..][..size].copy_from_slice(&buf[..size]);
         self.tail += size;
         Ok(size)
     }
@@ -383,6 +524,171 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Write for WriteBuf<B> {
     }
 }
 
+/// Write buffer that grows dynamically when it becomes full.
+///
+/// When an encoder fills up the buffer via `IoEncodeExt::encode_to_growable_write_buf` method,
+/// the capacity of the inner buffer is doubled (up to the optional maximum capacity).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bytes::BytesEncoder;
+/// use bytecodec::io::{GrowableWriteBuf, IoEncodeExt};
+///
+/// let mut buf = GrowableWriteBuf::new(2);
+/// let mut encoder = BytesEncoder::with_item(b"foobar").unwrap();
+/// encoder.encode_to_growable_write_buf(&mut buf).unwrap();
+/// assert_eq!(buf.inner_ref().len(), 6);
+/// assert_eq!(buf.inner_ref().capacity(), 8);
+/// ```
+#[derive(Debug)]
+pub struct GrowableWriteBuf {
+    inner: WriteBuf<Vec<u8>>,
+    max_capacity: Option<usize>,
+}
+impl GrowableWriteBuf {
+    /// Makes a new `GrowableWriteBuf` instance that has no capacity limit.
+    pub fn new(initial_capacity: usize) -> Self {
+        GrowableWriteBuf {
+            inner: WriteBuf::new(vec![0; initial_capacity]),
+            max_capacity: None,
+        }
+    }
+
+    /// Makes a new `GrowableWriteBuf` instance that grows up to `max_capacity` bytes.
+    pub fn with_max_capacity(initial_capacity: usize, max_capacity: usize) -> Self {
+        GrowableWriteBuf {
+            inner: WriteBuf::new(vec![0; initial_capacity]),
+            max_capacity: Some(max_capacity),
+        }
+    }
+
+    /// Returns the maximum capacity of the buffer.
+    ///
+    /// `None` means the buffer can grow unlimitedly.
+    pub fn max_capacity(&self) -> Option<usize> {
+        self.max_capacity
+    }
+
+    /// Returns a reference to the inner write buffer.
+    pub fn inner_ref(&self) -> &WriteBuf<Vec<u8>> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner write buffer.
+    pub fn inner_mut(&mut self) -> &mut WriteBuf<Vec<u8>> {
+        &mut self.inner
+    }
+
+    /// Takes ownership of `GrowableWriteBuf` and returns the inner write buffer.
+    pub fn into_inner(self) -> WriteBuf<Vec<u8>> {
+        self.inner
+    }
+
+    fn grow(&mut self) -> bool {
+        let capacity = self.inner.capacity();
+        let mut new_capacity = cmp::max(capacity.saturating_mul(2), 1);
+        if let Some(max) = self.max_capacity {
+            new_capacity = cmp::min(new_capacity, max);
+        }
+        if new_capacity <= capacity {
+            return false;
+        }
+        self.inner.inner.resize(new_capacity, 0);
+        true
+    }
+}
+
+/// Writer wrapper that coalesces the bytes of encoded items by using a reusable write buffer.
+///
+/// Unlike `IoEncodeExt::encode_all`, the bytes are written to the writer only when the buffer becomes full
+/// (or `flush` method is called).
+/// This reduces the number of `write` calls when sending many small items to the same writer.
+///
+/// Note that the methods of this type are blocking.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::bytes::Utf8Encoder;
+/// use bytecodec::io::WriterSink;
+///
+/// let mut sink = WriterSink::new(Vec::new(), 1024);
+/// let mut encoder = Utf8Encoder::new();
+/// sink.send(&mut encoder, "foo").unwrap();
+/// sink.send(&mut encoder, "bar").unwrap();
+/// assert!(sink.writer_ref().is_empty());
+///
+/// sink.flush().unwrap();
+/// assert_eq!(sink.writer_ref(), b"foobar");
+/// ```
+#[derive(Debug)]
+pub struct WriterSink<W> {
+    writer: W,
+    buf: WriteBuf<Vec<u8>>,
+}
+impl<W: Write> WriterSink<W> {
+    /// Makes a new `WriterSink` instance that has a write buffer of `buf_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If `buf_size` is `0`, this function will panic.
+    pub fn new(writer: W, buf_size: usize) -> Self {
+        assert_ne!(buf_size, 0);
+        WriterSink {
+            writer,
+            buf: WriteBuf::new(vec![0; buf_size]),
+        }
+    }
+
+    /// Encodes the given item by using `encoder` and writes the resulting bytes to the sink.
+    ///
+    /// The bytes may be left in the buffer of the sink until it becomes full.
+    pub fn send<E: Encode>(&mut self, encoder: &mut E, item: E::Item) -> Result<()> {
+        track!(encoder.start_encoding(item))?;
+        track!(encoder.encode_to_writer_buffered(self))
+    }
+
+    /// Writes all of the buffered bytes to the writer and flushes the writer.
+    pub fn flush(&mut self) -> Result<()> {
+        track!(self.flush_buf())?;
+        track!(self.writer.flush().map_err(Error::from))?;
+        Ok(())
+    }
+
+    /// Returns a reference to the writer.
+    pub fn writer_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns a reference to the write buffer.
+    pub fn write_buf_ref(&self) -> &WriteBuf<Vec<u8>> {
+        &self.buf
+    }
+
+    /// Takes ownership of this instance and returns the writer.
+    ///
+    /// Note that the buffered bytes that have not been flushed are discarded.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn flush_buf(&mut self) -> Result<()> {
+        track!(self.buf.flush(&mut self.writer))?;
+        match self.buf.stream_state() {
+            StreamState::Eos if !self.buf.is_empty() => track_panic!(ErrorKind::UnexpectedEos),
+            StreamState::WouldBlock => track_panic!(ErrorKind::Other, "Would block"),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// Buffered I/O stream.
 #[cfg_attr(feature = "tokio-async", pin_project)]
 #[derive(Debug)]
@@ -401,6 +707,29 @@ impl<T: Read + Write> BufferedIo<T> {
         track!(self.wbuf.flush(&mut self.stream))?;
         Ok(())
     }
+
+    /// Executes an I/O operation on the inner stream without over-reading past the current item.
+    ///
+    /// This is the same as `execute_io` except that the read is limited by `requiring_bytes`
+    /// (i.e., typically the result of `Decode::requiring_bytes` of the decoder consuming the read buffer):
+    /// - `ByteCount::Finite(n)`: reads until the read buffer contains at least `n` bytes
+    /// - `ByteCount::Unknown`: reads until the read buffer contains at least one byte
+    /// - `ByteCount::Infinite`: reads without limitation
+    ///
+    /// This is useful when the protocol of the stream may be switched after the current item,
+    /// because the bytes following the item are left in the inner stream.
+    pub fn execute_io_limited(&mut self, requiring_bytes: ByteCount) -> Result<()> {
+        match requiring_bytes {
+            ByteCount::Finite(n) => {
+                let n = cmp::min(n, usize::MAX as u64) as usize;
+                track!(self.rbuf.fill_at_least(&mut self.stream, n))?;
+            }
+            ByteCount::Unknown => track!(self.rbuf.fill_at_least(&mut self.stream, 1))?,
+            ByteCount::Infinite => track!(self.rbuf.fill(&mut self.stream))?,
+        }
+        track!(self.wbuf.flush(&mut self.stream))?;
+        Ok(())
+    }
 }
 
 impl<T> BufferedIo<T> {
@@ -460,13 +789,160 @@ impl<T> BufferedIo<T> {
     }
 }
 
+/// Multiplexer that interleaves the outputs of several encoders into a shared write buffer.
+///
+/// Each encoder is wrapped by `Slice` and is serviced in round-robin order.
+/// In each turn, an encoder can write at most `quantum` bytes to the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::Encode;
+/// use bytecodec::bytes::Utf8Encoder;
+/// use bytecodec::io::{Multiplexer, WriteBuf};
+///
+/// let mut mux = Multiplexer::new(WriteBuf::new(vec![0; 16]), 2);
+/// let a = mux.add_stream(Utf8Encoder::new());
+/// let b = mux.add_stream(Utf8Encoder::new());
+/// mux.stream_mut(a).unwrap().start_encoding("aaaa").unwrap();
+/// mux.stream_mut(b).unwrap().start_encoding("bbb").unwrap();
+///
+/// mux.poll_encode().unwrap();
+/// assert!(mux.is_idle());
+///
+/// let mut output = Vec::new();
+/// mux.write_buf_mut().flush(&mut output).unwrap();
+/// assert_eq!(output, b"aabbaab");
+/// ```
+#[derive(Debug)]
+pub struct Multiplexer<E, B> {
+    streams: Vec<Slice<E>>,
+    buf: WriteBuf<B>,
+    quantum: usize,
+    next: usize,
+}
+impl<E: Encode, B: AsRef<[u8]> + AsMut<[u8]>> Multiplexer<E, B> {
+    /// Makes a new `Multiplexer` instance that has no substreams.
+    ///
+    /// `quantum` is the maximum number of bytes written by a substream in a turn.
+    pub fn new(buf: WriteBuf<B>, quantum: usize) -> Self {
+        Multiplexer {
+            streams: Vec::new(),
+            buf,
+            quantum,
+            next: 0,
+        }
+    }
+
+    /// Adds a substream encoder and returns the index of it.
+    pub fn add_stream(&mut self, encoder: E) -> usize {
+        self.streams.push(Slice::new(encoder));
+        self.streams.len() - 1
+    }
+
+    /// Returns the number of substreams.
+    pub fn stream_count(&self) -> usize {
+        self.streams.len()
+    }
+
+    /// Returns a reference to the substream encoder at `index`.
+    pub fn stream_ref(&self, index: usize) -> Option<&Slice<E>> {
+        self.streams.get(index)
+    }
+
+    /// Returns a mutable reference to the substream encoder at `index`.
+    pub fn stream_mut(&mut self, index: usize) -> Option<&mut Slice<E>> {
+        self.streams.get_mut(index)
+    }
+
+    /// Returns the indices of the idle substreams.
+    pub fn idle_streams(&self) -> impl Iterator<Item = usize> + '_ {
+        self.streams
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.is_idle())
+            .map(|(i, _)| i)
+    }
+
+    /// Returns `true` if all of the substreams are idle, otherwise `false`.
+    pub fn is_idle(&self) -> bool {
+        self.streams.iter().all(Encode::is_idle)
+    }
+
+    /// Encodes the items of the substreams in round-robin order and
+    /// writes the encoded bytes to the write buffer.
+    ///
+    /// This continues until the buffer becomes full or no substream can make progress.
+    /// If the buffer becomes full in the middle of a turn,
+    /// the turn will be resumed by the next call.
+    pub fn poll_encode(&mut self) -> Result<()> {
+        let mut stalled = 0;
+        while !self.buf.is_full() && stalled < self.streams.len() {
+            let index = self.next;
+            let stream = &mut self.streams[index];
+            let mut progressed = false;
+            if !stream.is_idle() {
+                if stream.is_suspended() {
+                    stream.set_consumable_bytes(self.quantum as u64);
+                }
+                let before = self.buf.tail;
+                track!(stream.encode_to_write_buf(&mut self.buf); index)?;
+                progressed = self.buf.tail != before;
+                if progressed && !stream.is_idle() && !stream.is_suspended() {
+                    // The buffer is full; resumes this turn later.
+                    continue;
+                }
+            }
+            stream.set_consumable_bytes(0);
+            self.next = (index + 1) % self.streams.len();
+            stalled = if progressed { 0 } else { stalled + 1 };
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the write buffer.
+    pub fn write_buf_ref(&self) -> &WriteBuf<B> {
+        &self.buf
+    }
+
+    /// Returns a mutable reference to the write buffer.
+    pub fn write_buf_mut(&mut self) -> &mut WriteBuf<B> {
+        &mut self.buf
+    }
+
+    /// Takes ownership of this instance and returns the substreams and the write buffer.
+    pub fn into_inner(self) -> (Vec<Slice<E>>, WriteBuf<B>) {
+        (self.streams, self.buf)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::bytes::{Utf8Decoder, Utf8Encoder};
-    use crate::EncodeExt;
+    use crate::bytes::{BytesEncoder, CopyableBytesDecoder, Utf8Decoder, Utf8Encoder};
+    use crate::{DecodeExt, EncodeExt};
     use std::io::{Read, Write};
 
+    #[test]
+    fn encode_all_to_vec_works() {
+        let text = "foo".repeat(1000);
+        let mut encoder = Utf8Encoder::new();
+        track_try_unwrap!(encoder.start_encoding(text.clone()));
+
+        let mut output = Vec::new();
+        track_try_unwrap!(encoder.encode_all_to_vec(&mut output));
+        assert!(encoder.is_idle());
+        assert_eq!(output, text.as_bytes());
+        assert_eq!(output.capacity(), text.len());
+
+        let mut output = vec![0; 4000];
+        output.shrink_to_fit();
+        track_try_unwrap!(encoder.start_encoding(text.clone()));
+        track_try_unwrap!(encoder.encode_all_to_vec(&mut output));
+        assert_eq!(&output[4000..], text.as_bytes());
+        assert_eq!(output.capacity(), 4000 + text.len());
+    }
+
     #[test]
     fn decode_from_read_buf_works() {
         let mut buf = ReadBuf::new(vec![0; 1024]);
@@ -479,6 +955,163 @@ mod test {
         assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "foo");
     }
 
+    #[test]
+    fn fill_at_least_works() {
+        struct TwoBytesReader<'a>(&'a [u8]);
+        impl<'a> Read for TwoBytesReader<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let size = cmp::min(2, buf.len());
+                self.0.read(&mut buf[..size])
+            }
+        }
+
+        let mut reader = TwoBytesReader(b"0123456789");
+        let mut buf = ReadBuf::new(vec![0; 8]);
+        track_try_unwrap!(buf.fill_at_least(&mut reader, 5));
+        assert_eq!(buf.len(), 5);
+        assert_eq!(buf.room(), 3);
+        assert_eq!(buf.stream_state(), StreamState::Normal);
+
+        track_try_unwrap!(buf.fill_at_least(&mut reader, 100));
+        assert!(buf.is_full());
+
+        let mut output = Vec::new();
+        track_try_unwrap!(buf.read_to_end(&mut output).map_err(Error::from));
+        assert_eq!(output, b"01234567");
+
+        track_try_unwrap!(buf.fill_at_least(&mut reader, 5));
+        assert_eq!(buf.len(), 2);
+        assert_eq!(buf.stream_state(), StreamState::Eos);
+    }
+
+    #[test]
+    fn interrupted_io_is_retried() {
+        struct InterruptOnce<T> {
+            inner: T,
+            interrupted: bool,
+        }
+        impl<T> InterruptOnce<T> {
+            fn check(&mut self) -> io::Result<()> {
+                if self.interrupted {
+                    Ok(())
+                } else {
+                    self.interrupted = true;
+                    Err(io::ErrorKind::Interrupted.into())
+                }
+            }
+        }
+        impl<T: Read> Read for InterruptOnce<T> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.check()?;
+                self.inner.read(buf)
+            }
+        }
+        impl<T: Write> Write for InterruptOnce<T> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.check()?;
+                self.inner.write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut buf = ReadBuf::new(vec![0; 3]);
+        let reader = InterruptOnce {
+            inner: &b"foo"[..],
+            interrupted: false,
+        };
+        track_try_unwrap!(buf.fill(reader));
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.stream_state(), StreamState::Normal);
+
+        let mut buf = WriteBuf::new(vec![0; 3]);
+        track_try_unwrap!(buf.write_all(b"bar").map_err(Error::from));
+        let mut writer = InterruptOnce {
+            inner: Vec::new(),
+            interrupted: false,
+        };
+        track_try_unwrap!(buf.flush(&mut writer));
+        assert!(buf.is_empty());
+        assert_eq!(buf.stream_state(), StreamState::Normal);
+        assert_eq!(writer.inner, b"bar");
+    }
+
+    #[test]
+    fn encode_to_growable_write_buf_works() {
+        let item = vec![b'a'; 10 * 1024];
+
+        let mut buf = GrowableWriteBuf::new(1024);
+        let mut encoder = track_try_unwrap!(BytesEncoder::with_item(item.clone()));
+        track_try_unwrap!(encoder.encode_to_growable_write_buf(&mut buf));
+        assert!(encoder.is_idle());
+        assert_eq!(buf.inner_ref().len(), item.len());
+        assert_eq!(buf.inner_ref().capacity(), 16 * 1024);
+
+        let mut output = Vec::new();
+        track_try_unwrap!(buf.inner_mut().flush(&mut output));
+        assert_eq!(output, item);
+
+        let mut buf = GrowableWriteBuf::with_max_capacity(1024, 3000);
+        let mut encoder = track_try_unwrap!(BytesEncoder::with_item(item));
+        track_try_unwrap!(encoder.encode_to_growable_write_buf(&mut buf));
+        assert!(!encoder.is_idle());
+        assert!(buf.inner_ref().is_full());
+        assert_eq!(buf.inner_ref().capacity(), 3000);
+    }
+
+    #[test]
+    fn decode_exact_from_buffered_works() {
+        let stream = io::Cursor::new(b"foo".to_vec());
+        let mut io = BufferedIo::new(stream, 2, 2);
+        let mut decoder = Utf8Decoder::new();
+        let item = track_try_unwrap!(decoder.decode_exact_from_buffered(&mut io));
+        assert_eq!(item.as_deref(), Some("foo"));
+        assert!(io.is_eos());
+
+        struct WouldBlock;
+        impl Read for WouldBlock {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+        }
+        impl Write for WouldBlock {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::WouldBlock.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut io = BufferedIo::new(WouldBlock, 2, 2);
+        let item = track_try_unwrap!(decoder.decode_exact_from_buffered(&mut io));
+        assert_eq!(item, None);
+    }
+
+    #[test]
+    fn execute_io_limited_works() {
+        let stream = io::Cursor::new(b"foobar".to_vec());
+        let mut io = BufferedIo::new(stream, 1024, 1024);
+        let mut decoder = Utf8Decoder::new().length(3);
+        while !decoder.is_idle() {
+            track_try_unwrap!(io.execute_io_limited(decoder.requiring_bytes()));
+            track_try_unwrap!(decoder.decode_from_read_buf(io.read_buf_mut()));
+        }
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "foo");
+        assert!(io.read_buf_ref().is_empty());
+        assert_eq!(io.stream_ref().position(), 3);
+
+        // The following bytes can be decoded by another decoder.
+        let mut decoder = CopyableBytesDecoder::new([0; 3]);
+        while !decoder.is_idle() {
+            track_try_unwrap!(io.execute_io_limited(decoder.requiring_bytes()));
+            track_try_unwrap!(decoder.decode_from_read_buf(io.read_buf_mut()));
+        }
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), *b"bar");
+    }
+
     #[test]
     fn read_from_read_buf_works() {
         let mut rbuf = ReadBuf::new(vec![0; 1024]);
@@ -519,4 +1152,61 @@ mod test {
         assert_eq!(buf.stream_state(), StreamState::Normal);
         assert_eq!(v, b"foo");
     }
+
+    #[test]
+    fn multiplexer_works() {
+        let mut mux = Multiplexer::new(WriteBuf::new(vec![0; 8]), 3);
+        let a = mux.add_stream(Utf8Encoder::new());
+        let b = mux.add_stream(Utf8Encoder::new());
+        track_try_unwrap!(mux.stream_mut(a).unwrap().start_encoding("foobar"));
+        track_try_unwrap!(mux.stream_mut(b).unwrap().start_encoding("012345678"));
+        assert_eq!(mux.idle_streams().count(), 0);
+
+        let mut output = Vec::new();
+        track_try_unwrap!(mux.poll_encode());
+        assert_eq!(mux.write_buf_ref().len(), 8);
+        track_try_unwrap!(mux.write_buf_mut().flush(&mut output));
+        assert_eq!(output, b"foo012ba");
+
+        track_try_unwrap!(mux.poll_encode());
+        track_try_unwrap!(mux.write_buf_mut().flush(&mut output));
+        assert_eq!(output, b"foo012bar345678");
+        assert_eq!(mux.idle_streams().collect::<Vec<_>>(), vec![a, b]);
+        assert!(mux.is_idle());
+    }
+
+    #[test]
+    fn writer_sink_works() {
+        struct CountingWriter {
+            bytes: Vec<u8>,
+            write_calls: usize,
+        }
+        impl Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.write_calls += 1;
+                self.bytes.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let writer = CountingWriter {
+            bytes: Vec::new(),
+            write_calls: 0,
+        };
+        let mut sink = WriterSink::new(writer, 64);
+        let mut encoder = Utf8Encoder::new();
+        for _ in 0..100 {
+            track_try_unwrap!(sink.send(&mut encoder, "foo"));
+        }
+        track_try_unwrap!(sink.flush());
+        assert!(sink.write_buf_ref().is_empty());
+
+        let writer = sink.into_inner();
+        assert_eq!(writer.bytes, "foo".repeat(100).as_bytes());
+        assert_eq!(writer.write_calls, 5);
+    }
 }
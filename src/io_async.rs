@@ -1,8 +1,10 @@
 //! I/O (i.e., `Read` and `Write` traits) related module.
-use crate::io::{BufferedIo, ReadBuf, StreamState, WriteBuf};
-use crate::{Error, Result};
+use crate::io::{BufferedIo, IoDecodeExt, ReadBuf, StreamState, WriteBuf};
+use crate::{Decode, Error, ErrorKind, Result};
 use core::pin::Pin;
 use core::task::{Context, Poll};
+use futures_core::Stream;
+use pin_project::pin_project;
 use tokio::io::{AsyncRead, AsyncWrite};
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> ReadBuf<B> {
@@ -117,3 +119,130 @@ impl<T: AsyncRead + AsyncWrite> BufferedIo<T> {
         Poll::Ready(Ok(()))
     }
 }
+
+/// Stream of items decoded from an asynchronous reader.
+///
+/// This is created by calling `DecodeExt::into_stream` method.
+///
+/// The stream yields an item each time the decoder finishes decoding one,
+/// and ends when the reader reaches EOS between items.
+/// If the reader reaches EOS in the middle of an item, an `ErrorKind::UnexpectedEos` error is yielded.
+/// After an error is yielded, the stream ends.
+#[pin_project]
+#[derive(Debug)]
+pub struct DecodeStream<D, R> {
+    decoder: D,
+    #[pin]
+    reader: R,
+    buf: ReadBuf<Vec<u8>>,
+    in_progress: bool,
+    terminated: bool,
+}
+impl<D, R> DecodeStream<D, R> {
+    pub(crate) fn new(decoder: D, reader: R) -> Self {
+        DecodeStream {
+            decoder,
+            reader,
+            buf: ReadBuf::new(vec![0; 4096]),
+            in_progress: false,
+            terminated: false,
+        }
+    }
+
+    /// Returns a reference to the decoder.
+    pub fn decoder_ref(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns a mutable reference to the decoder.
+    pub fn decoder_mut(&mut self) -> &mut D {
+        &mut self.decoder
+    }
+
+    /// Returns a reference to the reader.
+    pub fn reader_ref(&self) -> &R {
+        &self.reader
+    }
+}
+impl<D: Decode, R: AsyncRead> DecodeStream<D, R> {
+    fn poll_next_item(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Result<Poll<Option<D::Item>>> {
+        let mut this = self.project();
+        loop {
+            let eos = this.buf.stream_state().is_eos();
+            if !this.buf.is_empty() || (eos && *this.in_progress) {
+                let before = this.buf.len();
+                track!(this.decoder.decode_from_read_buf(this.buf))?;
+                if this.buf.len() != before {
+                    *this.in_progress = true;
+                }
+            }
+            if this.decoder.is_idle() {
+                *this.in_progress = false;
+                let item = track!(this.decoder.finish_decoding())?;
+                return Ok(Poll::Ready(Some(item)));
+            }
+            if eos {
+                track_assert!(!*this.in_progress, ErrorKind::UnexpectedEos);
+                return Ok(Poll::Ready(None));
+            }
+            match this.buf.poll_fill(this.reader.as_mut(), cx) {
+                Poll::Pending => return Ok(Poll::Pending),
+                Poll::Ready(result) => track!(result)?,
+            }
+        }
+    }
+}
+impl<D: Decode, R: AsyncRead> Stream for DecodeStream<D, R> {
+    type Item = Result<D::Item>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        match self.as_mut().poll_next_item(cx) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(Some(item))) => Poll::Ready(Some(Ok(item))),
+            Ok(Poll::Ready(None)) => {
+                *self.project().terminated = true;
+                Poll::Ready(None)
+            }
+            Err(e) => {
+                *self.project().terminated = true;
+                Poll::Ready(Some(Err(e)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixnum::U8Decoder;
+    use crate::{DecodeExt, ErrorKind};
+    use core::future::poll_fn;
+    use core::pin::Pin;
+    use futures_core::Stream;
+
+    #[tokio::test]
+    async fn decode_stream_works() {
+        let reader: &[u8] = &[1, 2, 3];
+        let mut stream = U8Decoder::new().into_stream(reader);
+        let mut items = Vec::new();
+        while let Some(item) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            items.push(track_try_unwrap!(item));
+        }
+        assert_eq!(items, [1, 2, 3]);
+
+        let reader: &[u8] = &[1, 2, 3];
+        let mut stream = U8Decoder::new().chain(U8Decoder::new()).into_stream(reader);
+        let item = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+        assert_eq!(track_try_unwrap!(item.unwrap()), (1, 2));
+        let item = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+        assert_eq!(
+            *item.unwrap().err().unwrap().kind(),
+            ErrorKind::UnexpectedEos
+        );
+        assert!(poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))
+            .await
+            .is_none());
+    }
+}
@@ -1,13 +1,17 @@
 //! `#[cfg(feature = "json_codec")]` JSON encoder and decoder that use [serde_json] internally.
 //!
 //! [serde_json]: https://crates.io/crates/serde_json
+use crate::combinator::PreEncode;
+use crate::framing::{Endianness, VarWidthLengthPrefixDecoder, VarWidthLengthPrefixEncoder};
 use crate::monolithic::{MonolithicDecode, MonolithicDecoder, MonolithicEncode, MonolithicEncoder};
-use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result};
+use crate::{ByteCount, Decode, Encode, EncodeExt, Eos, ErrorKind, Result, SizedEncode};
 use serde::{Deserialize, Serialize};
 use std::io::{Read, Write};
 use std::marker::PhantomData;
 use trackable::error::ErrorKindExt;
 
+const FRAME_LENGTH_WIDTH: usize = 4;
+
 /// JSON decoder.
 ///
 /// Note that this decodes items monolithically
@@ -40,6 +44,16 @@ where
     pub fn new() -> Self {
         JsonDecoder(MonolithicDecoder::new(MonolithicJsonDecoder::new()))
     }
+
+    /// Makes a new `JsonDecoder` instance that buffers at most `max_buffer_bytes` bytes.
+    ///
+    /// See `MonolithicDecoder::with_max_buffer_bytes` for the details.
+    pub fn with_max_buffer_bytes(max_buffer_bytes: usize) -> Self {
+        JsonDecoder(MonolithicDecoder::with_max_buffer_bytes(
+            MonolithicJsonDecoder::new(),
+            max_buffer_bytes,
+        ))
+    }
 }
 impl<T> Decode for JsonDecoder<T>
 where
@@ -90,6 +104,107 @@ where
     }
 }
 
+/// JSON Lines (i.e., newline-delimited JSON) decoder.
+///
+/// Each line of the input is deserialized as an item.
+/// The last line may not be terminated by a newline if the input reaches EOS after it.
+/// Empty (or whitespace-only) lines are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, Eos};
+/// use bytecodec::json_codec::JsonLinesDecoder;
+/// use serde_json::Value;
+///
+/// let mut decoder = JsonLinesDecoder::<Value>::new();
+///
+/// assert_eq!(decoder.decode(b"[1, 2]\n[3", Eos::new(false)).unwrap(), 7);
+/// assert_eq!(decoder.finish_decoding().unwrap().to_string(), "[1,2]");
+///
+/// assert_eq!(decoder.decode(b"[3", Eos::new(false)).unwrap(), 2);
+/// assert_eq!(decoder.decode(b"]", Eos::new(true)).unwrap(), 1);
+/// assert_eq!(decoder.finish_decoding().unwrap().to_string(), "[3]");
+/// ```
+#[derive(Debug)]
+pub struct JsonLinesDecoder<T> {
+    line: Vec<u8>,
+    item: Option<T>,
+}
+impl<T> JsonLinesDecoder<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    /// Makes a new `JsonLinesDecoder` instance.
+    pub fn new() -> Self {
+        JsonLinesDecoder {
+            line: Vec::new(),
+            item: None,
+        }
+    }
+
+    fn parse_line(&mut self) -> Result<()> {
+        if !self.line.iter().all(u8::is_ascii_whitespace) {
+            let item =
+                track!(serde_json::from_slice(&self.line)
+                    .map_err(|e| ErrorKind::InvalidInput.cause(e)))?;
+            self.item = Some(item);
+        }
+        self.line.clear();
+        Ok(())
+    }
+}
+impl<T> Decode for JsonLinesDecoder<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    type Item = T;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        while self.item.is_none() {
+            if let Some(i) = buf[offset..].iter().position(|&b| b == b'\n') {
+                self.line.extend_from_slice(&buf[offset..][..i]);
+                offset += i + 1;
+                track!(self.parse_line())?;
+            } else {
+                self.line.extend_from_slice(&buf[offset..]);
+                offset = buf.len();
+                if eos.is_reached() {
+                    track!(self.parse_line())?;
+                }
+                break;
+            }
+        }
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track_assert_some!(self.item.take(), ErrorKind::IncompleteDecoding);
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.item.is_some() {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.item.is_some()
+    }
+}
+impl<T> Default for JsonLinesDecoder<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// JSON encoder.
 ///
 /// Note that this encodes items monolithically
@@ -136,6 +251,144 @@ where
     }
 }
 
+/// Decoder which decodes JSON values prefixed by their byte length.
+///
+/// The length prefix is a 4-byte big-endian integer (see `JsonFrameEncoder`).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::json_codec::JsonFrameDecoder;
+/// use serde_json::Value;
+///
+/// let mut decoder = JsonFrameDecoder::<Value>::new();
+/// let json = decoder.decode_from_bytes(b"\x00\x00\x00\x07[1,2,3]").unwrap();
+/// assert_eq!(json.to_string(), "[1,2,3]");
+/// ```
+#[derive(Debug)]
+pub struct JsonFrameDecoder<T>(VarWidthLengthPrefixDecoder<JsonDecoder<T>>)
+where
+    T: for<'de> Deserialize<'de>;
+impl<T> JsonFrameDecoder<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    /// Makes a new `JsonFrameDecoder` instance.
+    pub fn new() -> Self {
+        JsonFrameDecoder(VarWidthLengthPrefixDecoder::new(
+            JsonDecoder::new(),
+            FRAME_LENGTH_WIDTH,
+            Endianness::Big,
+        ))
+    }
+}
+impl<T> Decode for JsonFrameDecoder<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    type Item = T;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.0.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track!(self.0.finish_decoding())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl<T> Default for JsonFrameDecoder<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encoder which encodes JSON values prefixed by their byte length.
+///
+/// Each item is serialized to JSON beforehand and
+/// its byte length is emitted as a 4-byte big-endian integer followed by the JSON text.
+/// Unlike `JsonLinesDecoder`, the frames do not depend on any delimiter.
+///
+/// # Errors
+///
+/// If the length of a serialized item exceeds `u32::MAX`,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::json_codec::JsonFrameEncoder;
+///
+/// let mut encoder = JsonFrameEncoder::new();
+/// let bytes = encoder.encode_into_bytes((1, 2, 3)).unwrap();
+/// assert_eq!(bytes, b"\x00\x00\x00\x07[1,2,3]");
+/// ```
+#[derive(Debug)]
+pub struct JsonFrameEncoder<T: Serialize>(VarWidthLengthPrefixEncoder<PreEncode<JsonEncoder<T>>>);
+impl<T> JsonFrameEncoder<T>
+where
+    T: Serialize,
+{
+    /// Makes a new `JsonFrameEncoder` instance.
+    pub fn new() -> Self {
+        JsonFrameEncoder(VarWidthLengthPrefixEncoder::new(
+            JsonEncoder::new().pre_encode(),
+            FRAME_LENGTH_WIDTH,
+            Endianness::Big,
+        ))
+    }
+}
+impl<T> Encode for JsonFrameEncoder<T>
+where
+    T: Serialize,
+{
+    type Item = T;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.0.start_encoding(item))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl<T> SizedEncode for JsonFrameEncoder<T>
+where
+    T: Serialize,
+{
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+impl<T> Default for JsonFrameEncoder<T>
+where
+    T: Serialize,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug)]
 struct MonolithicJsonEncoder<T>(PhantomData<T>);
 impl<T> MonolithicJsonEncoder<T> {
@@ -215,4 +468,90 @@ mod test {
             r#"{"id":4,"name":"item4"}"#
         );
     }
+
+    #[test]
+    fn json_lines_decoder_works() {
+        let mut decoder = JsonLinesDecoder::<Value>::new();
+        let input = b"{\"id\": 1, \"name\": \"foo\"}\n\n{\"id\": 2, \"name\": \"bar\"}";
+
+        let mut items = Vec::new();
+        for chunk in input.chunks(10) {
+            let mut offset = 0;
+            while offset < chunk.len() {
+                offset += track_try_unwrap!(decoder.decode(&chunk[offset..], Eos::new(false)));
+                if decoder.is_idle() {
+                    items.push(track_try_unwrap!(decoder.finish_decoding()));
+                }
+            }
+        }
+        assert_eq!(items.len(), 1);
+        assert!(!decoder.is_idle());
+
+        track_try_unwrap!(decoder.decode(&[], Eos::new(true)));
+        items.push(track_try_unwrap!(decoder.finish_decoding()));
+        assert_eq!(items[0]["name"], "foo");
+        assert_eq!(items[1]["id"], 2);
+    }
+
+    #[test]
+    fn json_frame_works() {
+        #[derive(Debug, PartialEq)]
+        struct Item {
+            id: u64,
+            name: String,
+        }
+        impl Serialize for Item {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut state = serializer.serialize_struct("Item", 2)?;
+                state.serialize_field("id", &self.id)?;
+                state.serialize_field("name", &self.name)?;
+                state.end()
+            }
+        }
+        impl<'de> Deserialize<'de> for Item {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                use serde::de::Error;
+
+                let value = Value::deserialize(deserializer)?;
+                let id = value["id"]
+                    .as_u64()
+                    .ok_or_else(|| D::Error::custom("no id"))?;
+                let name = value["name"]
+                    .as_str()
+                    .ok_or_else(|| D::Error::custom("no name"))?;
+                Ok(Item {
+                    id,
+                    name: name.to_owned(),
+                })
+            }
+        }
+        let item = Item {
+            id: 4,
+            name: "item\n4".to_owned(),
+        };
+
+        let bytes = track_try_unwrap!(JsonFrameEncoder::new().encode_into_bytes(item));
+        assert_eq!(&bytes[..4], [0, 0, 0, 25]);
+        assert_eq!(&bytes[4..], br#"{"id":4,"name":"item\n4"}"#);
+
+        let mut decoder = JsonFrameDecoder::<Item>::new();
+        let mut offset = 0;
+        for chunk in bytes.chunks(3) {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+        }
+        assert_eq!(offset, bytes.len());
+        assert_eq!(
+            track_try_unwrap!(decoder.finish_decoding()),
+            Item {
+                id: 4,
+                name: "item\n4".to_owned()
+            }
+        );
+    }
 }
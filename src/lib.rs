@@ -31,10 +31,20 @@
 //! [serde_json]: https://crates.io/crates/serde_json
 //! [trackable]: https://crates.io/crates/trackable
 #![warn(missing_docs)]
+#![cfg_attr(
+    test,
+    allow(
+        clippy::bool_assert_comparison,
+        clippy::err_expect,
+        clippy::neg_cmp_op_on_partial_ord
+    )
+)]
 
 #[cfg(feature = "bincode_codec")]
 extern crate bincode;
 extern crate byteorder;
+#[cfg(feature = "flate_codec")]
+extern crate flate2;
 #[cfg(feature = "serde")]
 extern crate serde;
 #[cfg(feature = "json_codec")]
@@ -43,7 +53,7 @@ extern crate serde_json;
 extern crate trackable;
 
 pub use byte_count::ByteCount;
-pub use decode::{Decode, DecodeExt, TaggedDecode, TryTaggedDecode};
+pub use decode::{Decode, DecodeExt, DecodeStatus, TaggedDecode, TryTaggedDecode};
 pub use encode::{Encode, EncodeExt, SizedEncode};
 pub use eos::Eos;
 pub use error::{Error, ErrorKind};
@@ -51,22 +61,40 @@ pub use error::{Error, ErrorKind};
 #[macro_use]
 mod macros;
 
+pub mod array;
 #[cfg(feature = "bincode_codec")]
 pub mod bincode_codec;
+pub mod bits;
 pub mod bytes;
+pub mod checksum;
 pub mod combinator;
+pub mod fixed_point;
 pub mod fixnum;
+#[cfg(feature = "flate_codec")]
+pub mod flate_codec;
+pub mod framing;
 pub mod io;
 #[cfg(feature = "tokio-async")]
 pub mod io_async;
 #[cfg(feature = "json_codec")]
 pub mod json_codec;
 pub mod marker;
+pub mod mask;
 pub mod monolithic;
+pub mod net;
 pub mod null;
 pub mod padding;
+pub mod path;
+pub mod protobuf;
+pub mod rle;
 pub mod slice;
+pub mod tagged;
+pub mod text;
+pub mod time;
 pub mod tuple;
+#[cfg(feature = "uuid")]
+pub mod uuid_codec;
+pub mod varint;
 
 mod byte_count;
 mod decode;
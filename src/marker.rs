@@ -1,5 +1,72 @@
 //! Marker types.
+use crate::{ByteCount, Decode, Eos, ErrorKind, Result};
 
 /// Never instantiated type.
 #[derive(Debug)]
 pub struct Never(());
+
+/// Decoder that never decodes any items.
+///
+/// Both `decode` and `finish_decoding` methods always return an `ErrorKind::Other` error.
+/// This is useful as a placeholder for reserved branches in tagged dispatch tables.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, Eos, ErrorKind};
+/// use bytecodec::marker::NeverDecoder;
+///
+/// let mut decoder = NeverDecoder::new();
+/// let error = decoder.decode(b"foo", Eos::new(false)).err().unwrap();
+/// assert_eq!(*error.kind(), ErrorKind::Other);
+/// ```
+#[derive(Debug, Default)]
+pub struct NeverDecoder;
+impl NeverDecoder {
+    /// Makes a new `NeverDecoder` instance.
+    pub fn new() -> Self {
+        NeverDecoder
+    }
+}
+impl Decode for NeverDecoder {
+    type Item = Never;
+
+    fn decode(&mut self, _buf: &[u8], _eos: Eos) -> Result<usize> {
+        track_panic!(ErrorKind::Other, "NeverDecoder cannot decode any items")
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_panic!(ErrorKind::Other, "NeverDecoder cannot decode any items")
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Unknown
+    }
+
+    fn is_idle(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tagged::StringTaggedDecoder;
+    use crate::{DecodeExt, TaggedDecode};
+
+    #[test]
+    fn never_decoder_works() {
+        let mut decoder = NeverDecoder::new();
+        let error = decoder.decode(b"", Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::Other);
+
+        let error = decoder.finish_decoding().err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::Other);
+
+        let mut decoder = StringTaggedDecoder::new();
+        decoder.register("reserved", NeverDecoder::new().map(|_| 0u8));
+        track_try_unwrap!(decoder.start_decoding("reserved".to_owned()));
+        let error = decoder.decode(b"foo", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::Other);
+    }
+}
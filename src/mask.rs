@@ -0,0 +1,275 @@
+//! Encoders and decoders that XOR-mask byte sequences (e.g., the payloads of WebSocket client frames).
+//!
+//! The `i`-th byte of an item is XORed with `key[i % key.len()]`.
+use crate::{ByteCount, Decode, Encode, Eos, Result, SizedEncode};
+use std::cmp;
+
+#[derive(Debug)]
+struct Mask {
+    key: Vec<u8>,
+    offset: usize,
+}
+impl Mask {
+    fn new(key: Vec<u8>) -> Self {
+        assert!(!key.is_empty(), "Empty masking key");
+        Mask { key, offset: 0 }
+    }
+
+    fn apply(&mut self, buf: &mut [u8]) {
+        self.apply_without_advancing(buf);
+        self.advance(buf.len());
+    }
+
+    fn apply_without_advancing(&self, buf: &mut [u8]) {
+        let key = self.key.iter().cycle().skip(self.offset);
+        for (b, k) in buf.iter_mut().zip(key) {
+            *b ^= k;
+        }
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.offset = (self.offset + n) % self.key.len();
+    }
+}
+
+/// Encoder that XOR-masks the bytes produced by the inner encoder.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bytes::BytesEncoder;
+/// use bytecodec::mask::MaskEncoder;
+///
+/// let mut encoder = MaskEncoder::new(BytesEncoder::new(), vec![0x01, 0x02]);
+/// let bytes = encoder.encode_into_bytes(b"\x00\x00\x00").unwrap();
+/// assert_eq!(bytes, [0x01, 0x02, 0x01]);
+/// ```
+#[derive(Debug)]
+pub struct MaskEncoder<E> {
+    inner: E,
+    mask: Mask,
+}
+impl<E> MaskEncoder<E> {
+    /// Makes a new `MaskEncoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is empty, this function will panic.
+    pub fn new<K: Into<Vec<u8>>>(inner: E, key: K) -> Self {
+        MaskEncoder {
+            inner,
+            mask: Mask::new(key.into()),
+        }
+    }
+
+    /// Returns the masking key.
+    pub fn key(&self) -> &[u8] {
+        &self.mask.key
+    }
+
+    /// Sets the masking key used for the subsequent items.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is empty, this method will panic.
+    pub fn set_key<K: Into<Vec<u8>>>(&mut self, key: K) {
+        self.mask = Mask::new(key.into());
+    }
+
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+impl<E: Encode> Encode for MaskEncoder<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let size = track!(self.inner.encode(buf, eos))?;
+        self.mask.apply(&mut buf[..size]);
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.inner.start_encoding(item))?;
+        self.mask.offset = 0;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+impl<E: SizedEncode> SizedEncode for MaskEncoder<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Decoder that unmasks the input bytes and passes them to the inner decoder.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::bytes::RemainingBytesDecoder;
+/// use bytecodec::mask::MaskDecoder;
+///
+/// let mut decoder = MaskDecoder::new(RemainingBytesDecoder::new(), vec![0x01, 0x02]);
+/// let item = decoder.decode_from_bytes(&[0x01, 0x02, 0x01][..]).unwrap();
+/// assert_eq!(item, [0, 0, 0]);
+/// ```
+#[derive(Debug)]
+pub struct MaskDecoder<D> {
+    inner: D,
+    mask: Mask,
+    buf: Vec<u8>,
+}
+impl<D> MaskDecoder<D> {
+    /// Makes a new `MaskDecoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is empty, this function will panic.
+    pub fn new<K: Into<Vec<u8>>>(inner: D, key: K) -> Self {
+        MaskDecoder {
+            inner,
+            mask: Mask::new(key.into()),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Returns the masking key.
+    pub fn key(&self) -> &[u8] {
+        &self.mask.key
+    }
+
+    /// Sets the masking key used for the subsequent items.
+    ///
+    /// # Panics
+    ///
+    /// If `key` is empty, this method will panic.
+    pub fn set_key<K: Into<Vec<u8>>>(&mut self, key: K) {
+        self.mask = Mask::new(key.into());
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Decode> Decode for MaskDecoder<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        // Only the bytes that the inner decoder can consume are unmasked (if known)
+        let limit = match self.inner.requiring_bytes() {
+            ByteCount::Finite(n) => cmp::min(n, buf.len() as u64) as usize,
+            _ => buf.len(),
+        };
+        let eos = eos.back((buf.len() - limit) as u64);
+        self.buf.clear();
+        self.buf.extend_from_slice(&buf[..limit]);
+
+        // The key offset is advanced only by the number of the bytes consumed by the inner decoder
+        self.mask.apply_without_advancing(&mut self.buf);
+        let size = track!(self.inner.decode(&self.buf, eos))?;
+        self.mask.advance(size);
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let item = track!(self.inner.finish_decoding())?;
+        self.mask.offset = 0;
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bytes::{Utf8Decoder, Utf8Encoder};
+    use crate::fixnum::U8Decoder;
+    use crate::io::IoEncodeExt;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn mask_works() {
+        let key = [0x37, 0xfa, 0x21, 0x3d];
+        let mut encoder = MaskEncoder::new(Utf8Encoder::new(), key);
+        track_try_unwrap!(encoder.start_encoding("Hello"));
+
+        // RFC 6455, 5.7. Examples
+        let mut masked = Vec::new();
+        let mut buf = [0; 2];
+        while !encoder.is_idle() {
+            let size = track_try_unwrap!(encoder.encode(&mut buf, Eos::new(false)));
+            masked.extend_from_slice(&buf[..size]);
+        }
+        assert_eq!(masked, [0x7f, 0x9f, 0x4d, 0x51, 0x58]);
+
+        let mut decoder = MaskDecoder::new(Utf8Decoder::new().length(5), key);
+        for chunk in masked.chunks(3) {
+            let size = track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+            assert_eq!(size, chunk.len());
+        }
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "Hello");
+
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("Hello"));
+        let mut output = Vec::new();
+        track_try_unwrap!(encoder.start_encoding("Hello"));
+        track_try_unwrap!(encoder.encode_all(&mut output));
+        assert_eq!(bytes, output);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&bytes)),
+            "Hello"
+        );
+    }
+
+    #[test]
+    fn mask_decoder_unmasks_only_required_bytes() {
+        let mut decoder = MaskDecoder::new(U8Decoder::new(), [0xff]);
+        let input = [0xfe; 1000];
+        let mut offset = 0;
+        let mut items = Vec::new();
+        while offset < input.len() {
+            offset += track_try_unwrap!(decoder.decode(&input[offset..], Eos::new(false)));
+            assert_eq!(decoder.buf.len(), 1);
+            items.push(track_try_unwrap!(decoder.finish_decoding()));
+        }
+        assert_eq!(items, vec![1; 1000]);
+    }
+}
@@ -15,11 +15,16 @@ pub trait MonolithicDecode {
 }
 
 /// Monolithic decoder that implements `Decode` trait.
+///
+/// The input bytes are buffered until the stream reaches EOS.
+/// By default the buffer size is unlimited, so it is recommended to set
+/// an upper limit (see `with_max_buffer_bytes`) when decoding untrusted input.
 #[derive(Debug, Default)]
 pub struct MonolithicDecoder<D: MonolithicDecode> {
     inner: D,
     item: Option<D::Item>,
     buf: Vec<u8>,
+    max_buffer_bytes: Option<usize>,
 }
 impl<D: MonolithicDecode> MonolithicDecoder<D> {
     /// Makes a new `MonolithicDecoder` instance.
@@ -28,9 +33,33 @@ impl<D: MonolithicDecode> MonolithicDecoder<D> {
             inner,
             item: None,
             buf: Vec::new(),
+            max_buffer_bytes: None,
+        }
+    }
+
+    /// Makes a new `MonolithicDecoder` instance that buffers at most `max_buffer_bytes` bytes.
+    ///
+    /// If the number of bytes of an item (including the bytes given at EOS) exceeds the limit,
+    /// an `ErrorKind::InvalidInput` error will be returned and the buffered bytes are discarded.
+    pub fn with_max_buffer_bytes(inner: D, max_buffer_bytes: usize) -> Self {
+        MonolithicDecoder {
+            max_buffer_bytes: Some(max_buffer_bytes),
+            ..Self::new(inner)
         }
     }
 
+    /// Returns the maximum number of bytes that can be buffered.
+    pub fn max_buffer_bytes(&self) -> Option<usize> {
+        self.max_buffer_bytes
+    }
+
+    /// Sets the maximum number of bytes that can be buffered.
+    ///
+    /// `None` means unlimited.
+    pub fn set_max_buffer_bytes(&mut self, max_buffer_bytes: Option<usize>) {
+        self.max_buffer_bytes = max_buffer_bytes;
+    }
+
     /// Returns a reference to the inner decoder.
     pub fn inner_ref(&self) -> &D {
         &self.inner
@@ -50,6 +79,14 @@ impl<D: MonolithicDecode> Decode for MonolithicDecoder<D> {
     type Item = D::Item;
 
     fn decode(&mut self, mut buf: &[u8], eos: Eos) -> Result<usize> {
+        if let Some(limit) = self.max_buffer_bytes {
+            let buffered = self.buf.len() + buf.len();
+            if buffered > limit {
+                self.buf.clear();
+                track_panic!(ErrorKind::InvalidInput, "Max buffer bytes limit exceeded";
+                             buffered, limit);
+            }
+        }
         if eos.is_reached() {
             let original_len = buf.len();
             let item = track!(
@@ -191,3 +228,40 @@ impl<A: Write, B: Write> Write for WriterChain<A, B> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct ReadToEnd;
+    impl MonolithicDecode for ReadToEnd {
+        type Item = Vec<u8>;
+
+        fn monolithic_decode<R: Read>(&self, mut reader: R) -> Result<Self::Item> {
+            let mut buf = Vec::new();
+            track!(reader.read_to_end(&mut buf).map_err(crate::Error::from))?;
+            Ok(buf)
+        }
+    }
+
+    #[test]
+    fn max_buffer_bytes_works() {
+        let mut decoder = MonolithicDecoder::with_max_buffer_bytes(ReadToEnd, 4);
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        track_try_unwrap!(decoder.decode(b"b", Eos::new(false)));
+        let error = decoder.decode(b"ar", Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        let error = decoder.decode(&[0; 100], Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        track_try_unwrap!(decoder.decode(b"foo", Eos::new(false)));
+        let error = decoder.decode(b"ba", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        track_try_unwrap!(decoder.decode(b"fo", Eos::new(false)));
+        track_try_unwrap!(decoder.decode(b"ob", Eos::new(true)));
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), b"foob");
+    }
+}
@@ -0,0 +1,295 @@
+//! Encoders and decoders for network addresses.
+//!
+//! Addresses and ports are represented in network byte order (big-endian).
+use crate::bytes::{BytesEncoder, CopyableBytesDecoder};
+use crate::fixnum::{U16beDecoder, U16beEncoder};
+use crate::tuple::{TupleDecoder, TupleEncoder};
+use crate::{ByteCount, Decode, Encode, Eos, Result, SizedEncode};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+macro_rules! impl_decode {
+    ($ty:ty, $item:ty) => {
+        impl Decode for $ty {
+            type Item = $item;
+
+            fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+                track!(self.0.decode(buf, eos))
+            }
+
+            fn finish_decoding(&mut self) -> Result<Self::Item> {
+                track!(self.0.finish_decoding()).map(Self::decode_item)
+            }
+
+            fn requiring_bytes(&self) -> ByteCount {
+                self.0.requiring_bytes()
+            }
+
+            fn is_idle(&self) -> bool {
+                self.0.is_idle()
+            }
+        }
+    };
+}
+
+macro_rules! impl_encode {
+    ($ty:ty, $item:ty) => {
+        impl Encode for $ty {
+            type Item = $item;
+
+            fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+                track!(self.0.encode(buf, eos))
+            }
+
+            fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+                track!(self.0.start_encoding(Self::encode_item(item)))
+            }
+
+            fn requiring_bytes(&self) -> ByteCount {
+                self.0.requiring_bytes()
+            }
+
+            fn is_idle(&self) -> bool {
+                self.0.is_idle()
+            }
+        }
+        impl SizedEncode for $ty {
+            fn exact_requiring_bytes(&self) -> u64 {
+                self.0.exact_requiring_bytes()
+            }
+        }
+    };
+}
+
+/// Decoder which decodes `Ipv4Addr` values (4 bytes).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::net::Ipv4AddrDecoder;
+/// use std::net::Ipv4Addr;
+///
+/// let mut decoder = Ipv4AddrDecoder::new();
+/// let item = decoder.decode_from_bytes(&[127, 0, 0, 1][..]).unwrap();
+/// assert_eq!(item, Ipv4Addr::LOCALHOST);
+/// ```
+#[derive(Debug, Default)]
+pub struct Ipv4AddrDecoder(CopyableBytesDecoder<[u8; 4]>);
+impl Ipv4AddrDecoder {
+    /// Makes a new `Ipv4AddrDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 4]) -> Ipv4Addr {
+        Ipv4Addr::from(b)
+    }
+}
+impl_decode!(Ipv4AddrDecoder, Ipv4Addr);
+
+/// Encoder which encodes `Ipv4Addr` values (4 bytes).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::net::Ipv4AddrEncoder;
+/// use std::net::Ipv4Addr;
+///
+/// let mut encoder = Ipv4AddrEncoder::new();
+/// let bytes = encoder.encode_into_bytes(Ipv4Addr::LOCALHOST).unwrap();
+/// assert_eq!(bytes, [127, 0, 0, 1]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Ipv4AddrEncoder(BytesEncoder<[u8; 4]>);
+impl Ipv4AddrEncoder {
+    /// Makes a new `Ipv4AddrEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn encode_item(addr: Ipv4Addr) -> [u8; 4] {
+        addr.octets()
+    }
+}
+impl_encode!(Ipv4AddrEncoder, Ipv4Addr);
+
+/// Decoder which decodes `Ipv6Addr` values (16 bytes).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::net::Ipv6AddrDecoder;
+/// use std::net::Ipv6Addr;
+///
+/// let mut decoder = Ipv6AddrDecoder::new();
+/// let item = decoder.decode_from_bytes(&Ipv6Addr::LOCALHOST.octets()[..]).unwrap();
+/// assert_eq!(item, Ipv6Addr::LOCALHOST);
+/// ```
+#[derive(Debug, Default)]
+pub struct Ipv6AddrDecoder(CopyableBytesDecoder<[u8; 16]>);
+impl Ipv6AddrDecoder {
+    /// Makes a new `Ipv6AddrDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item(b: [u8; 16]) -> Ipv6Addr {
+        Ipv6Addr::from(b)
+    }
+}
+impl_decode!(Ipv6AddrDecoder, Ipv6Addr);
+
+/// Encoder which encodes `Ipv6Addr` values (16 bytes).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::net::Ipv6AddrEncoder;
+/// use std::net::Ipv6Addr;
+///
+/// let mut encoder = Ipv6AddrEncoder::new();
+/// let bytes = encoder.encode_into_bytes(Ipv6Addr::LOCALHOST).unwrap();
+/// assert_eq!(bytes, Ipv6Addr::LOCALHOST.octets());
+/// ```
+#[derive(Debug, Default)]
+pub struct Ipv6AddrEncoder(BytesEncoder<[u8; 16]>);
+impl Ipv6AddrEncoder {
+    /// Makes a new `Ipv6AddrEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn encode_item(addr: Ipv6Addr) -> [u8; 16] {
+        addr.octets()
+    }
+}
+impl_encode!(Ipv6AddrEncoder, Ipv6Addr);
+
+/// Decoder which decodes `SocketAddrV4` values (4 bytes address followed by 2 bytes port).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::net::SocketAddrV4Decoder;
+/// use std::net::{Ipv4Addr, SocketAddrV4};
+///
+/// let mut decoder = SocketAddrV4Decoder::new();
+/// let item = decoder.decode_from_bytes(&[127, 0, 0, 1, 0x1f, 0x90][..]).unwrap();
+/// assert_eq!(item, SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080));
+/// ```
+#[derive(Debug, Default)]
+pub struct SocketAddrV4Decoder(TupleDecoder<(Ipv4AddrDecoder, U16beDecoder)>);
+impl SocketAddrV4Decoder {
+    /// Makes a new `SocketAddrV4Decoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item((ip, port): (Ipv4Addr, u16)) -> SocketAddrV4 {
+        SocketAddrV4::new(ip, port)
+    }
+}
+impl_decode!(SocketAddrV4Decoder, SocketAddrV4);
+
+/// Encoder which encodes `SocketAddrV4` values (4 bytes address followed by 2 bytes port).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::net::SocketAddrV4Encoder;
+/// use std::net::{Ipv4Addr, SocketAddrV4};
+///
+/// let mut encoder = SocketAddrV4Encoder::new();
+/// let bytes = encoder.encode_into_bytes(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080)).unwrap();
+/// assert_eq!(bytes, [127, 0, 0, 1, 0x1f, 0x90]);
+/// ```
+#[derive(Debug, Default)]
+pub struct SocketAddrV4Encoder(TupleEncoder<(Ipv4AddrEncoder, U16beEncoder)>);
+impl SocketAddrV4Encoder {
+    /// Makes a new `SocketAddrV4Encoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn encode_item(addr: SocketAddrV4) -> (Ipv4Addr, u16) {
+        (*addr.ip(), addr.port())
+    }
+}
+impl_encode!(SocketAddrV4Encoder, SocketAddrV4);
+
+/// Decoder which decodes `SocketAddrV6` values (16 bytes address followed by 2 bytes port).
+///
+/// The flow information and the scope identifier of the decoded addresses are always `0`.
+#[derive(Debug, Default)]
+pub struct SocketAddrV6Decoder(TupleDecoder<(Ipv6AddrDecoder, U16beDecoder)>);
+impl SocketAddrV6Decoder {
+    /// Makes a new `SocketAddrV6Decoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn decode_item((ip, port): (Ipv6Addr, u16)) -> SocketAddrV6 {
+        SocketAddrV6::new(ip, port, 0, 0)
+    }
+}
+impl_decode!(SocketAddrV6Decoder, SocketAddrV6);
+
+/// Encoder which encodes `SocketAddrV6` values (16 bytes address followed by 2 bytes port).
+///
+/// The flow information and the scope identifier of the addresses are not encoded.
+#[derive(Debug, Default)]
+pub struct SocketAddrV6Encoder(TupleEncoder<(Ipv6AddrEncoder, U16beEncoder)>);
+impl SocketAddrV6Encoder {
+    /// Makes a new `SocketAddrV6Encoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn encode_item(addr: SocketAddrV6) -> (Ipv6Addr, u16) {
+        (*addr.ip(), addr.port())
+    }
+}
+impl_encode!(SocketAddrV6Encoder, SocketAddrV6);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn ip_addr_works() {
+        let addr = Ipv4Addr::new(192, 168, 0, 1);
+        let bytes = track_try_unwrap!(Ipv4AddrEncoder::new().encode_into_bytes(addr));
+        assert_eq!(bytes, [192, 168, 0, 1]);
+        let item = track_try_unwrap!(Ipv4AddrDecoder::new().decode_from_bytes(&bytes));
+        assert_eq!(item, addr);
+
+        let addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let bytes = track_try_unwrap!(Ipv6AddrEncoder::new().encode_into_bytes(addr));
+        assert_eq!(bytes[..4], [0x20, 0x01, 0x0d, 0xb8]);
+        assert_eq!(bytes[15], 1);
+        let item = track_try_unwrap!(Ipv6AddrDecoder::new().decode_from_bytes(&bytes));
+        assert_eq!(item, addr);
+    }
+
+    #[test]
+    fn socket_addr_works() {
+        let addr = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 443);
+        let bytes = track_try_unwrap!(SocketAddrV4Encoder::new().encode_into_bytes(addr));
+        assert_eq!(bytes, [10, 0, 0, 1, 0x01, 0xbb]);
+        let item = track_try_unwrap!(SocketAddrV4Decoder::new().decode_from_bytes(&bytes));
+        assert_eq!(item, addr);
+
+        let addr = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0);
+        let bytes = track_try_unwrap!(SocketAddrV6Encoder::new().encode_into_bytes(addr));
+        assert_eq!(bytes.len(), 18);
+        assert_eq!(bytes[16..], [0x1f, 0x90]);
+        let item = track_try_unwrap!(SocketAddrV6Decoder::new().decode_from_bytes(&bytes));
+        assert_eq!(item, addr);
+    }
+}
@@ -1,5 +1,5 @@
 //! Null decoder and encoder.
-use crate::{ByteCount, Decode, Encode, Eos, Result, SizedEncode};
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
 
 /// Null decoder.
 ///
@@ -26,6 +26,64 @@ impl Decode for NullDecoder {
     }
 }
 
+/// Decoder which asserts that the input stream has no bytes.
+///
+/// `AssertEmptyDecoder` consumes no bytes and waits for the stream to reach EOS.
+/// If any byte is given before EOS, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, ErrorKind};
+/// use bytecodec::null::AssertEmptyDecoder;
+///
+/// let mut decoder = AssertEmptyDecoder::new();
+/// assert_eq!(decoder.decode_from_bytes(b"").unwrap(), ());
+///
+/// let error = decoder.decode_from_bytes(b"foo").err().unwrap();
+/// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+/// ```
+#[derive(Debug, Default)]
+pub struct AssertEmptyDecoder {
+    eos: bool,
+}
+impl AssertEmptyDecoder {
+    /// Makes a new `AssertEmptyDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for AssertEmptyDecoder {
+    type Item = ();
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track_assert!(buf.is_empty(), ErrorKind::InvalidInput, "Unexpected bytes";
+                      buf.len());
+        if eos.is_reached() {
+            self.eos = true;
+        }
+        Ok(0)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.eos, ErrorKind::IncompleteDecoding);
+        self.eos = false;
+        Ok(())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.eos
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.eos {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Infinite
+        }
+    }
+}
+
 /// Null encoder.
 ///
 /// `NullEncoder` produces no bytes.
@@ -68,6 +126,19 @@ mod test {
         assert_eq!(decoder.finish_decoding().ok(), Some(()));
     }
 
+    #[test]
+    fn assert_empty_decoder_works() {
+        let mut decoder = AssertEmptyDecoder::new();
+        assert_eq!(decoder.decode(&[][..], Eos::new(false)).ok(), Some(0));
+        assert!(!decoder.is_idle());
+        assert_eq!(decoder.decode(&[][..], Eos::new(true)).ok(), Some(0));
+        assert_eq!(decoder.finish_decoding().ok(), Some(()));
+
+        let error = decoder.decode(&[0][..], Eos::new(true)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert!(decoder.finish_decoding().is_err());
+    }
+
     #[test]
     fn null_encoder_works() {
         let mut encoder = NullEncoder;
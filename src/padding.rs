@@ -1,5 +1,6 @@
 //! Encoder and decoder for padding bytes.
-use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result};
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use std::cmp;
 
 /// Decoder for reading padding bytes from input streams.
 ///
@@ -118,10 +119,192 @@ impl Encode for PaddingEncoder {
     }
 }
 
+/// Decoder for reading the padding bytes that align a position to a boundary.
+///
+/// The absolute offset (e.g., relative to the start of a message) of the current position
+/// has to be supplied via `set_offset` method before decoding the padding.
+/// Then, `AlignDecoder` consumes the bytes up to the next multiple of `align` and
+/// verifies each of them is equal to the padding byte (`0` by default).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, Eos};
+/// use bytecodec::padding::AlignDecoder;
+///
+/// let mut decoder = AlignDecoder::new(4);
+/// decoder.set_offset(5);
+/// assert_eq!(decoder.decode(&[0; 8][..], Eos::new(false)).unwrap(), 3);
+/// assert!(decoder.finish_decoding().is_ok());
+/// ```
+#[derive(Debug)]
+pub struct AlignDecoder {
+    align: u64,
+    padding_byte: u8,
+    remaining_bytes: u64,
+}
+impl AlignDecoder {
+    /// Makes a new `AlignDecoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `align` is `0`, this function will panic.
+    pub fn new(align: u64) -> Self {
+        assert_ne!(align, 0);
+        AlignDecoder {
+            align,
+            padding_byte: 0,
+            remaining_bytes: 0,
+        }
+    }
+
+    /// Returns the alignment boundary.
+    pub fn align(&self) -> u64 {
+        self.align
+    }
+
+    /// Returns the expected padding byte.
+    pub fn padding_byte(&self) -> u8 {
+        self.padding_byte
+    }
+
+    /// Sets the expected padding byte.
+    pub fn set_padding_byte(&mut self, b: u8) {
+        self.padding_byte = b;
+    }
+
+    /// Sets the absolute offset of the current position.
+    ///
+    /// This determines the number of padding bytes to be consumed.
+    pub fn set_offset(&mut self, offset: u64) {
+        self.remaining_bytes = padding_size(offset, self.align);
+    }
+}
+impl Decode for AlignDecoder {
+    type Item = ();
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let size = cmp::min(buf.len() as u64, self.remaining_bytes) as usize;
+        for &b in &buf[..size] {
+            track_assert_eq!(b, self.padding_byte, ErrorKind::InvalidInput);
+        }
+        self.remaining_bytes -= size as u64;
+        if self.remaining_bytes != 0 {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.remaining_bytes);
+        }
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert_eq!(self.remaining_bytes, 0, ErrorKind::IncompleteDecoding);
+        Ok(())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.remaining_bytes == 0
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.remaining_bytes)
+    }
+}
+
+/// Encoder for writing the padding bytes that align a position to a boundary.
+///
+/// The input item is the absolute offset (e.g., relative to the start of a message)
+/// of the current position, and `AlignEncoder` writes the padding bytes (`0` by default)
+/// up to the next multiple of `align`.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::padding::AlignEncoder;
+///
+/// let mut encoder = AlignEncoder::new(4);
+/// assert_eq!(encoder.encode_into_bytes(5).unwrap(), [0; 3]);
+/// assert!(encoder.encode_into_bytes(8).unwrap().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct AlignEncoder {
+    align: u64,
+    padding_byte: u8,
+    remaining_bytes: u64,
+}
+impl AlignEncoder {
+    /// Makes a new `AlignEncoder` instance.
+    ///
+    /// # Panics
+    ///
+    /// If `align` is `0`, this function will panic.
+    pub fn new(align: u64) -> Self {
+        assert_ne!(align, 0);
+        AlignEncoder {
+            align,
+            padding_byte: 0,
+            remaining_bytes: 0,
+        }
+    }
+
+    /// Returns the alignment boundary.
+    pub fn align(&self) -> u64 {
+        self.align
+    }
+
+    /// Returns the padding byte.
+    pub fn padding_byte(&self) -> u8 {
+        self.padding_byte
+    }
+
+    /// Sets the padding byte.
+    pub fn set_padding_byte(&mut self, b: u8) {
+        self.padding_byte = b;
+    }
+}
+impl Encode for AlignEncoder {
+    type Item = u64;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let size = cmp::min(buf.len() as u64, self.remaining_bytes) as usize;
+        for b in &mut buf[..size] {
+            *b = self.padding_byte;
+        }
+        self.remaining_bytes -= size as u64;
+        if self.remaining_bytes != 0 {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.remaining_bytes);
+        }
+        Ok(size)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        self.remaining_bytes = padding_size(item, self.align);
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.remaining_bytes)
+    }
+
+    fn is_idle(&self) -> bool {
+        self.remaining_bytes == 0
+    }
+}
+impl SizedEncode for AlignEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.remaining_bytes
+    }
+}
+
+fn padding_size(offset: u64, align: u64) -> u64 {
+    (align - offset % align) % align
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::io::IoDecodeExt;
+    use crate::fixnum::{U24beDecoder, U24beEncoder};
+    use crate::io::{IoDecodeExt, IoEncodeExt};
     use crate::{Encode, EncodeExt, Eos};
 
     #[test]
@@ -142,4 +325,29 @@ mod test {
         assert!(decoder.decode_exact(&[1; 8][..]).is_ok());
         assert!(decoder.decode_exact(&[0; 8][..]).is_err());
     }
+
+    #[test]
+    fn align_works() {
+        let mut encoder = AlignEncoder::new(4);
+        let mut output = track_try_unwrap!(U24beEncoder::new().encode_into_bytes(0x123456));
+        track_try_unwrap!(encoder.start_encoding(output.len() as u64));
+        track_try_unwrap!(encoder.encode_all(&mut output));
+        output.push(7);
+        assert_eq!(output, [0x12, 0x34, 0x56, 0, 7]);
+
+        let mut input = &output[..];
+        let mut decoder = AlignDecoder::new(4);
+        assert_eq!(
+            track_try_unwrap!(U24beDecoder::new().decode_exact(&mut input)),
+            0x123456
+        );
+        decoder.set_offset(3);
+        track_try_unwrap!(decoder.decode_exact(&mut input));
+        assert_eq!(input, [7]);
+
+        decoder.set_padding_byte(0xFF);
+        decoder.set_offset(3);
+        let error = decoder.decode(&[0], Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
 }
@@ -0,0 +1,158 @@
+//! Encoders and decoders for file system paths.
+//!
+//! A path is represented as a UTF-8 string prefixed with its length (4 bytes, big-endian).
+//!
+//! Note that paths are not guaranteed to be valid UTF-8 on some platforms
+//! (e.g., arbitrary bytes on Unix and ill-formed UTF-16 on Windows).
+//! Such paths cannot be encoded by `PathEncoder`.
+use crate::bytes::{Utf8Decoder, Utf8Encoder};
+use crate::framing::{Endianness, VarWidthLengthPrefixDecoder, VarWidthLengthPrefixEncoder};
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use std::path::PathBuf;
+
+const LENGTH_WIDTH: usize = 4;
+
+/// Decoder which decodes `PathBuf` values.
+///
+/// # Errors
+///
+/// If the path is not a valid UTF-8 string, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::path::PathDecoder;
+/// use std::path::PathBuf;
+///
+/// let mut decoder = PathDecoder::new();
+/// let item = decoder.decode_from_bytes(b"\x00\x00\x00\x07foo/bar").unwrap();
+/// assert_eq!(item, PathBuf::from("foo/bar"));
+/// ```
+#[derive(Debug)]
+pub struct PathDecoder(VarWidthLengthPrefixDecoder<Utf8Decoder>);
+impl PathDecoder {
+    /// Makes a new `PathDecoder` instance.
+    pub fn new() -> Self {
+        PathDecoder(VarWidthLengthPrefixDecoder::new(
+            Utf8Decoder::new(),
+            LENGTH_WIDTH,
+            Endianness::Big,
+        ))
+    }
+}
+impl Default for PathDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Decode for PathDecoder {
+    type Item = PathBuf;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.0.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track!(self.0.finish_decoding()).map(PathBuf::from)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+
+/// Encoder which encodes `PathBuf` values.
+///
+/// # Errors
+///
+/// If the path is not a valid UTF-8 string, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::path::PathEncoder;
+/// use std::path::PathBuf;
+///
+/// let mut encoder = PathEncoder::new();
+/// let bytes = encoder.encode_into_bytes(PathBuf::from("foo/bar")).unwrap();
+/// assert_eq!(bytes, b"\x00\x00\x00\x07foo/bar");
+/// ```
+#[derive(Debug)]
+pub struct PathEncoder(VarWidthLengthPrefixEncoder<Utf8Encoder>);
+impl PathEncoder {
+    /// Makes a new `PathEncoder` instance.
+    pub fn new() -> Self {
+        PathEncoder(VarWidthLengthPrefixEncoder::new(
+            Utf8Encoder::new(),
+            LENGTH_WIDTH,
+            Endianness::Big,
+        ))
+    }
+}
+impl Default for PathEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Encode for PathEncoder {
+    type Item = PathBuf;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let s = track_assert_some!(
+            item.to_str(),
+            ErrorKind::InvalidInput,
+            "Non UTF-8 path";
+            item
+        );
+        track!(self.0.start_encoding(s.to_owned()))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for PathEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn path_works() {
+        let path = PathBuf::from("src").join("lib.rs");
+        let bytes = track_try_unwrap!(PathEncoder::new().encode_into_bytes(path.clone()));
+        assert_eq!(&bytes[..4], [0, 0, 0, 10]);
+        let item = track_try_unwrap!(PathDecoder::new().decode_from_bytes(&bytes));
+        assert_eq!(item, path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn path_encoder_rejects_non_utf8_paths() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = PathBuf::from(OsStr::from_bytes(b"foo\xff"));
+        let error = PathEncoder::new().encode_into_bytes(path).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,162 @@
+//! Encoders and decoders for [Protocol Buffers] primitives.
+//!
+//! [Protocol Buffers]: https://protobuf.dev/programming-guides/encoding/
+use crate::varint::{VarintDecoder, VarintEncoder};
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+
+/// The maximum value of field numbers.
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// The maximum value of wire types.
+pub const MAX_WIRE_TYPE: u8 = 5;
+
+/// Decoder which decodes the tag (i.e., the header) of a field.
+///
+/// A tag is a varint of `(field_number << 3) | wire_type`.
+/// The decoded item is a tuple of the field number and the wire type.
+///
+/// # Errors
+///
+/// If the wire type is not in the range `0..=5` or
+/// the field number is not in the range `1..=2^29-1`,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::protobuf::ProtobufTagDecoder;
+///
+/// let mut decoder = ProtobufTagDecoder::new();
+/// let item = decoder.decode_from_bytes(&[0x0a][..]).unwrap();
+/// assert_eq!(item, (1, 2));
+/// ```
+#[derive(Debug, Default)]
+pub struct ProtobufTagDecoder(VarintDecoder);
+impl ProtobufTagDecoder {
+    /// Makes a new `ProtobufTagDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for ProtobufTagDecoder {
+    type Item = (u32, u8);
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.0.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let value = track!(self.0.finish_decoding())?;
+        let field_number = value >> 3;
+        let wire_type = (value & 0b111) as u8;
+        track_assert!(wire_type <= MAX_WIRE_TYPE, ErrorKind::InvalidInput,
+                      "Unknown wire type"; wire_type);
+        track_assert!(
+            (1..=u64::from(MAX_FIELD_NUMBER)).contains(&field_number),
+            ErrorKind::InvalidInput,
+            "Invalid field number";
+            field_number
+        );
+        Ok((field_number as u32, wire_type))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+
+/// Encoder which encodes the tag (i.e., the header) of a field.
+///
+/// The input item is a tuple of the field number and the wire type.
+///
+/// # Errors
+///
+/// If the wire type is not in the range `0..=5` or the field number is not in the range `1..=2^29-1`,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::protobuf::ProtobufTagEncoder;
+///
+/// let mut encoder = ProtobufTagEncoder::new();
+/// let bytes = encoder.encode_into_bytes((1, 2)).unwrap();
+/// assert_eq!(bytes, [0x0a]);
+/// ```
+#[derive(Debug, Default)]
+pub struct ProtobufTagEncoder(VarintEncoder);
+impl ProtobufTagEncoder {
+    /// Makes a new `ProtobufTagEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for ProtobufTagEncoder {
+    type Item = (u32, u8);
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, (field_number, wire_type): Self::Item) -> Result<()> {
+        track_assert!(wire_type <= MAX_WIRE_TYPE, ErrorKind::InvalidInput,
+                      "Unknown wire type"; wire_type);
+        track_assert!(
+            (1..=MAX_FIELD_NUMBER).contains(&field_number),
+            ErrorKind::InvalidInput,
+            "Invalid field number";
+            field_number
+        );
+        let value = (u64::from(field_number) << 3) | u64::from(wire_type);
+        track!(self.0.start_encoding(value))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for ProtobufTagEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn protobuf_tag_works() {
+        let mut decoder = ProtobufTagDecoder::new();
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&[0x0a])),
+            (1, 2)
+        );
+
+        let mut encoder = ProtobufTagEncoder::new();
+        for &item in &[(1, 0), (16, 5), (MAX_FIELD_NUMBER, 2)] {
+            let bytes = track_try_unwrap!(encoder.encode_into_bytes(item));
+            assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), item);
+        }
+        assert_eq!(
+            track_try_unwrap!(encoder.encode_into_bytes((16, 0))),
+            [0x80, 0x01]
+        );
+
+        let error = decoder.decode_from_bytes(&[0x0e]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        assert!(encoder.encode_into_bytes((1, 6)).is_err());
+        assert!(encoder.encode_into_bytes((0, 0)).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Encoder and decoder for run-length encoded byte sequences.
+//!
+//! A run-length encoded byte sequence consists of `(count, value)` byte pairs.
+//! Each pair represents `count` repetitions of `value`.
+use crate::bytes::BytesEncoder;
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use std::mem;
+
+/// Decoder which expands run-length encoded `(count, value)` pairs until it reaches EOS.
+///
+/// If the input reaches EOS in the middle of a pair, an `ErrorKind::UnexpectedEos` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::rle::RleDecoder;
+///
+/// let mut decoder = RleDecoder::new();
+/// let item = decoder.decode_from_bytes(&[3, b'a', 1, b'b'][..]).unwrap();
+/// assert_eq!(item, b"aaab");
+/// ```
+#[derive(Debug, Default)]
+pub struct RleDecoder {
+    buf: Vec<u8>,
+    count: Option<u8>,
+    eos: bool,
+}
+impl RleDecoder {
+    /// Makes a new `RleDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for RleDecoder {
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.eos {
+            return Ok(0);
+        }
+
+        for &b in buf {
+            if let Some(count) = self.count.take() {
+                self.buf.resize(self.buf.len() + usize::from(count), b);
+            } else {
+                self.count = Some(b);
+            }
+        }
+        if eos.is_reached() {
+            track_assert!(self.count.is_none(), ErrorKind::UnexpectedEos; self.count);
+            self.eos = true;
+        }
+        Ok(buf.len())
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.eos, ErrorKind::IncompleteDecoding);
+        self.eos = false;
+        Ok(mem::take(&mut self.buf))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.eos {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Infinite
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.eos
+    }
+}
+
+/// Encoder which compresses byte sequences into run-length encoded `(count, value)` pairs.
+///
+/// Runs longer than `255` bytes are split into multiple pairs.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::rle::RleEncoder;
+///
+/// let mut encoder = RleEncoder::new();
+/// let bytes = encoder.encode_into_bytes(b"aaab".to_vec()).unwrap();
+/// assert_eq!(bytes, [3, b'a', 1, b'b']);
+/// ```
+#[derive(Debug, Default)]
+pub struct RleEncoder(BytesEncoder<Vec<u8>>);
+impl RleEncoder {
+    /// Makes a new `RleEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for RleEncoder {
+    type Item = Vec<u8>;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let mut runs: Vec<u8> = Vec::new();
+        for b in item {
+            match runs.len() {
+                n if n >= 2 && runs[n - 1] == b && runs[n - 2] < 255 => runs[n - 2] += 1,
+                _ => runs.extend_from_slice(&[1, b]),
+            }
+        }
+        track!(self.0.start_encoding(runs))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for RleEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn rle_works() {
+        let mut input = b"abbccc".to_vec();
+        input.extend_from_slice(&[b'd'; 300]);
+
+        let mut encoder = RleEncoder::new();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(input.clone()));
+        assert_eq!(bytes, [1, b'a', 2, b'b', 3, b'c', 255, b'd', 45, b'd']);
+
+        let mut decoder = RleDecoder::new();
+        let mut offset = 0;
+        for chunk in bytes.chunks(3) {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+        }
+        track_try_unwrap!(decoder.decode(&[], Eos::new(true)));
+        assert_eq!(offset, bytes.len());
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), input);
+
+        let error = decoder.decode_from_bytes(&[1, b'a', 2]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::UnexpectedEos);
+    }
+}
@@ -0,0 +1,518 @@
+//! Encoders and decoders for tagged items.
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, TaggedDecode, TryTaggedDecode};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Decoder that dispatches to one of the registered decoders by a string tag.
+///
+/// This is useful for decoding unions keyed by a name (e.g., Thrift or Avro unions).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, DecodeExt, TaggedDecode};
+/// use bytecodec::fixnum::{U16beDecoder, U8Decoder};
+/// use bytecodec::tagged::StringTaggedDecoder;
+///
+/// let mut decoder = StringTaggedDecoder::new();
+/// decoder.register("byte", U8Decoder::new().map(u32::from));
+/// decoder.register("short", U16beDecoder::new().map(u32::from));
+///
+/// decoder.start_decoding("short".to_owned()).unwrap();
+/// assert_eq!(decoder.decode_from_bytes(&[1, 2][..]).unwrap(), 0x0102);
+///
+/// assert!(decoder.start_decoding("long".to_owned()).is_err());
+/// ```
+pub struct StringTaggedDecoder<T> {
+    branches: HashMap<String, Box<dyn Decode<Item = T>>>,
+    current: Option<String>,
+}
+impl<T> StringTaggedDecoder<T> {
+    /// Makes a new `StringTaggedDecoder` instance that has no branches.
+    pub fn new() -> Self {
+        StringTaggedDecoder {
+            branches: HashMap::new(),
+            current: None,
+        }
+    }
+
+    /// Registers the decoder used for decoding items tagged by `name`.
+    ///
+    /// If a decoder has already been registered with the same name, it will be replaced.
+    pub fn register<S, D>(&mut self, name: S, decoder: D)
+    where
+        S: Into<String>,
+        D: Decode<Item = T> + 'static,
+    {
+        self.branches.insert(name.into(), Box::new(decoder));
+    }
+
+    /// Returns `true` if a decoder has been registered with the given name, otherwise `false`.
+    pub fn contains_tag(&self, name: &str) -> bool {
+        self.branches.contains_key(name)
+    }
+
+    /// Returns the tag of the item being decoded.
+    pub fn current_tag(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    fn current_branch(&self) -> Option<&dyn Decode<Item = T>> {
+        self.current
+            .as_ref()
+            .and_then(|tag| self.branches.get(tag))
+            .map(|d| &**d)
+    }
+}
+impl<T> Default for StringTaggedDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> fmt::Debug for StringTaggedDecoder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "StringTaggedDecoder {{ tags: {:?}, current: {:?} }}",
+            self.branches.keys().collect::<Vec<_>>(),
+            self.current
+        )
+    }
+}
+impl<T> Decode for StringTaggedDecoder<T> {
+    type Item = T;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if let Some(ref tag) = self.current {
+            let decoder = track_assert_some!(
+                self.branches.get_mut(tag),
+                ErrorKind::InconsistentState;
+                tag
+            );
+            track!(decoder.decode(buf, eos); tag)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let tag = track_assert_some!(self.current.take(), ErrorKind::IncompleteDecoding);
+        let decoder = track_assert_some!(
+            self.branches.get_mut(&tag),
+            ErrorKind::InconsistentState;
+            tag
+        );
+        track!(decoder.finish_decoding(); tag)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.current_branch()
+            .map_or(ByteCount::Unknown, Decode::requiring_bytes)
+    }
+
+    fn is_idle(&self) -> bool {
+        self.current_branch().is_some_and(Decode::is_idle)
+    }
+}
+impl<T> TaggedDecode for StringTaggedDecoder<T> {
+    type Tag = String;
+
+    fn start_decoding(&mut self, tag: Self::Tag) -> Result<()> {
+        track_assert!(self.current.is_none(), ErrorKind::IncompleteDecoding);
+        track_assert!(self.branches.contains_key(&tag), ErrorKind::InvalidInput; tag);
+        self.current = Some(tag);
+        Ok(())
+    }
+}
+impl<T> TryTaggedDecode for StringTaggedDecoder<T> {
+    type Tag = String;
+
+    fn try_start_decoding(&mut self, tag: Self::Tag) -> Result<bool> {
+        track_assert!(self.current.is_none(), ErrorKind::IncompleteDecoding);
+        if !self.branches.contains_key(&tag) {
+            return Ok(false);
+        }
+        self.current = Some(tag);
+        Ok(true)
+    }
+}
+
+/// Decoder that converts integer discriminants into enum values.
+///
+/// The discriminants are decoded by the inner decoder and mapped by the given function.
+/// If the function returns `None`, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{DecodeExt, ErrorKind};
+/// use bytecodec::fixnum::U8Decoder;
+/// use bytecodec::tagged::EnumDecoder;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     Green,
+/// }
+///
+/// let mut decoder = EnumDecoder::new(U8Decoder::new(), |n| match n {
+///     0 => Some(Color::Red),
+///     1 => Some(Color::Green),
+///     _ => None,
+/// });
+/// assert_eq!(decoder.decode_from_bytes(&[1][..]).unwrap(), Color::Green);
+///
+/// let error = decoder.decode_from_bytes(&[2][..]).err().unwrap();
+/// assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+/// ```
+#[derive(Debug)]
+pub struct EnumDecoder<D, T, F> {
+    inner: D,
+    map: F,
+    _item: PhantomData<T>,
+}
+impl<D, T, F> EnumDecoder<D, T, F>
+where
+    D: Decode,
+    D::Item: Into<u64>,
+    F: Fn(u64) -> Option<T>,
+{
+    /// Makes a new `EnumDecoder` instance.
+    pub fn new(inner: D, f: F) -> Self {
+        EnumDecoder {
+            inner,
+            map: f,
+            _item: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D, T, F> Decode for EnumDecoder<D, T, F>
+where
+    D: Decode,
+    D::Item: Into<u64>,
+    F: Fn(u64) -> Option<T>,
+{
+    type Item = T;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let n = track!(self.inner.finish_decoding())?.into();
+        let item = track_assert_some!((self.map)(n), ErrorKind::InvalidInput,
+                                      "Unknown discriminant"; n);
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+
+/// Encoder that writes the tag of each item followed by the body encoded by the branch for the tag.
+///
+/// This is the encoding side of tagged unions.
+/// The tag of an item is extracted by the given function and encoded by the tag encoder,
+/// then the item itself is encoded by the body encoder registered for the tag.
+///
+/// # Errors
+///
+/// If no body encoder has been registered for the tag of an item,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::fixnum::{U16beEncoder, U8Encoder};
+/// use bytecodec::tagged::TaggedEncoder;
+///
+/// let mut encoder = TaggedEncoder::new(U8Encoder::new(), |n: &u16| if *n < 0x100 { 0 } else { 1 });
+/// encoder.register(0, U8Encoder::new().map_from(|n: u16| n as u8));
+/// encoder.register(1, U16beEncoder::new());
+///
+/// assert_eq!(encoder.encode_into_bytes(0x12).unwrap(), [0, 0x12]);
+/// assert_eq!(encoder.encode_into_bytes(0x1234).unwrap(), [1, 0x12, 0x34]);
+/// ```
+pub struct TaggedEncoder<E: Encode, T, F> {
+    tag: E,
+    tag_of: F,
+    branches: HashMap<E::Item, Box<dyn Encode<Item = T>>>,
+    current: Option<E::Item>,
+}
+impl<E, T, F> TaggedEncoder<E, T, F>
+where
+    E: Encode,
+    E::Item: Eq + Hash + Clone,
+    F: Fn(&T) -> E::Item,
+{
+    /// Makes a new `TaggedEncoder` instance that has no branches.
+    ///
+    /// `tag_of` is used for extracting the tag of each item.
+    pub fn new(tag_encoder: E, tag_of: F) -> Self {
+        TaggedEncoder {
+            tag: tag_encoder,
+            tag_of,
+            branches: HashMap::new(),
+            current: None,
+        }
+    }
+
+    /// Registers the encoder used for encoding the bodies of items tagged by `tag`.
+    ///
+    /// If an encoder has already been registered with the same tag, it will be replaced.
+    pub fn register<B>(&mut self, tag: E::Item, encoder: B)
+    where
+        B: Encode<Item = T> + 'static,
+    {
+        self.branches.insert(tag, Box::new(encoder));
+    }
+
+    /// Returns `true` if an encoder has been registered with the given tag, otherwise `false`.
+    pub fn contains_tag(&self, tag: &E::Item) -> bool {
+        self.branches.contains_key(tag)
+    }
+
+    /// Returns a reference to the tag encoder.
+    pub fn tag_encoder_ref(&self) -> &E {
+        &self.tag
+    }
+
+    /// Returns a mutable reference to the tag encoder.
+    pub fn tag_encoder_mut(&mut self) -> &mut E {
+        &mut self.tag
+    }
+}
+impl<E: Encode, T, F> TaggedEncoder<E, T, F>
+where
+    E::Item: Eq + Hash,
+{
+    fn current_branch(&self) -> Option<&dyn Encode<Item = T>> {
+        self.current
+            .as_ref()
+            .and_then(|tag| self.branches.get(tag))
+            .map(|e| &**e)
+    }
+}
+impl<E, T, F> fmt::Debug for TaggedEncoder<E, T, F>
+where
+    E: Encode + fmt::Debug,
+    E::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TaggedEncoder {{ tag: {:?}, tags: {:?}, current: {:?} }}",
+            self.tag,
+            self.branches.keys().collect::<Vec<_>>(),
+            self.current
+        )
+    }
+}
+impl<E, T, F> Encode for TaggedEncoder<E, T, F>
+where
+    E: Encode,
+    E::Item: Eq + Hash + Clone + fmt::Debug,
+    F: Fn(&T) -> E::Item,
+{
+    type Item = T;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        bytecodec_try_encode!(self.tag, offset, buf, eos);
+        if let Some(ref tag) = self.current {
+            let encoder = track_assert_some!(
+                self.branches.get_mut(tag),
+                ErrorKind::InconsistentState;
+                tag
+            );
+            offset += track!(encoder.encode(&mut buf[offset..], eos); tag)?;
+        }
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        let tag = (self.tag_of)(&item);
+        let encoder = track_assert_some!(
+            self.branches.get_mut(&tag),
+            ErrorKind::InvalidInput,
+            "Unknown tag";
+            tag
+        );
+        track!(self.tag.start_encoding(tag.clone()))?;
+        track!(encoder.start_encoding(item); tag)?;
+        self.current = Some(tag);
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        let body = self
+            .current_branch()
+            .map_or(ByteCount::Finite(0), Encode::requiring_bytes);
+        self.tag.requiring_bytes().add_for_encoding(body)
+    }
+
+    // `Option::is_none_or` requires Rust 1.82
+    #[allow(clippy::unnecessary_map_or)]
+    fn is_idle(&self) -> bool {
+        self.tag.is_idle() && self.current_branch().map_or(true, Encode::is_idle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bytes::Utf8Decoder;
+    use crate::fixnum::{U16beDecoder, U16beEncoder, U8Decoder, U8Encoder};
+    use crate::io::{IoDecodeExt, IoEncodeExt};
+    use crate::{DecodeExt, EncodeExt};
+    use trackable::error::ErrorKindExt;
+
+    #[test]
+    fn string_tagged_decoder_works() {
+        let mut decoder = StringTaggedDecoder::new();
+        decoder.register("byte", U8Decoder::new().map(u32::from));
+        decoder.register("short", U16beDecoder::new().map(u32::from));
+
+        let mut input = &b"\x04byte\x07\x05short\x01\x02"[..];
+        let mut name_decoder = U8Decoder::new().and_then(|n| Utf8Decoder::new().length(n.into()));
+
+        let name = track_try_unwrap!(name_decoder.decode_exact(&mut input));
+        track_try_unwrap!(decoder.start_decoding(name));
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), 7);
+
+        let name = track_try_unwrap!(name_decoder.decode_exact(&mut input));
+        track_try_unwrap!(decoder.start_decoding(name));
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), 0x0102);
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn string_tagged_decoder_rejects_unknown_tag() {
+        let mut decoder = StringTaggedDecoder::<u8>::new();
+        decoder.register("byte", U8Decoder::new());
+
+        let error = decoder.start_decoding("short".to_owned()).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn enum_decoder_works() {
+        #[derive(Debug, PartialEq)]
+        enum Kind {
+            A,
+            B,
+            C,
+        }
+
+        let mut decoder = EnumDecoder::new(U8Decoder::new(), |n| match n {
+            0 => Some(Kind::A),
+            1 => Some(Kind::B),
+            2 => Some(Kind::C),
+            _ => None,
+        });
+        let mut input = &[2, 0, 1, 3][..];
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), Kind::C);
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), Kind::A);
+        assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), Kind::B);
+
+        let error = decoder.decode_exact(&mut input).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn tagged_encoder_works() {
+        #[derive(Debug, Clone, PartialEq)]
+        enum Shape {
+            Circle(u8),
+            Rect(u16, u16),
+        }
+
+        let mut encoder = TaggedEncoder::new(U8Encoder::new(), |shape: &Shape| match shape {
+            Shape::Circle(_) => 0,
+            Shape::Rect(..) => 1,
+        });
+        encoder.register(
+            0,
+            U8Encoder::new().try_map_from(|shape| match shape {
+                Shape::Circle(r) => Ok(r),
+                _ => Err(ErrorKind::InvalidInput.error()),
+            }),
+        );
+        encoder.register(
+            1,
+            U16beEncoder::new()
+                .chain(U16beEncoder::new())
+                .try_map_from(|shape| match shape {
+                    Shape::Rect(w, h) => Ok((w, h)),
+                    _ => Err(ErrorKind::InvalidInput.error()),
+                }),
+        );
+
+        let mut decoder = U8Decoder::new().and_then(|tag| match tag {
+            0 => U8Decoder::new().map(Shape::Circle).boxed(),
+            _ => U16beDecoder::new()
+                .chain(U16beDecoder::new())
+                .map(|(w, h)| Shape::Rect(w, h))
+                .boxed(),
+        });
+
+        let shapes = [Shape::Circle(3), Shape::Rect(0x100, 2)];
+        let mut bytes = Vec::new();
+        for shape in shapes.iter().cloned() {
+            track_try_unwrap!(encoder.start_encoding(shape));
+            track_try_unwrap!(encoder.encode_all(&mut bytes));
+        }
+        assert_eq!(bytes, [0, 3, 1, 1, 0, 0, 2]);
+
+        let mut input = &bytes[..];
+        for shape in &shapes {
+            assert_eq!(track_try_unwrap!(decoder.decode_exact(&mut input)), *shape);
+        }
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn tagged_encoder_does_not_start_body_if_tag_fails() {
+        let tag_encoder = U8Encoder::new().try_map_from(|tag: u8| {
+            if tag < 0x80 {
+                Ok(tag)
+            } else {
+                Err(ErrorKind::InvalidInput.error())
+            }
+        });
+        let mut encoder = TaggedEncoder::new(tag_encoder, |n: &u8| *n);
+        encoder.register(0, U8Encoder::new());
+        encoder.register(0xff, U8Encoder::new());
+
+        assert!(encoder.start_encoding(0xff).is_err());
+        assert!(encoder.is_idle());
+        assert!(encoder.branches[&0xff].is_idle());
+
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(0));
+        assert_eq!(bytes, [0, 0]);
+    }
+}
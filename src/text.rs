@@ -0,0 +1,747 @@
+//! Encoders and decoders for text related values.
+use crate::fixnum::{U32beDecoder, U32beEncoder};
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use std::cmp;
+
+/// Decoder which decodes unsigned integers expressed as ASCII digits (e.g., `b"123"`).
+///
+/// The digits are terminated by the first byte that is not a digit of the radix (or by EOS).
+/// The terminating byte is not consumed by this decoder,
+/// so it can be handled by a subsequent decoder (e.g., `b"\r\n"` in HTTP chunked encoding).
+///
+/// # Errors
+///
+/// If there are no digits or the value overflows `u64`,
+/// an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::{Decode, Eos};
+/// use bytecodec::text::AsciiUintDecoder;
+///
+/// let mut decoder = AsciiUintDecoder::new();
+/// assert_eq!(decoder.decode(b"12", Eos::new(false)).unwrap(), 2);
+/// assert_eq!(decoder.decode(b"3\r\n", Eos::new(false)).unwrap(), 1);
+/// assert_eq!(decoder.finish_decoding().unwrap(), 123);
+///
+/// let mut decoder = AsciiUintDecoder::with_radix(16);
+/// assert_eq!(decoder.decode(b"1aF;", Eos::new(false)).unwrap(), 3);
+/// assert_eq!(decoder.finish_decoding().unwrap(), 0x1af);
+/// ```
+#[derive(Debug)]
+pub struct AsciiUintDecoder {
+    radix: u32,
+    value: u64,
+    digits: usize,
+    terminated: bool,
+}
+impl AsciiUintDecoder {
+    /// Makes a new `AsciiUintDecoder` instance which decodes decimal digits.
+    pub fn new() -> Self {
+        Self::with_radix(10)
+    }
+
+    /// Makes a new `AsciiUintDecoder` instance which decodes digits of the given radix.
+    ///
+    /// Letters used as digits (if `radix > 10`) are case insensitive.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in the range of `2..=36`, this function will panic.
+    pub fn with_radix(radix: u32) -> Self {
+        assert!((2..=36).contains(&radix), "Unsupported radix: {}", radix);
+        AsciiUintDecoder {
+            radix,
+            value: 0,
+            digits: 0,
+            terminated: false,
+        }
+    }
+
+    /// Returns the radix of the digits.
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+}
+impl Decode for AsciiUintDecoder {
+    type Item = u64;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.terminated {
+            return Ok(0);
+        }
+        for (i, &b) in buf.iter().enumerate() {
+            if let Some(digit) = (b as char).to_digit(self.radix) {
+                let value = self
+                    .value
+                    .checked_mul(u64::from(self.radix))
+                    .and_then(|v| v.checked_add(u64::from(digit)));
+                self.value = track_assert_some!(value, ErrorKind::InvalidInput, "Too large value");
+                self.digits += 1;
+            } else {
+                track_assert_ne!(self.digits, 0, ErrorKind::InvalidInput, "No digits");
+                self.terminated = true;
+                return Ok(i);
+            }
+        }
+        if eos.is_reached() {
+            track_assert_ne!(self.digits, 0, ErrorKind::InvalidInput, "No digits");
+            self.terminated = true;
+        }
+        Ok(buf.len())
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.terminated, ErrorKind::IncompleteDecoding);
+        let value = self.value;
+        self.value = 0;
+        self.digits = 0;
+        self.terminated = false;
+        Ok(value)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.terminated {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.terminated
+    }
+}
+impl Default for AsciiUintDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decoder which decodes unsigned integers stored in fixed-width padded ASCII fields.
+///
+/// This kind of fields are used in legacy formats (e.g., COBOL records and tar headers).
+/// Leading and trailing spaces (and NUL bytes, as used by tar) are trimmed before parsing.
+/// Leading zeros are allowed.
+///
+/// # Errors
+///
+/// If the trimmed field contains a byte that is not a digit of the radix
+/// or the value overflows `u64`, an `ErrorKind::InvalidInput` error will be returned.
+/// An empty (or all-space) field is also an error unless `set_empty_as_zero(true)` is called.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::text::PaddedAsciiNumberDecoder;
+///
+/// let mut decoder = PaddedAsciiNumberDecoder::new(6);
+/// assert_eq!(decoder.decode_from_bytes(b"  123 ").unwrap(), 123);
+///
+/// let mut decoder = PaddedAsciiNumberDecoder::with_radix(12, 8);
+/// assert_eq!(decoder.decode_from_bytes(b"00000001750\0").unwrap(), 1000);
+/// ```
+#[derive(Debug)]
+pub struct PaddedAsciiNumberDecoder {
+    width: usize,
+    radix: u32,
+    empty_as_zero: bool,
+    field: Vec<u8>,
+}
+impl PaddedAsciiNumberDecoder {
+    /// Makes a new `PaddedAsciiNumberDecoder` instance which decodes `width` bytes decimal fields.
+    pub fn new(width: usize) -> Self {
+        Self::with_radix(width, 10)
+    }
+
+    /// Makes a new `PaddedAsciiNumberDecoder` instance which decodes `width` bytes fields
+    /// consisting of digits of the given radix.
+    ///
+    /// Letters used as digits (if `radix > 10`) are case insensitive.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in the range of `2..=36`, this function will panic.
+    pub fn with_radix(width: usize, radix: u32) -> Self {
+        assert!((2..=36).contains(&radix), "Unsupported radix: {}", radix);
+        PaddedAsciiNumberDecoder {
+            width,
+            radix,
+            empty_as_zero: false,
+            field: Vec::with_capacity(width),
+        }
+    }
+
+    /// Returns the width (in bytes) of the fields.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the radix of the digits.
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// Returns `true` if empty (or all-space) fields are decoded as `0`, otherwise `false`.
+    pub fn empty_as_zero(&self) -> bool {
+        self.empty_as_zero
+    }
+
+    /// Sets whether empty (or all-space) fields are decoded as `0`.
+    ///
+    /// The default value is `false` (i.e., such fields are rejected).
+    pub fn set_empty_as_zero(&mut self, enabled: bool) {
+        self.empty_as_zero = enabled;
+    }
+
+    fn parse(&self) -> Result<u64> {
+        let is_padding = |b: &u8| *b == b' ' || *b == 0;
+        let start = self
+            .field
+            .iter()
+            .position(|b| !is_padding(b))
+            .unwrap_or(self.field.len());
+        let end = self
+            .field
+            .iter()
+            .rposition(|b| !is_padding(b))
+            .map_or(start, |i| i + 1);
+        let digits = &self.field[start..end];
+        if digits.is_empty() {
+            track_assert!(self.empty_as_zero, ErrorKind::InvalidInput, "Empty field");
+            return Ok(0);
+        }
+
+        let mut value = 0u64;
+        for &b in digits {
+            let digit = track_assert_some!(
+                (b as char).to_digit(self.radix),
+                ErrorKind::InvalidInput,
+                "Not a digit";
+                b, self.radix
+            );
+            let next = value
+                .checked_mul(u64::from(self.radix))
+                .and_then(|v| v.checked_add(u64::from(digit)));
+            value = track_assert_some!(next, ErrorKind::InvalidInput, "Too large value");
+        }
+        Ok(value)
+    }
+}
+impl Decode for PaddedAsciiNumberDecoder {
+    type Item = u64;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let size = cmp::min(buf.len(), self.width - self.field.len());
+        self.field.extend_from_slice(&buf[..size]);
+        if self.field.len() < self.width {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+        }
+        Ok(size)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert_eq!(self.field.len(), self.width, ErrorKind::IncompleteDecoding);
+        let result = track!(self.parse());
+        self.field.clear();
+        result
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite((self.width - self.field.len()) as u64)
+    }
+
+    fn is_idle(&self) -> bool {
+        self.field.len() == self.width
+    }
+}
+
+/// Decoder which decodes `char` values represented as 4-byte big-endian Unicode scalar values.
+///
+/// Surrogate code points and values above `0x10FFFF` are rejected
+/// with an `ErrorKind::InvalidInput` error.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::text::CharDecoder;
+///
+/// let mut decoder = CharDecoder::new();
+/// assert_eq!(decoder.decode_from_bytes(&[0, 0, 0, 0x61][..]).unwrap(), 'a');
+/// assert!(decoder.decode_from_bytes(&[0, 0x11, 0, 0][..]).is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct CharDecoder(U32beDecoder);
+impl CharDecoder {
+    /// Makes a new `CharDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for CharDecoder {
+    type Item = char;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.0.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let n = track!(self.0.finish_decoding())?;
+        let c = track_assert_some!(
+            char::from_u32(n),
+            ErrorKind::InvalidInput,
+            "Not a Unicode scalar value";
+            n
+        );
+        Ok(c)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+
+/// Encoder which encodes `char` values as 4-byte big-endian Unicode scalar values.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::text::CharEncoder;
+///
+/// let mut encoder = CharEncoder::new();
+/// assert_eq!(encoder.encode_into_bytes('a').unwrap(), [0, 0, 0, 0x61]);
+/// ```
+#[derive(Debug, Default)]
+pub struct CharEncoder(U32beEncoder);
+impl CharEncoder {
+    /// Makes a new `CharEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for CharEncoder {
+    type Item = char;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.0.start_encoding(u32::from(item)))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for CharEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+/// Byte order mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bom {
+    /// UTF-8 BOM (`EF BB BF`).
+    Utf8,
+
+    /// UTF-16 big-endian BOM (`FE FF`).
+    Utf16Be,
+
+    /// UTF-16 little-endian BOM (`FF FE`).
+    Utf16Le,
+}
+impl Bom {
+    /// Returns the byte sequence of the BOM.
+    pub fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Bom::Utf8 => &[0xef, 0xbb, 0xbf],
+            Bom::Utf16Be => &[0xfe, 0xff],
+            Bom::Utf16Le => &[0xff, 0xfe],
+        }
+    }
+}
+
+/// Encoder which emits a BOM before the bytes produced by the inner encoder.
+///
+/// The BOM is emitted once for each item.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::bytes::Utf8Encoder;
+/// use bytecodec::text::{Bom, BomEncoder};
+///
+/// let mut encoder = BomEncoder::new(Utf8Encoder::new(), Bom::Utf8);
+/// let bytes = encoder.encode_into_bytes("foo".to_owned()).unwrap();
+/// assert_eq!(bytes, b"\xef\xbb\xbffoo");
+/// ```
+#[derive(Debug)]
+pub struct BomEncoder<E> {
+    inner: E,
+    bom: Bom,
+    bom_offset: usize,
+}
+impl<E> BomEncoder<E> {
+    /// Makes a new `BomEncoder` instance.
+    pub fn new(inner: E, bom: Bom) -> Self {
+        BomEncoder {
+            inner,
+            bom,
+            bom_offset: bom.as_bytes().len(),
+        }
+    }
+
+    /// Returns the BOM emitted by this encoder.
+    pub fn bom(&self) -> Bom {
+        self.bom
+    }
+
+    /// Returns a reference to the inner encoder.
+    pub fn inner_ref(&self) -> &E {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner encoder.
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner encoder.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    fn bom_remaining(&self) -> &'static [u8] {
+        &self.bom.as_bytes()[self.bom_offset..]
+    }
+}
+impl<E: Default> Default for BomEncoder<E> {
+    fn default() -> Self {
+        Self::new(E::default(), Bom::Utf8)
+    }
+}
+impl<E: Encode> Encode for BomEncoder<E> {
+    type Item = E::Item;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        let bom = self.bom_remaining();
+        let mut offset = cmp::min(buf.len(), bom.len());
+        buf[..offset].copy_from_slice(&bom[..offset]);
+        self.bom_offset += offset;
+        if !self.bom_remaining().is_empty() {
+            track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos);
+            return Ok(offset);
+        }
+
+        bytecodec_try_encode!(self.inner, offset, buf, eos);
+        Ok(offset)
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track_assert!(self.is_idle(), ErrorKind::EncoderFull);
+        track!(self.inner.start_encoding(item))?;
+        self.bom_offset = 0;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.bom_remaining().len() as u64)
+            .add_for_encoding(self.inner.requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        self.bom_remaining().is_empty() && self.inner.is_idle()
+    }
+}
+impl<E: SizedEncode> SizedEncode for BomEncoder<E> {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.bom_remaining().len() as u64 + self.inner.exact_requiring_bytes()
+    }
+}
+
+/// Decoder which strips a leading BOM (if present) before passing the bytes to the inner decoder.
+///
+/// If an item does not start with the BOM, all of its bytes are passed to the inner decoder as they are.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::bytes::Utf8Decoder;
+/// use bytecodec::text::{Bom, BomDecoder};
+///
+/// let mut decoder = BomDecoder::new(Utf8Decoder::new(), Bom::Utf8);
+/// assert_eq!(decoder.decode_from_bytes(b"\xef\xbb\xbffoo").unwrap(), "foo");
+/// assert!(decoder.has_bom());
+///
+/// assert_eq!(decoder.decode_from_bytes(b"bar").unwrap(), "bar");
+/// assert!(!decoder.has_bom());
+/// ```
+#[derive(Debug)]
+pub struct BomDecoder<D> {
+    inner: D,
+    bom: Bom,
+    prefix: Vec<u8>,
+    prefix_offset: usize,
+    checked: bool,
+    has_bom: bool,
+}
+impl<D> BomDecoder<D> {
+    /// Makes a new `BomDecoder` instance.
+    pub fn new(inner: D, bom: Bom) -> Self {
+        BomDecoder {
+            inner,
+            bom,
+            prefix: Vec::new(),
+            prefix_offset: 0,
+            checked: false,
+            has_bom: false,
+        }
+    }
+
+    /// Returns the BOM stripped by this decoder.
+    pub fn bom(&self) -> Bom {
+        self.bom
+    }
+
+    /// Returns `true` if the current (or the last decoded) item started with the BOM, otherwise `false`.
+    pub fn has_bom(&self) -> bool {
+        self.has_bom
+    }
+
+    /// Returns a reference to the inner decoder.
+    pub fn inner_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner decoder.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Takes ownership of this instance and returns the inner decoder.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+impl<D: Default> Default for BomDecoder<D> {
+    fn default() -> Self {
+        Self::new(D::default(), Bom::Utf8)
+    }
+}
+impl<D: Decode> Decode for BomDecoder<D> {
+    type Item = D::Item;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        let mut offset = 0;
+        if !self.checked {
+            if self.prefix.is_empty() {
+                self.has_bom = false;
+            }
+            let bom = self.bom.as_bytes();
+            while offset < buf.len()
+                && self.prefix.len() < bom.len()
+                && buf[offset] == bom[self.prefix.len()]
+            {
+                self.prefix.push(buf[offset]);
+                offset += 1;
+            }
+            if self.prefix.len() == bom.len() {
+                self.prefix.clear();
+                self.has_bom = true;
+            } else if offset == buf.len() && !eos.is_reached() {
+                return Ok(offset);
+            }
+            self.checked = true;
+        }
+
+        if self.prefix_offset < self.prefix.len() {
+            // Passes the bytes that were mistaken for a part of the BOM to the inner decoder.
+            let prefix_eos = eos.back((buf.len() - offset) as u64);
+            self.prefix_offset += track!(self
+                .inner
+                .decode(&self.prefix[self.prefix_offset..], prefix_eos))?;
+            if self.prefix_offset < self.prefix.len() {
+                return Ok(offset);
+            }
+        }
+
+        offset += track!(self.inner.decode(&buf[offset..], eos))?;
+        Ok(offset)
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.checked, ErrorKind::IncompleteDecoding);
+        let item = track!(self.inner.finish_decoding())?;
+        if self.prefix_offset == self.prefix.len() {
+            self.prefix.clear();
+            self.prefix_offset = 0;
+            self.checked = false;
+        }
+        Ok(item)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.checked && self.prefix_offset == self.prefix.len() {
+            self.inner.requiring_bytes()
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.checked && self.inner.is_idle()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bytes::{RemainingBytesDecoder, Utf8Decoder, Utf8Encoder};
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn ascii_uint_decoder_works() {
+        let mut decoder = AsciiUintDecoder::new();
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(b"123\r", Eos::new(false))),
+            3
+        );
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), 123);
+
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(b"45")), 45);
+
+        let error = decoder.decode(b"\r\n", Eos::new(false)).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn ascii_uint_decoder_rejects_overflow() {
+        let mut decoder = AsciiUintDecoder::new();
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(b"18446744073709551615")),
+            u64::MAX
+        );
+
+        let error = decoder
+            .decode(b"18446744073709551616", Eos::new(true))
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        let mut decoder = AsciiUintDecoder::with_radix(16);
+        let error = decoder
+            .decode(b"10000000000000000", Eos::new(true))
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn char_codec_works() {
+        let mut encoder = CharEncoder::new();
+        let mut decoder = CharDecoder::new();
+        for &(c, bytes) in &[
+            ('\u{3042}', [0, 0, 0x30, 0x42]),
+            ('\u{1f600}', [0, 0x01, 0xf6, 0x00]),
+        ] {
+            assert_eq!(track_try_unwrap!(encoder.encode_into_bytes(c)), bytes);
+            assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), c);
+        }
+
+        let error = decoder
+            .decode_from_bytes(&[0, 0, 0xd8, 0x00])
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn bom_codec_works() {
+        let mut encoder = BomEncoder::new(Utf8Encoder::new(), Bom::Utf8);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes("foo".to_owned()));
+        assert_eq!(bytes, b"\xef\xbb\xbffoo");
+
+        let mut decoder = BomDecoder::new(Utf8Decoder::new(), Bom::Utf8);
+        let mut offset = 0;
+        for chunk in bytes.chunks(2) {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+        }
+        track_try_unwrap!(decoder.decode(&[], Eos::new(true)));
+        assert_eq!(offset, bytes.len());
+        assert!(decoder.has_bom());
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), "foo");
+    }
+
+    #[test]
+    fn bom_decoder_passes_through_items_without_bom() {
+        let mut decoder = BomDecoder::new(Utf8Decoder::new(), Bom::Utf8);
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(b"bar")), "bar");
+        assert!(!decoder.has_bom());
+
+        // A partial BOM followed by other bytes.
+        let mut offset = 0;
+        for chunk in [&b"\xef\xbb"[..], b"x"].iter() {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+        }
+        track_try_unwrap!(decoder.decode(&[], Eos::new(true)));
+        assert_eq!(offset, 3);
+        assert!(!decoder.has_bom());
+        let error = decoder.finish_decoding().err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+
+        let mut decoder = BomDecoder::new(RemainingBytesDecoder::new(), Bom::Utf16Le);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(b"\xff")),
+            b"\xff"
+        );
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(b"")), b"");
+    }
+
+    #[test]
+    fn padded_ascii_number_decoder_works() {
+        let mut decoder = PaddedAsciiNumberDecoder::new(8);
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"   00042"));
+        assert_eq!(item, 42);
+        let item = track_try_unwrap!(decoder.decode_from_bytes(b"  987   "));
+        assert_eq!(item, 987);
+
+        let error = decoder.decode_from_bytes(b"        ").err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+        decoder.set_empty_as_zero(true);
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(b"        ")), 0);
+
+        let error = decoder.decode_from_bytes(b"  1 2   ").err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn padded_ascii_number_decoder_decodes_tar_size_fields() {
+        let mut decoder = PaddedAsciiNumberDecoder::with_radix(12, 8);
+        let mut offset = 0;
+        let input = b"00000012345\0";
+        for chunk in input.chunks(5) {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+        }
+        assert_eq!(offset, input.len());
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), 0o12345);
+
+        let error = decoder.decode_from_bytes(b"00000012348\0").err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,372 @@
+//! Encoders and decoders for time related types.
+use crate::fixnum::{U32beDecoder, U32beEncoder, U64beDecoder, U64beEncoder};
+use crate::tuple::{TupleDecoder, TupleEncoder};
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Binary representation of durations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationFormat {
+    /// Big-endian 64-bit unsigned integer of seconds.
+    ///
+    /// The sub-second part of a duration is truncated when encoding.
+    Secs,
+
+    /// Big-endian 64-bit unsigned integer of milliseconds.
+    ///
+    /// The sub-millisecond part of a duration is truncated when encoding.
+    Millis,
+
+    /// Big-endian 64-bit unsigned integer of seconds followed by
+    /// big-endian 32-bit unsigned integer of nanoseconds (less than `1_000_000_000`).
+    SecsNanos,
+}
+
+/// Decoder which decodes `Duration` values.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::time::{DurationDecoder, DurationFormat};
+/// use std::time::Duration;
+///
+/// let mut decoder = DurationDecoder::new(DurationFormat::Millis);
+/// let item = decoder.decode_from_bytes(&[0, 0, 0, 0, 0, 0, 0x04, 0xd2][..]).unwrap();
+/// assert_eq!(item, Duration::from_millis(1234));
+/// ```
+#[derive(Debug)]
+pub struct DurationDecoder {
+    format: DurationFormat,
+    integer: U64beDecoder,
+    secs_nanos: TupleDecoder<(U64beDecoder, U32beDecoder)>,
+}
+impl DurationDecoder {
+    /// Makes a new `DurationDecoder` instance.
+    pub fn new(format: DurationFormat) -> Self {
+        DurationDecoder {
+            format,
+            integer: U64beDecoder::new(),
+            secs_nanos: TupleDecoder::new((U64beDecoder::new(), U32beDecoder::new())),
+        }
+    }
+
+    /// Returns the representation of the decoded durations.
+    pub fn format(&self) -> DurationFormat {
+        self.format
+    }
+}
+impl Decode for DurationDecoder {
+    type Item = Duration;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        match self.format {
+            DurationFormat::Secs | DurationFormat::Millis => track!(self.integer.decode(buf, eos)),
+            DurationFormat::SecsNanos => track!(self.secs_nanos.decode(buf, eos)),
+        }
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        match self.format {
+            DurationFormat::Secs => {
+                let secs = track!(self.integer.finish_decoding())?;
+                Ok(Duration::from_secs(secs))
+            }
+            DurationFormat::Millis => {
+                let millis = track!(self.integer.finish_decoding())?;
+                Ok(Duration::from_millis(millis))
+            }
+            DurationFormat::SecsNanos => {
+                let (secs, nanos) = track!(self.secs_nanos.finish_decoding())?;
+                track_assert!(nanos < NANOS_PER_SEC, ErrorKind::InvalidInput; nanos);
+                Ok(Duration::new(secs, nanos))
+            }
+        }
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        match self.format {
+            DurationFormat::Secs | DurationFormat::Millis => self.integer.requiring_bytes(),
+            DurationFormat::SecsNanos => self.secs_nanos.requiring_bytes(),
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        match self.format {
+            DurationFormat::Secs | DurationFormat::Millis => self.integer.is_idle(),
+            DurationFormat::SecsNanos => self.secs_nanos.is_idle(),
+        }
+    }
+}
+
+/// Encoder which encodes `Duration` values.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::time::{DurationEncoder, DurationFormat};
+/// use std::time::Duration;
+///
+/// let mut encoder = DurationEncoder::new(DurationFormat::SecsNanos);
+/// let bytes = encoder.encode_into_bytes(Duration::new(1, 5)).unwrap();
+/// assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 5]);
+/// ```
+#[derive(Debug)]
+pub struct DurationEncoder {
+    format: DurationFormat,
+    integer: U64beEncoder,
+    secs_nanos: TupleEncoder<(U64beEncoder, U32beEncoder)>,
+}
+impl DurationEncoder {
+    /// Makes a new `DurationEncoder` instance.
+    pub fn new(format: DurationFormat) -> Self {
+        DurationEncoder {
+            format,
+            integer: U64beEncoder::new(),
+            secs_nanos: TupleEncoder::new((U64beEncoder::new(), U32beEncoder::new())),
+        }
+    }
+
+    /// Returns the representation of the encoded durations.
+    pub fn format(&self) -> DurationFormat {
+        self.format
+    }
+}
+impl Encode for DurationEncoder {
+    type Item = Duration;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        match self.format {
+            DurationFormat::Secs | DurationFormat::Millis => track!(self.integer.encode(buf, eos)),
+            DurationFormat::SecsNanos => track!(self.secs_nanos.encode(buf, eos)),
+        }
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        match self.format {
+            DurationFormat::Secs => track!(self.integer.start_encoding(item.as_secs())),
+            DurationFormat::Millis => {
+                let millis = track_assert_some!(
+                    u64::try_from(item.as_millis()).ok(),
+                    ErrorKind::InvalidInput;
+                    item
+                );
+                track!(self.integer.start_encoding(millis))
+            }
+            DurationFormat::SecsNanos => {
+                let item = (item.as_secs(), item.subsec_nanos());
+                track!(self.secs_nanos.start_encoding(item))
+            }
+        }
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        ByteCount::Finite(self.exact_requiring_bytes())
+    }
+
+    fn is_idle(&self) -> bool {
+        match self.format {
+            DurationFormat::Secs | DurationFormat::Millis => self.integer.is_idle(),
+            DurationFormat::SecsNanos => self.secs_nanos.is_idle(),
+        }
+    }
+}
+impl SizedEncode for DurationEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        match self.format {
+            DurationFormat::Secs | DurationFormat::Millis => self.integer.exact_requiring_bytes(),
+            DurationFormat::SecsNanos => self.secs_nanos.exact_requiring_bytes(),
+        }
+    }
+}
+
+/// Decoder which decodes `SystemTime` values represented as durations since the Unix epoch.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::time::{DurationFormat, UnixTimestampDecoder};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let mut decoder = UnixTimestampDecoder::new(DurationFormat::Secs);
+/// let item = decoder.decode_from_bytes(&[0, 0, 0, 0, 0x5a, 0x4f, 0xc2, 0x00][..]).unwrap();
+/// assert_eq!(item, UNIX_EPOCH + Duration::from_secs(1_515_176_448));
+/// ```
+#[derive(Debug)]
+pub struct UnixTimestampDecoder(DurationDecoder);
+impl UnixTimestampDecoder {
+    /// Makes a new `UnixTimestampDecoder` instance.
+    pub fn new(format: DurationFormat) -> Self {
+        UnixTimestampDecoder(DurationDecoder::new(format))
+    }
+
+    /// Returns the representation of the decoded timestamps.
+    pub fn format(&self) -> DurationFormat {
+        self.0.format()
+    }
+}
+impl Decode for UnixTimestampDecoder {
+    type Item = SystemTime;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.0.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let duration = track!(self.0.finish_decoding())?;
+        let time = track_assert_some!(
+            UNIX_EPOCH.checked_add(duration),
+            ErrorKind::InvalidInput,
+            "Unrepresentable timestamp";
+            duration
+        );
+        Ok(time)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+
+/// Encoder which encodes `SystemTime` values as durations since the Unix epoch.
+///
+/// Times before the Unix epoch are rejected with an `ErrorKind::InvalidInput` error.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::time::{DurationFormat, UnixTimestampEncoder};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let mut encoder = UnixTimestampEncoder::new(DurationFormat::Secs);
+/// let bytes = encoder.encode_into_bytes(UNIX_EPOCH + Duration::from_secs(1_515_176_448)).unwrap();
+/// assert_eq!(bytes, [0, 0, 0, 0, 0x5a, 0x4f, 0xc2, 0x00]);
+///
+/// assert!(encoder.encode_into_bytes(UNIX_EPOCH - Duration::from_secs(1)).is_err());
+/// ```
+#[derive(Debug)]
+pub struct UnixTimestampEncoder(DurationEncoder);
+impl UnixTimestampEncoder {
+    /// Makes a new `UnixTimestampEncoder` instance.
+    pub fn new(format: DurationFormat) -> Self {
+        UnixTimestampEncoder(DurationEncoder::new(format))
+    }
+
+    /// Returns the representation of the encoded timestamps.
+    pub fn format(&self) -> DurationFormat {
+        self.0.format()
+    }
+}
+impl Encode for UnixTimestampEncoder {
+    type Item = SystemTime;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let duration = track_assert_some!(
+            item.duration_since(UNIX_EPOCH).ok(),
+            ErrorKind::InvalidInput,
+            "Time before the Unix epoch";
+            item
+        );
+        track!(self.0.start_encoding(duration))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for UnixTimestampEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn duration_works() {
+        for &format in &[DurationFormat::Millis, DurationFormat::SecsNanos] {
+            let mut encoder = DurationEncoder::new(format);
+            let mut decoder = DurationDecoder::new(format);
+            for &duration in &[
+                Duration::from_millis(0),
+                Duration::from_millis(250),
+                Duration::from_millis(123_456_789),
+            ] {
+                let bytes = track_try_unwrap!(encoder.encode_into_bytes(duration));
+                assert_eq!(
+                    track_try_unwrap!(decoder.decode_from_bytes(&bytes)),
+                    duration
+                );
+            }
+        }
+
+        let mut encoder = DurationEncoder::new(DurationFormat::SecsNanos);
+        let mut decoder = DurationDecoder::new(DurationFormat::SecsNanos);
+        let duration = Duration::new(3, 999_999_999);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(duration));
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&bytes)),
+            duration
+        );
+    }
+
+    #[test]
+    fn duration_decoder_rejects_invalid_nanos() {
+        let mut decoder = DurationDecoder::new(DurationFormat::SecsNanos);
+        let bytes = [0, 0, 0, 0, 0, 0, 0, 1, 0x3b, 0x9a, 0xca, 0x00]; // nanos = 1_000_000_000
+        let error = decoder.decode_from_bytes(&bytes[..]).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn unix_timestamp_works() {
+        // 2018-01-05T18:20:48.123456789Z
+        let time = UNIX_EPOCH + Duration::new(1_515_176_448, 123_456_789);
+
+        let mut encoder = UnixTimestampEncoder::new(DurationFormat::SecsNanos);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(time));
+        assert_eq!(
+            bytes,
+            [0, 0, 0, 0, 0x5a, 0x4f, 0xc2, 0x00, 0x07, 0x5b, 0xcd, 0x15]
+        );
+
+        let mut decoder = UnixTimestampDecoder::new(DurationFormat::SecsNanos);
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), time);
+
+        let mut encoder = UnixTimestampEncoder::new(DurationFormat::Secs);
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(time));
+        let mut decoder = UnixTimestampDecoder::new(DurationFormat::Secs);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_from_bytes(&bytes)),
+            UNIX_EPOCH + Duration::from_secs(1_515_176_448)
+        );
+
+        let error = encoder
+            .encode_into_bytes(UNIX_EPOCH - Duration::from_millis(1))
+            .err()
+            .unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -1,5 +1,5 @@
 //! Encoders and decoders for tuples.
-use crate::{ByteCount, Decode, Encode, Eos, Result, SizedEncode};
+use crate::{ByteCount, Decode, DecodeStatus, Encode, Eos, Result, SizedEncode};
 
 /// Decoder for tuples.
 #[derive(Debug, Default)]
@@ -55,6 +55,13 @@ macro_rules! impl_decode {
             fn is_idle(&self) -> bool {
                 $(self.inner.$i.is_idle())&&*
             }
+
+            fn decode_status(&self) -> DecodeStatus {
+                $(if !self.inner.$i.is_idle() {
+                    return self.inner.$i.decode_status();
+                })*
+                DecodeStatus::Idle
+            }
         }
     }
 }
@@ -65,6 +72,38 @@ impl_decode!([D0, D1, D2, D3, D4], [0, 1, 2, 3, 4]);
 impl_decode!([D0, D1, D2, D3, D4, D5], [0, 1, 2, 3, 4, 5]);
 impl_decode!([D0, D1, D2, D3, D4, D5, D6], [0, 1, 2, 3, 4, 5, 6]);
 impl_decode!([D0, D1, D2, D3, D4, D5, D6, D7], [0, 1, 2, 3, 4, 5, 6, 7]);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8]
+);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9, D10],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]
+);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]
+);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]
+);
+impl_decode!(
+    [D0, D1, D2, D3, D4, D5, D6, D7, D8, D9, D10, D11, D12, D13, D14, D15],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+);
 
 /// Encoder for tuples.
 #[derive(Debug, Default)]
@@ -137,6 +176,38 @@ impl_encode!([E0, E1, E2, E3, E4], [0, 1, 2, 3, 4]);
 impl_encode!([E0, E1, E2, E3, E4, E5], [0, 1, 2, 3, 4, 5]);
 impl_encode!([E0, E1, E2, E3, E4, E5, E6], [0, 1, 2, 3, 4, 5, 6]);
 impl_encode!([E0, E1, E2, E3, E4, E5, E6, E7], [0, 1, 2, 3, 4, 5, 6, 7]);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8]
+);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8, E9],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8, E9, E10],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8, E9, E10, E11],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]
+);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8, E9, E10, E11, E12],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8, E9, E10, E11, E12, E13],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]
+);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8, E9, E10, E11, E12, E13, E14],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]
+);
+impl_encode!(
+    [E0, E1, E2, E3, E4, E5, E6, E7, E8, E9, E10, E11, E12, E13, E14, E15],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+);
 
 #[cfg(test)]
 mod test {
@@ -154,6 +225,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn wide_tuple_decoder_works() {
+        let mut decoder = TupleDecoder::new((
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+            U8Decoder::new(),
+        ));
+        assert_eq!(
+            track_try_unwrap!(decoder.decode_exact(b"0123456789".as_ref())),
+            (b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9')
+        );
+    }
+
     #[test]
     fn tuple_encoder_works() {
         let mut encoder = TupleEncoder::<(U8Encoder, U8Encoder)>::with_item((0, 1)).unwrap();
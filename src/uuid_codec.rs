@@ -0,0 +1,122 @@
+//! `#[cfg(feature = "uuid")]` Encoder and decoder for [uuid] values.
+//!
+//! [uuid]: https://crates.io/crates/uuid
+use crate::bytes::{BytesEncoder, CopyableBytesDecoder};
+use crate::{ByteCount, Decode, Encode, Eos, Result, SizedEncode};
+use uuid::Uuid;
+
+/// Decoder which decodes `Uuid` values.
+///
+/// The 16 bytes of a UUID are read in the standard big-endian (network) order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::uuid_codec::UuidDecoder;
+/// use uuid::Uuid;
+///
+/// let mut decoder = UuidDecoder::new();
+/// let item = decoder.decode_from_bytes(&[0; 16][..]).unwrap();
+/// assert_eq!(item, Uuid::nil());
+/// ```
+#[derive(Debug, Default)]
+pub struct UuidDecoder(CopyableBytesDecoder<[u8; 16]>);
+impl UuidDecoder {
+    /// Makes a new `UuidDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for UuidDecoder {
+    type Item = Uuid;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.0.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track!(self.0.finish_decoding()).map(Uuid::from_bytes)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+
+/// Encoder which encodes `Uuid` values.
+///
+/// The 16 bytes of a UUID are written in the standard big-endian (network) order.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::uuid_codec::UuidEncoder;
+/// use uuid::Uuid;
+///
+/// let mut encoder = UuidEncoder::new();
+/// let bytes = encoder.encode_into_bytes(Uuid::nil()).unwrap();
+/// assert_eq!(bytes, [0; 16]);
+/// ```
+#[derive(Debug, Default)]
+pub struct UuidEncoder(BytesEncoder<[u8; 16]>);
+impl UuidEncoder {
+    /// Makes a new `UuidEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for UuidEncoder {
+    type Item = Uuid;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        track!(self.0.start_encoding(item.into_bytes()))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for UuidEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn uuid_codec_works() {
+        let uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+        assert_eq!(uuid.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+
+        let mut encoder = UuidEncoder::new();
+        let bytes = track_try_unwrap!(encoder.encode_into_bytes(uuid));
+        assert_eq!(
+            bytes,
+            [
+                0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+                0xe0, 0xc8
+            ]
+        );
+
+        let mut decoder = UuidDecoder::new();
+        assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), uuid);
+    }
+}
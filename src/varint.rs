@@ -0,0 +1,530 @@
+//! Encoders and decoders for variable-length integers.
+//!
+//! Integers are represented in the [LEB128] format (i.e., the same format as
+//! the varints of Protocol Buffers), except for `SqliteVarintDecoder` and `SqliteVarintEncoder`.
+//!
+//! [LEB128]: https://en.wikipedia.org/wiki/LEB128
+use crate::bytes::BytesEncoder;
+use crate::{ByteCount, Decode, Encode, Eos, ErrorKind, Result, SizedEncode};
+
+/// The maximum number of bytes of a varint-encoded `u64` value.
+const MAX_VARINT_BYTES: usize = 10;
+
+/// Decoder which decodes LEB128 encoded `u64` values.
+///
+/// # Errors
+///
+/// If the decoded value exceeds `u64::MAX`, an `ErrorKind::InvalidInput` error will be returned.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::varint::VarintDecoder;
+///
+/// let mut decoder = VarintDecoder::new();
+/// let item = decoder.decode_from_bytes(&[0xac, 0x02][..]).unwrap();
+/// assert_eq!(item, 300);
+/// ```
+#[derive(Debug, Default)]
+pub struct VarintDecoder {
+    value: u64,
+    bytes: usize,
+    done: bool,
+}
+impl VarintDecoder {
+    /// Makes a new `VarintDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for VarintDecoder {
+    type Item = u64;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.done {
+            return Ok(0);
+        }
+
+        for (i, &b) in buf.iter().enumerate() {
+            let payload = u64::from(b & 0x7f);
+            track_assert!(
+                self.bytes < MAX_VARINT_BYTES
+                    && (payload << (7 * self.bytes)) >> (7 * self.bytes) == payload,
+                ErrorKind::InvalidInput,
+                "Too large varint"
+            );
+            self.value |= payload << (7 * self.bytes);
+            self.bytes += 1;
+            if b & 0x80 == 0 {
+                self.done = true;
+                return Ok(i + 1);
+            }
+        }
+        track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.bytes);
+        Ok(buf.len())
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.done, ErrorKind::IncompleteDecoding);
+        let value = self.value;
+        *self = Self::default();
+        Ok(value)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.done {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.done
+    }
+}
+
+/// Encoder which encodes `u64` values in the LEB128 format.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::varint::VarintEncoder;
+///
+/// let mut encoder = VarintEncoder::new();
+/// let bytes = encoder.encode_into_bytes(300).unwrap();
+/// assert_eq!(bytes, [0xac, 0x02]);
+/// ```
+#[derive(Debug, Default)]
+pub struct VarintEncoder(BytesEncoder<VarintBytes>);
+impl VarintEncoder {
+    /// Makes a new `VarintEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for VarintEncoder {
+    type Item = u64;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, mut item: Self::Item) -> Result<()> {
+        let mut bytes = VarintBytes::default();
+        loop {
+            let b = (item & 0x7f) as u8;
+            item >>= 7;
+            if item == 0 {
+                bytes.bytes[bytes.len] = b;
+                bytes.len += 1;
+                break;
+            }
+            bytes.bytes[bytes.len] = b | 0x80;
+            bytes.len += 1;
+        }
+        track!(self.0.start_encoding(bytes))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for VarintEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+#[derive(Debug, Default)]
+struct VarintBytes {
+    bytes: [u8; MAX_VARINT_BYTES],
+    len: usize,
+}
+impl AsRef<[u8]> for VarintBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+/// Decoder which decodes zig-zag delta-compressed `i64` sequences.
+///
+/// Each value is represented as a varint of the zig-zag encoded difference from the previous value
+/// (the initial previous value is `0`).
+/// The decoder keeps track of the previous value across items,
+/// so each decoded item is the absolute value of the sequence.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::varint::DeltaVarintDecoder;
+///
+/// let mut decoder = DeltaVarintDecoder::new().collect::<Vec<_>>();
+/// let item = decoder.decode_from_bytes(&[0xc8, 0x01, 0x04, 0x01][..]).unwrap();
+/// assert_eq!(item, [100, 102, 101]);
+/// ```
+#[derive(Debug, Default)]
+pub struct DeltaVarintDecoder {
+    inner: VarintDecoder,
+    prev: i64,
+}
+impl DeltaVarintDecoder {
+    /// Makes a new `DeltaVarintDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the previously decoded value.
+    pub fn prev(&self) -> i64 {
+        self.prev
+    }
+
+    /// Resets the previous value to `0`.
+    ///
+    /// This should be called when a new sequence begins.
+    pub fn reset(&mut self) {
+        self.prev = 0;
+    }
+}
+impl Decode for DeltaVarintDecoder {
+    type Item = i64;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.decode(buf, eos))
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        let delta = zigzag_decode(track!(self.inner.finish_decoding())?);
+        self.prev = self.prev.wrapping_add(delta);
+        Ok(self.prev)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+
+/// Encoder which encodes `i64` sequences as zig-zag delta-compressed varints.
+///
+/// The encoder keeps track of the previously encoded value across items
+/// (the initial previous value is `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::io::IoEncodeExt;
+/// use bytecodec::varint::DeltaVarintEncoder;
+/// use bytecodec::Encode;
+///
+/// let mut encoder = DeltaVarintEncoder::new();
+/// let mut bytes = Vec::new();
+/// for &value in &[100, 102, 101] {
+///     encoder.start_encoding(value).unwrap();
+///     encoder.encode_all(&mut bytes).unwrap();
+/// }
+/// assert_eq!(bytes, [0xc8, 0x01, 0x04, 0x01]);
+/// ```
+#[derive(Debug, Default)]
+pub struct DeltaVarintEncoder {
+    inner: VarintEncoder,
+    prev: i64,
+}
+impl DeltaVarintEncoder {
+    /// Makes a new `DeltaVarintEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the previously encoded value.
+    pub fn prev(&self) -> i64 {
+        self.prev
+    }
+
+    /// Resets the previous value to `0`.
+    ///
+    /// This should be called when a new sequence begins.
+    pub fn reset(&mut self) {
+        self.prev = 0;
+    }
+}
+impl Encode for DeltaVarintEncoder {
+    type Item = i64;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.inner.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let delta = item.wrapping_sub(self.prev);
+        track!(self.inner.start_encoding(zigzag_encode(delta)))?;
+        self.prev = item;
+        Ok(())
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.inner.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.inner.is_idle()
+    }
+}
+impl SizedEncode for DeltaVarintEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.inner.exact_requiring_bytes()
+    }
+}
+
+/// The maximum number of bytes of a SQLite varint.
+const MAX_SQLITE_VARINT_BYTES: usize = 9;
+
+/// Decoder which decodes `u64` values represented in the SQLite varint format.
+///
+/// Unlike LEB128, a SQLite varint is big-endian and consists of at most nine bytes.
+/// Each of the first eight bytes holds seven bits of the value and a continuation flag (the most significant bit),
+/// and the ninth byte (if any) holds all of its eight bits.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::DecodeExt;
+/// use bytecodec::varint::SqliteVarintDecoder;
+///
+/// let mut decoder = SqliteVarintDecoder::new();
+/// let item = decoder.decode_from_bytes(&[0x82, 0x2c][..]).unwrap();
+/// assert_eq!(item, 300);
+/// ```
+#[derive(Debug, Default)]
+pub struct SqliteVarintDecoder {
+    value: u64,
+    bytes: usize,
+    done: bool,
+}
+impl SqliteVarintDecoder {
+    /// Makes a new `SqliteVarintDecoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Decode for SqliteVarintDecoder {
+    type Item = u64;
+
+    fn decode(&mut self, buf: &[u8], eos: Eos) -> Result<usize> {
+        if self.done {
+            return Ok(0);
+        }
+
+        for (i, &b) in buf.iter().enumerate() {
+            self.bytes += 1;
+            if self.bytes == MAX_SQLITE_VARINT_BYTES {
+                self.value = (self.value << 8) | u64::from(b);
+                self.done = true;
+            } else {
+                self.value = (self.value << 7) | u64::from(b & 0x7f);
+                self.done = b & 0x80 == 0;
+            }
+            if self.done {
+                return Ok(i + 1);
+            }
+        }
+        track_assert!(!eos.is_reached(), ErrorKind::UnexpectedEos; self.bytes);
+        Ok(buf.len())
+    }
+
+    fn finish_decoding(&mut self) -> Result<Self::Item> {
+        track_assert!(self.done, ErrorKind::IncompleteDecoding);
+        let value = self.value;
+        *self = Self::default();
+        Ok(value)
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        if self.done {
+            ByteCount::Finite(0)
+        } else {
+            ByteCount::Unknown
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.done
+    }
+}
+
+/// Encoder which encodes `u64` values in the SQLite varint format.
+///
+/// See `SqliteVarintDecoder` for the details of the format.
+///
+/// # Examples
+///
+/// ```
+/// use bytecodec::EncodeExt;
+/// use bytecodec::varint::SqliteVarintEncoder;
+///
+/// let mut encoder = SqliteVarintEncoder::new();
+/// let bytes = encoder.encode_into_bytes(300).unwrap();
+/// assert_eq!(bytes, [0x82, 0x2c]);
+/// ```
+#[derive(Debug, Default)]
+pub struct SqliteVarintEncoder(BytesEncoder<VarintBytes>);
+impl SqliteVarintEncoder {
+    /// Makes a new `SqliteVarintEncoder` instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl Encode for SqliteVarintEncoder {
+    type Item = u64;
+
+    fn encode(&mut self, buf: &mut [u8], eos: Eos) -> Result<usize> {
+        track!(self.0.encode(buf, eos))
+    }
+
+    fn start_encoding(&mut self, item: Self::Item) -> Result<()> {
+        let mut bytes = VarintBytes::default();
+        if item >> 56 != 0 {
+            // The ninth byte holds the least significant eight bits.
+            for i in 0..8 {
+                bytes.bytes[i] = ((item >> (8 + 7 * (7 - i))) as u8 & 0x7f) | 0x80;
+            }
+            bytes.bytes[8] = item as u8;
+            bytes.len = MAX_SQLITE_VARINT_BYTES;
+        } else {
+            let mut n = 1;
+            while n < 8 && item >> (7 * n) != 0 {
+                n += 1;
+            }
+            for i in 0..n {
+                let b = (item >> (7 * (n - 1 - i))) as u8 & 0x7f;
+                bytes.bytes[i] = if i + 1 < n { b | 0x80 } else { b };
+            }
+            bytes.len = n;
+        }
+        track!(self.0.start_encoding(bytes))
+    }
+
+    fn requiring_bytes(&self) -> ByteCount {
+        self.0.requiring_bytes()
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.is_idle()
+    }
+}
+impl SizedEncode for SqliteVarintEncoder {
+    fn exact_requiring_bytes(&self) -> u64 {
+        self.0.exact_requiring_bytes()
+    }
+}
+
+fn zigzag_encode(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+fn zigzag_decode(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::IoEncodeExt;
+    use crate::{DecodeExt, EncodeExt};
+
+    #[test]
+    fn varint_works() {
+        let mut encoder = VarintEncoder::new();
+        let mut decoder = VarintDecoder::new();
+        for &n in &[0, 1, 127, 128, 300, u64::MAX] {
+            let bytes = track_try_unwrap!(encoder.encode_into_bytes(n));
+            assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), n);
+        }
+        assert_eq!(
+            track_try_unwrap!(encoder.encode_into_bytes(u64::MAX)).len(),
+            MAX_VARINT_BYTES
+        );
+
+        let mut too_large = vec![0xff; 9];
+        too_large.push(0x02);
+        let error = decoder.decode_from_bytes(&too_large).err().unwrap();
+        assert_eq!(*error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn delta_varint_works() {
+        let values = [100, 102, 101, 200];
+
+        let mut encoder = DeltaVarintEncoder::new();
+        let mut bytes = Vec::new();
+        for &value in &values {
+            track_try_unwrap!(encoder.start_encoding(value));
+            track_try_unwrap!(encoder.encode_all(&mut bytes));
+        }
+        assert_eq!(bytes, [0xc8, 0x01, 0x04, 0x01, 0xc6, 0x01]);
+
+        let mut decoder = DeltaVarintDecoder::new();
+        let mut decoded = Vec::new();
+        let mut offset = 0;
+        for chunk in bytes.chunks(1) {
+            offset += track_try_unwrap!(decoder.decode(chunk, Eos::new(false)));
+            if decoder.is_idle() {
+                decoded.push(track_try_unwrap!(decoder.finish_decoding()));
+            }
+        }
+        assert_eq!(offset, bytes.len());
+        assert_eq!(decoded, values);
+        assert_eq!(decoder.prev(), 200);
+
+        assert_eq!(zigzag_encode(i64::MIN), u64::MAX);
+        assert_eq!(zigzag_decode(u64::MAX), i64::MIN);
+    }
+
+    #[test]
+    fn sqlite_varint_works() {
+        let cases: &[(u64, &[u8])] = &[
+            (0, &[0x00]),
+            (0x7f, &[0x7f]),
+            (0x80, &[0x81, 0x00]),
+            (0x3fff, &[0xff, 0x7f]),
+            (0x4000, &[0x81, 0x80, 0x00]),
+            (
+                0x00ff_ffff_ffff_ffff,
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f],
+            ),
+            (
+                0x0100_0000_0000_0000,
+                &[0x80, 0xc0, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00],
+            ),
+            (u64::MAX, &[0xff; 9]),
+        ];
+
+        let mut encoder = SqliteVarintEncoder::new();
+        let mut decoder = SqliteVarintDecoder::new();
+        for &(n, expected) in cases {
+            let bytes = track_try_unwrap!(encoder.encode_into_bytes(n));
+            assert_eq!(bytes, expected, "n={:#x}", n);
+            assert_eq!(track_try_unwrap!(decoder.decode_from_bytes(&bytes)), n);
+        }
+
+        // The ninth byte is used as is even if its most significant bit is set.
+        let mut input = vec![0x80; 8];
+        input.push(0xff);
+        input.push(0x01);
+        assert_eq!(
+            track_try_unwrap!(decoder.decode(&input, Eos::new(false))),
+            9
+        );
+        assert_eq!(track_try_unwrap!(decoder.finish_decoding()), 0xff);
+    }
+}